@@ -0,0 +1,73 @@
+use crate::{
+    menu::{GameStartedState, MenuState},
+    AppState,
+};
+
+use bevy::prelude::*;
+
+pub mod components;
+use components::*;
+
+pub mod resources;
+use resources::*;
+
+pub mod systems;
+use systems::*;
+
+pub struct EditorPlugin;
+
+impl Plugin for EditorPlugin {
+    fn build(&self, app: &mut App) {
+        // The editor is only available in dev builds or if it is requested on the command line
+        if !(cfg!(debug_assertions) || editor_requested_on_command_line()) {
+            return;
+        }
+        app
+            // Register types for debug
+            .register_type::<EditorUiElement>()
+            .register_type::<EditorStatusText>()
+            .register_type::<RoutePreview>()
+            // Add editor State
+            .init_state::<EditorState>()
+            // Initialize Resources
+            .init_resource::<MapEditor>()
+            .init_resource::<RoutePreview>()
+            .init_resource::<EditedMapPreview>()
+            // Spawn and despawn the editor ui
+            .add_systems(OnEnter(EditorState::Open), spawn_editor_ui)
+            .add_systems(
+                OnExit(EditorState::Open),
+                crate::menu::systems::despawn_entities_with_specific_component::<EditorUiElement>,
+            )
+            // Add update systems
+            .add_systems(
+                Update,
+                toggle_editor.run_if(
+                    in_state(AppState::Menu)
+                        .and_then(in_state(GameStartedState::GameHasNotStarted))
+                        .and_then(in_state(MenuState::Main).or_else(in_state(EditorState::Open))),
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_editor_keyboard_input,
+                    handle_editor_mouse_input,
+                    apply_edited_map.run_if(resource_changed::<MapEditor>),
+                    advance_route_preview,
+                    draw_editor_gizmos,
+                    update_editor_status_text,
+                )
+                    .chain()
+                    .run_if(in_state(EditorState::Open)),
+            );
+    }
+}
+
+/// State of the map editor
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States)]
+pub enum EditorState {
+    #[default]
+    Closed,
+    Open,
+}
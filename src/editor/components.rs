@@ -0,0 +1,11 @@
+use super::*;
+
+/// Component used to tag the ui elements of the editor
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct EditorUiElement;
+
+/// Component used to tag the text showing the current state of the editor
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct EditorStatusText;
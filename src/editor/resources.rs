@@ -0,0 +1,47 @@
+use super::*;
+
+use crate::game::map::resources::{LoadedMap, Map};
+
+/// Resource holding the map that is being edited and the state of the editor
+#[derive(Resource, Default)]
+pub struct MapEditor {
+    /// Copy of the selected map that is edited. Is set when the editor is opened
+    pub map: Option<Map>,
+    pub selected_route: usize,
+    /// Index of the route whose branch from the selected route is edited
+    pub branch_target: usize,
+    pub tool: EditorTool,
+    pub dragged_object: Option<DraggedObject>,
+}
+
+/// Resource holding the edited map as it is loaded in the game. The editor draws and previews the
+/// routes from it, such that the map of the game is only changed by exporting
+#[derive(Resource, Default)]
+pub struct EditedMapPreview {
+    pub map: LoadedMap,
+}
+
+/// What a left click places in the editor
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorTool {
+    #[default]
+    Checkpoints,
+    Trees,
+    Castle,
+}
+
+/// An object of the map that is being dragged with the mouse
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DraggedObject {
+    Checkpoint { route: usize, index: usize },
+    Tree(usize),
+    Castle,
+}
+
+/// Resource for tracking the preview enemy walking along the selected route
+#[derive(Reflect, Resource, Default)]
+#[reflect(Resource)]
+pub struct RoutePreview {
+    /// Distance the preview enemy walked along the route. None if there is no preview
+    pub distance: Option<f32>,
+}
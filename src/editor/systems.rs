@@ -0,0 +1,543 @@
+use bevy::window::PrimaryWindow;
+
+use super::*;
+use crate::game::map::resources::{
+    BranchDescription, LoadedMap, Map, MapHandles, Route, RouteDescription, RouteSmoothing,
+    SelectedMap,
+};
+use crate::storage::write_save_file;
+
+/// Command line argument for enabling the editor in release builds
+const EDITOR_COMMAND_LINE_ARGUMENT: &str = "--editor";
+/// Distance in pixels within which a click grabs a checkpoint, tree or the castle
+const PICK_RADIUS: f32 = 20.0;
+/// Speed of the preview enemy in pixels per second
+const PREVIEW_SPEED: f32 = 150.0;
+/// Size of the castle sprite in pixels
+const CASTLE_SIZE: Vec2 = Vec2::new(320.0, 256.0);
+/// Color of the selected route
+const SELECTED_ROUTE_COLOR: Color = Color::ORANGE_RED;
+/// Color of the route the selected route branches into when edited
+const BRANCH_TARGET_COLOR: Color = Color::FUCHSIA;
+/// Weight of a newly added branch
+const DEFAULT_BRANCH_WEIGHT: f32 = 1.0;
+/// Change of the weight of a branch per key press. Also the lowest weight of a branch
+const BRANCH_WEIGHT_STEP: f32 = 0.5;
+/// Color of the other routes
+const ROUTE_COLOR: Color = Color::AZURE;
+/// Color of the tree and castle markers
+const DECORATION_COLOR: Color = Color::LIME_GREEN;
+/// Color of the preview enemy
+const PREVIEW_COLOR: Color = Color::GOLD;
+/// Controls shown in the editor
+const EDITOR_CONTROLS: &str = "Map editor (F2 to close)
+1 / 2 / 3: place checkpoints / trees / castle
+Left click: place or drag, right click: remove
+Tab: next route, N: new route, Delete: remove route
+S: toggle smoothing, X: toggle spawning on route
+B: boss walks this route
+J: next branch target, K: toggle branch to it
+[ / ]: lower / raise weight of the branch
+P: preview enemy, E: export map
+";
+
+/// Returns whether the editor was requested on the command line
+pub fn editor_requested_on_command_line() -> bool {
+    std::env::args().any(|arg| arg == EDITOR_COMMAND_LINE_ARGUMENT)
+}
+
+/// Opens and closes the editor when F2 is pressed. The selected map is copied into the editor
+pub fn toggle_editor(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    editor_state: Res<State<EditorState>>,
+    mut next_editor_state: ResMut<NextState<EditorState>>,
+    mut next_menu_state: ResMut<NextState<MenuState>>,
+    mut map_editor: ResMut<MapEditor>,
+    map_handles: Res<MapHandles>,
+    selected_map: Res<SelectedMap>,
+    maps: Res<Assets<Map>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F2) {
+        return;
+    }
+    match editor_state.get() {
+        EditorState::Closed => {
+            let Some(map) = map_handles
+                .handles
+                .get(selected_map.index)
+                .and_then(|handle| maps.get(handle))
+            else {
+                warn!("The selected map is not loaded yet");
+                return;
+            };
+            *map_editor = MapEditor {
+                map: Some(map.clone()),
+                ..default()
+            };
+            next_editor_state.set(EditorState::Open);
+            next_menu_state.set(MenuState::NotInTheMenu);
+        }
+        EditorState::Open => {
+            next_editor_state.set(EditorState::Closed);
+            next_menu_state.set(MenuState::Main);
+        }
+    }
+}
+
+/// Spawns the text with the controls and the state of the editor
+pub fn spawn_editor_ui(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_sections([
+            TextSection::new(
+                EDITOR_CONTROLS,
+                TextStyle {
+                    font_size: 24.0,
+                    ..default()
+                },
+            ),
+            TextSection::new(
+                "",
+                TextStyle {
+                    font_size: 24.0,
+                    color: SELECTED_ROUTE_COLOR,
+                    ..default()
+                },
+            ),
+        ])
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            margin: UiRect::all(Val::Percent(1.0)),
+            ..default()
+        }),
+        EditorUiElement,
+        EditorStatusText,
+        Name::new("Editor Controls"),
+    ));
+}
+
+/// Updates the text showing the selected route and tool
+pub fn update_editor_status_text(
+    map_editor: Res<MapEditor>,
+    mut status_text_query: Query<&mut Text, With<EditorStatusText>>,
+) {
+    let Some(map) = &map_editor.map else {
+        return;
+    };
+    let route_name = map
+        .routes
+        .get(map_editor.selected_route)
+        .map_or("none", |route| route.name.as_str());
+    let branch_target_name = map
+        .routes
+        .get(map_editor.branch_target)
+        .map_or("none", |route| route.name.as_str());
+    let branch_weight = map
+        .routes
+        .get(map_editor.selected_route)
+        .and_then(|route| {
+            route
+                .branches
+                .iter()
+                .find(|branch| branch.route == branch_target_name)
+        })
+        .map_or("no branch".to_string(), |branch| {
+            format!("weight {}", branch.weight)
+        });
+    for mut text in status_text_query.iter_mut() {
+        text.sections[1].value = format!(
+            "Map: {} | Route: {} | Branch to: {} ({}) | Tool: {:?}",
+            map.name, route_name, branch_target_name, branch_weight, map_editor.tool
+        );
+    }
+}
+
+/// Handles the keyboard shortcuts of the editor
+pub fn handle_editor_keyboard_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut map_editor: ResMut<MapEditor>,
+    mut route_preview: ResMut<RoutePreview>,
+) {
+    // Only access the editor mutably on key presses in order to not rebuild the map every frame
+    if keyboard_input.get_just_pressed().next().is_none() {
+        return;
+    }
+    let map_editor = &mut *map_editor;
+    let Some(map) = &mut map_editor.map else {
+        return;
+    };
+    for key in keyboard_input.get_just_pressed() {
+        match key {
+            KeyCode::Digit1 => map_editor.tool = EditorTool::Checkpoints,
+            KeyCode::Digit2 => map_editor.tool = EditorTool::Trees,
+            KeyCode::Digit3 => map_editor.tool = EditorTool::Castle,
+            KeyCode::Tab if !map.routes.is_empty() => {
+                map_editor.selected_route = (map_editor.selected_route + 1) % map.routes.len();
+                route_preview.distance = None;
+            }
+            KeyCode::KeyN => {
+                map.routes.push(new_route(map));
+                map_editor.selected_route = map.routes.len() - 1;
+                route_preview.distance = None;
+            }
+            KeyCode::Delete | KeyCode::Backspace
+                if map_editor.selected_route < map.routes.len() =>
+            {
+                remove_route(map, map_editor.selected_route);
+                map_editor.dragged_object = None;
+                map_editor.selected_route = map_editor.selected_route.saturating_sub(1);
+                map_editor.branch_target = map_editor
+                    .branch_target
+                    .min(map.routes.len().saturating_sub(1));
+                route_preview.distance = None;
+            }
+            KeyCode::KeyS => {
+                if let Some(route) = map.routes.get_mut(map_editor.selected_route) {
+                    route.smoothing = match route.smoothing {
+                        RouteSmoothing::None => RouteSmoothing::CatmullRom,
+                        RouteSmoothing::CatmullRom => RouteSmoothing::None,
+                    };
+                }
+            }
+            KeyCode::KeyX => {
+                if let Some(route) = map.routes.get_mut(map_editor.selected_route) {
+                    route.spawn = !route.spawn;
+                }
+            }
+            KeyCode::KeyB => {
+                if let Some(route) = map.routes.get(map_editor.selected_route) {
+                    map.boss_route = route.name.clone();
+                }
+            }
+            KeyCode::KeyJ if !map.routes.is_empty() => {
+                map_editor.branch_target = (map_editor.branch_target + 1) % map.routes.len();
+            }
+            KeyCode::KeyK => {
+                toggle_branch(map, map_editor.selected_route, map_editor.branch_target)
+            }
+            KeyCode::BracketLeft | KeyCode::BracketRight => {
+                let step = if *key == KeyCode::BracketLeft {
+                    -BRANCH_WEIGHT_STEP
+                } else {
+                    BRANCH_WEIGHT_STEP
+                };
+                if let Some(branch) =
+                    branch_mut(map, map_editor.selected_route, map_editor.branch_target)
+                {
+                    branch.weight = (branch.weight + step).max(BRANCH_WEIGHT_STEP);
+                }
+            }
+            KeyCode::KeyP => {
+                route_preview.distance = match route_preview.distance {
+                    Some(_) => None,
+                    None => Some(0.0),
+                };
+            }
+            KeyCode::KeyE => export_map(map),
+            _ => {}
+        }
+    }
+}
+
+/// Handles placing, dragging and removing checkpoints, trees and the castle with the mouse
+pub fn handle_editor_mouse_input(
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    mut map_editor: ResMut<MapEditor>,
+) {
+    // Only access the editor mutably on mouse input in order to not rebuild the map every frame
+    if !(mouse_input.pressed(MouseButton::Left)
+        || mouse_input.just_released(MouseButton::Left)
+        || mouse_input.just_pressed(MouseButton::Right))
+    {
+        return;
+    }
+    let Some(cursor_position) = cursor_world_position(&window_query, &camera_query) else {
+        return;
+    };
+    let map_editor = &mut *map_editor;
+    let Some(map) = &mut map_editor.map else {
+        return;
+    };
+    let cursor = (cursor_position.x, cursor_position.y);
+
+    if mouse_input.just_pressed(MouseButton::Left) {
+        map_editor.dragged_object = pick_object(map, cursor_position);
+        match (map_editor.dragged_object, map_editor.tool) {
+            (Some(DraggedObject::Checkpoint { route, .. }), _) => {
+                map_editor.selected_route = route;
+            }
+            (Some(_), _) => {}
+            (None, EditorTool::Checkpoints) => {
+                if map.routes.is_empty() {
+                    map.routes.push(new_route(map));
+                    map_editor.selected_route = 0;
+                }
+                let route = &mut map.routes[map_editor.selected_route];
+                route.checkpoints.push(cursor);
+                map_editor.dragged_object = Some(DraggedObject::Checkpoint {
+                    route: map_editor.selected_route,
+                    index: route.checkpoints.len() - 1,
+                });
+            }
+            (None, EditorTool::Trees) => {
+                map.tree_positions.push(cursor);
+                map_editor.dragged_object = Some(DraggedObject::Tree(map.tree_positions.len() - 1));
+            }
+            (None, EditorTool::Castle) => {
+                map.castle_position = cursor;
+                map_editor.dragged_object = Some(DraggedObject::Castle);
+            }
+        }
+    } else if mouse_input.pressed(MouseButton::Left) {
+        match map_editor.dragged_object {
+            Some(DraggedObject::Checkpoint { route, index }) => {
+                if let Some(checkpoint) = map
+                    .routes
+                    .get_mut(route)
+                    .and_then(|route| route.checkpoints.get_mut(index))
+                {
+                    *checkpoint = cursor;
+                }
+            }
+            Some(DraggedObject::Tree(index)) => {
+                if let Some(tree) = map.tree_positions.get_mut(index) {
+                    *tree = cursor;
+                }
+            }
+            Some(DraggedObject::Castle) => map.castle_position = cursor,
+            None => {}
+        }
+    } else if map_editor.dragged_object.is_some() {
+        map_editor.dragged_object = None;
+    }
+
+    if mouse_input.just_pressed(MouseButton::Right) {
+        match pick_object(map, cursor_position) {
+            Some(DraggedObject::Checkpoint { route, index }) => {
+                map.routes[route].checkpoints.remove(index);
+                map_editor.dragged_object = None;
+            }
+            Some(DraggedObject::Tree(index)) => {
+                map.tree_positions.remove(index);
+                map_editor.dragged_object = None;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns an empty route with a name that no other route of the map has
+fn new_route(map: &Map) -> RouteDescription {
+    let name = (1..)
+        .map(|number| format!("route_{number}"))
+        .find(|name| map.routes.iter().all(|route| route.name != *name))
+        .expect("There should be an unused route name");
+    RouteDescription {
+        name,
+        checkpoints: Vec::new(),
+        smoothing: RouteSmoothing::None,
+        spawn: true,
+        branches: Vec::new(),
+    }
+}
+
+/// Removes the route with the given index together with the branches into it. If the boss walked
+/// the route, it walks the first remaining route instead
+fn remove_route(map: &mut Map, index: usize) {
+    let removed_route = map.routes.remove(index);
+    for route in &mut map.routes {
+        route
+            .branches
+            .retain(|branch| branch.route != removed_route.name);
+    }
+    if map.boss_route == removed_route.name {
+        map.boss_route = map
+            .routes
+            .first()
+            .map(|route| route.name.clone())
+            .unwrap_or_default();
+    }
+}
+
+/// Adds a branch from the given route into the target route or removes it if it exists already
+fn toggle_branch(map: &mut Map, route: usize, target: usize) {
+    let Some(target_name) = map.routes.get(target).map(|route| route.name.clone()) else {
+        return;
+    };
+    let Some(route) = map.routes.get_mut(route) else {
+        return;
+    };
+    match route
+        .branches
+        .iter()
+        .position(|branch| branch.route == target_name)
+    {
+        Some(branch) => {
+            route.branches.remove(branch);
+        }
+        None => route.branches.push(BranchDescription {
+            route: target_name,
+            weight: DEFAULT_BRANCH_WEIGHT,
+        }),
+    }
+}
+
+/// Returns the branch from the given route into the target route if it exists
+fn branch_mut(map: &mut Map, route: usize, target: usize) -> Option<&mut BranchDescription> {
+    let target_name = map.routes.get(target)?.name.clone();
+    map.routes
+        .get_mut(route)?
+        .branches
+        .iter_mut()
+        .find(|branch| branch.route == target_name)
+}
+
+/// Returns the position of the cursor in world coordinates if it is inside the window
+pub fn cursor_world_position(
+    window_query: &Query<&Window, With<PrimaryWindow>>,
+    camera_query: &Query<(&Camera, &GlobalTransform)>,
+) -> Option<Vec2> {
+    let window = window_query.get_single().ok()?;
+    let (camera, camera_transform) = camera_query.get_single().ok()?;
+    camera.viewport_to_world_2d(camera_transform, window.cursor_position()?)
+}
+
+/// Returns the checkpoint, tree or castle closest to the given position within the pick radius
+fn pick_object(map: &Map, position: Vec2) -> Option<DraggedObject> {
+    let checkpoints = map
+        .routes
+        .iter()
+        .enumerate()
+        .flat_map(|(route, description)| {
+            description
+                .checkpoints
+                .iter()
+                .enumerate()
+                .map(move |(index, &checkpoint)| {
+                    (DraggedObject::Checkpoint { route, index }, checkpoint)
+                })
+        });
+    let trees = map
+        .tree_positions
+        .iter()
+        .enumerate()
+        .map(|(index, &tree)| (DraggedObject::Tree(index), tree));
+    let castle = std::iter::once((DraggedObject::Castle, map.castle_position));
+
+    checkpoints
+        .chain(trees)
+        .chain(castle)
+        .map(|(object, point)| (object, Vec2::from(point).distance(position)))
+        .filter(|(_, distance)| *distance < PICK_RADIUS)
+        .min_by(|(_, first_distance), (_, second_distance)| {
+            first_distance.total_cmp(second_distance)
+        })
+        .map(|(object, _)| object)
+}
+
+/// Loads the edited map into the preview such that the routes are drawn as they would be walked in
+/// the game. The map of the game stays untouched
+pub fn apply_edited_map(
+    map_editor: Res<MapEditor>,
+    mut edited_map_preview: ResMut<EditedMapPreview>,
+) {
+    if let Some(map) = &map_editor.map {
+        edited_map_preview.map = LoadedMap::from_map(map);
+    }
+}
+
+/// Returns the route of the preview that was loaded from the route with the given index of the edited
+/// map. Routes with fewer than two checkpoints are not loaded
+fn preview_route<'a>(
+    map: &Map,
+    edited_map_preview: &'a EditedMapPreview,
+    index: usize,
+) -> Option<&'a Route> {
+    let description = map.routes.get(index)?;
+    edited_map_preview.map.route_named(&description.name)
+}
+
+/// Lets the preview enemy walk along the selected route and restarts it at the end
+pub fn advance_route_preview(
+    time: Res<Time>,
+    map_editor: Res<MapEditor>,
+    edited_map_preview: Res<EditedMapPreview>,
+    mut route_preview: ResMut<RoutePreview>,
+) {
+    let (Some(distance), Some(map)) = (route_preview.distance, &map_editor.map) else {
+        return;
+    };
+    let route_length = preview_route(map, &edited_map_preview, map_editor.selected_route)
+        .map_or(0.0, Route::length);
+    let distance = distance + PREVIEW_SPEED * time.delta_seconds();
+    route_preview.distance = Some(if distance > route_length {
+        0.0
+    } else {
+        distance
+    });
+}
+
+/// Draws the routes, checkpoints, trees, castle and the preview enemy
+pub fn draw_editor_gizmos(
+    mut gizmos: Gizmos,
+    map_editor: Res<MapEditor>,
+    edited_map_preview: Res<EditedMapPreview>,
+    route_preview: Res<RoutePreview>,
+) {
+    let Some(map) = &map_editor.map else {
+        return;
+    };
+    for (index, description) in map.routes.iter().enumerate() {
+        let color = if index == map_editor.selected_route {
+            SELECTED_ROUTE_COLOR
+        } else if index == map_editor.branch_target {
+            BRANCH_TARGET_COLOR
+        } else {
+            ROUTE_COLOR
+        };
+        if let Some(route) = preview_route(map, &edited_map_preview, index) {
+            gizmos.linestrip_2d(route.points.iter().copied(), color);
+        }
+        for &checkpoint in &description.checkpoints {
+            gizmos.circle_2d(Vec2::from(checkpoint), PICK_RADIUS * 0.5, color);
+        }
+    }
+    for &tree in &map.tree_positions {
+        gizmos.circle_2d(Vec2::from(tree), PICK_RADIUS, DECORATION_COLOR);
+    }
+    gizmos.rect_2d(
+        Vec2::from(map.castle_position),
+        0.0,
+        CASTLE_SIZE,
+        DECORATION_COLOR,
+    );
+    gizmos.circle_2d(
+        Vec2::from(map.castle_position),
+        PICK_RADIUS,
+        DECORATION_COLOR,
+    );
+
+    if let (Some(distance), Some(route)) = (
+        route_preview.distance,
+        preview_route(map, &edited_map_preview, map_editor.selected_route),
+    ) {
+        gizmos.circle_2d(route.position_at(distance), PICK_RADIUS, PREVIEW_COLOR);
+    }
+}
+
+/// Writes the map into the saves directory in the map asset format and logs it
+fn export_map(map: &Map) {
+    let file_name = format!("{}.map.toml", map.name.to_lowercase().replace(' ', "_"));
+    let contents = map_to_toml(map);
+    write_save_file(&file_name, &contents);
+    info!("Exported map to saves/{file_name}:\n{contents}");
+}
+
+/// Returns the map in the map asset format
+fn map_to_toml(map: &Map) -> String {
+    let toml = toml::to_string_pretty(map).expect("Map should be serializable to toml");
+    format!(
+        "# Coordinates are in pixels of the background image with the origin at its center\n{toml}"
+    )
+}
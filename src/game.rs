@@ -19,6 +19,36 @@ use effects::EffectsPlugin;
 mod boss;
 use boss::BossPlugin;
 
+pub mod randomness;
+use randomness::RandomnessPlugin;
+
+pub mod daily_challenge;
+use daily_challenge::DailyChallengePlugin;
+
+pub mod map;
+use map::MapPlugin;
+
+pub mod shop;
+use shop::ShopPlugin;
+
+mod towers;
+use towers::TowersPlugin;
+
+pub mod power_ups;
+use power_ups::PowerUpsPlugin;
+
+pub mod waves;
+use waves::WavesPlugin;
+
+pub mod campaign;
+use campaign::CampaignPlugin;
+
+pub mod adaptive_difficulty;
+use adaptive_difficulty::AdaptiveDifficultyPlugin;
+
+mod stress_test;
+use stress_test::StressTestPlugin;
+
 use bevy::prelude::*;
 
 use crate::AppState;
@@ -40,6 +70,8 @@ impl Plugin for GamePlugin {
             .init_state::<RoundState>()
             .init_state::<LoosingState>()
             // Add own plugins
+            .add_plugins(RandomnessPlugin)
+            .add_plugins(MapPlugin)
             .add_plugins(EnemiesPlugin)
             .add_plugins(CastlePlugin)
             .add_plugins(RoundsAndIndicatorsPlugin)
@@ -47,6 +79,14 @@ impl Plugin for GamePlugin {
             .add_plugins(DecorationsPlugin)
             .add_plugins(HUDPlugin)
             .add_plugins(BossPlugin)
+            .add_plugins(DailyChallengePlugin)
+            .add_plugins(ShopPlugin)
+            .add_plugins(TowersPlugin)
+            .add_plugins(PowerUpsPlugin)
+            .add_plugins(WavesPlugin)
+            .add_plugins(CampaignPlugin)
+            .add_plugins(AdaptiveDifficultyPlugin)
+            .add_plugins(StressTestPlugin)
             // Configure System Sets
             .configure_sets(
                 Update,
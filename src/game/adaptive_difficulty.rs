@@ -0,0 +1,39 @@
+pub mod resources;
+use resources::*;
+
+pub mod systems;
+use systems::*;
+
+use crate::menu::GameStartedState;
+
+use super::*;
+
+pub struct AdaptiveDifficultyPlugin;
+
+impl Plugin for AdaptiveDifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Register types for debug
+            .register_type::<AdaptiveDifficulty>()
+            // Initialize Resources
+            .init_resource::<AdaptiveDifficulty>()
+            // Adjust the next round once the previous one is over
+            .add_systems(
+                OnEnter(RoundState::InBetweenRounds),
+                record_round_performance.run_if(
+                    adaptive_difficulty_is_active
+                        .and_then(in_state(GameStartedState::GameHasStarted)),
+                ),
+            )
+            // The boss is spawned with the adjusted speed of the round
+            .add_systems(
+                OnEnter(RoundState::InRound),
+                apply_adaptive_difficulty
+                    .after(rounds_and_indicators::systems::increase_round_difficulty)
+                    .before(boss::systems::spawn_boss)
+                    .run_if(adaptive_difficulty_is_active),
+            )
+            // Add update systems
+            .add_systems(Update, reset_adaptive_difficulty_on_restart);
+    }
+}
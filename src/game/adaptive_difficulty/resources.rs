@@ -0,0 +1,244 @@
+use std::fmt;
+
+use super::*;
+use crate::game::waves::resources::WordTier;
+
+/// Number of rounds the performance of the player is averaged over
+pub const ROLLING_WINDOW: usize = 3;
+/// Highest and lowest adjustment level
+pub const MAX_ADJUSTMENT_LEVEL: i32 = 4;
+/// Change of the number of enemies per adjustment level
+const ENEMY_COUNT_STEP: f32 = 0.15;
+/// Change of the base speed of enemies per adjustment level
+const SPEED_STEP: f32 = 0.08;
+/// Change of the spawn interval of enemies per adjustment level
+const SPAWN_INTERVAL_STEP: f32 = 0.08;
+/// Accuracy in percent below which the player is struggling
+const STRUGGLING_ACCURACY: f64 = 85.0;
+/// Accuracy in percent above which the player is cruising if the other conditions hold as well
+const CRUISING_ACCURACY: f64 = 95.0;
+/// Share of the target wpm of the difficulty below which the player is struggling
+const STRUGGLING_WPM_SHARE: f64 = 0.6;
+/// Lives lost per round on average from which on the player is struggling
+const STRUGGLING_LIVES_LOST_PER_ROUND: f64 = 0.5;
+
+/// How the player performed in a round
+#[derive(Reflect, Default, Debug, Clone, Copy)]
+pub struct RoundPerformance {
+    pub wpm: f64,
+    pub accuracy: f64,
+    pub lives_lost: u32,
+}
+
+/// Where the recent performance of the player lies compared to the target challenge band
+#[derive(Reflect, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeBand {
+    /// Losing lives, many mistakes or slow typing. The game gets easier
+    Struggling,
+    #[default]
+    OnTarget,
+    /// No lives lost, few mistakes and fast typing. The game gets harder
+    Cruising,
+}
+
+/// Resource for the adaptive mode, in which the rounds of the endless mode get harder or easier
+/// depending on how the player performed in the last rounds
+#[derive(Reflect, Resource, Default)]
+#[reflect(Resource)]
+pub struct AdaptiveDifficulty {
+    /// Whether the adaptive mode is turned on. Kept on restart
+    pub enabled: bool,
+    /// Performance in the last rounds, the latest last
+    pub recent_rounds: Vec<RoundPerformance>,
+    pub band: ChallengeBand,
+    /// Steps the game is made harder (positive) or easier (negative) than the difficulty alone
+    pub level: i32,
+}
+
+impl AdaptiveDifficulty {
+    /// Adds the performance of a round and moves the level one step if the average over the last
+    /// rounds is outside of the target challenge band
+    pub fn record_round(&mut self, performance: RoundPerformance, target_wpm: f64) {
+        self.recent_rounds.push(performance);
+        if self.recent_rounds.len() > ROLLING_WINDOW {
+            self.recent_rounds.remove(0);
+        }
+        let average = self.average();
+        let lives_lost_per_round =
+            average.lives_lost as f64 / self.recent_rounds.len().max(1) as f64;
+        self.band = if lives_lost_per_round >= STRUGGLING_LIVES_LOST_PER_ROUND
+            || average.accuracy < STRUGGLING_ACCURACY
+            || average.wpm < target_wpm * STRUGGLING_WPM_SHARE
+        {
+            ChallengeBand::Struggling
+        } else if average.lives_lost == 0
+            && average.accuracy >= CRUISING_ACCURACY
+            && average.wpm >= target_wpm
+        {
+            ChallengeBand::Cruising
+        } else {
+            ChallengeBand::OnTarget
+        };
+        self.level = match self.band {
+            ChallengeBand::Struggling => self.level - 1,
+            ChallengeBand::OnTarget => self.level,
+            ChallengeBand::Cruising => self.level + 1,
+        }
+        .clamp(-MAX_ADJUSTMENT_LEVEL, MAX_ADJUSTMENT_LEVEL);
+    }
+
+    /// Returns the average wpm and accuracy and the total lives lost over the last rounds
+    pub fn average(&self) -> RoundPerformance {
+        let rounds = self.recent_rounds.len().max(1) as f64;
+        RoundPerformance {
+            wpm: self
+                .recent_rounds
+                .iter()
+                .map(|round| round.wpm)
+                .sum::<f64>()
+                / rounds,
+            accuracy: self
+                .recent_rounds
+                .iter()
+                .map(|round| round.accuracy)
+                .sum::<f64>()
+                / rounds,
+            lives_lost: self
+                .recent_rounds
+                .iter()
+                .map(|round| round.lives_lost)
+                .sum(),
+        }
+    }
+
+    /// Factor the number of enemies of endless rounds is multiplied with
+    pub fn enemy_count_factor(&self) -> f32 {
+        1.0 + self.level as f32 * ENEMY_COUNT_STEP
+    }
+
+    /// Factor the base speed of enemies is multiplied with
+    pub fn speed_factor(&self) -> f32 {
+        1.0 + self.level as f32 * SPEED_STEP
+    }
+
+    /// Factor the spawn interval of enemies is multiplied with
+    pub fn spawn_interval_factor(&self) -> f32 {
+        1.0 - self.level as f32 * SPAWN_INTERVAL_STEP
+    }
+
+    /// Returns the words enemies of endless rounds get
+    pub fn word_tier(&self) -> WordTier {
+        match self.level {
+            i32::MIN..=-2 => WordTier::Short,
+            2..=3 => WordTier::Medium,
+            4..=i32::MAX => WordTier::Long,
+            _ => WordTier::Any,
+        }
+    }
+}
+
+impl fmt::Display for AdaptiveDifficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let average = self.average();
+        write!(
+            f,
+            "Adaptive difficulty: {} over the last {} rounds ({:.0} wpm, {:.1}% accuracy, {} lives lost)\n\
+             Next round at level {:+}: enemies {:.0}%, speed {:.0}%, spawn interval {:.0}%, {} words",
+            match self.band {
+                ChallengeBand::Struggling => "struggling",
+                ChallengeBand::OnTarget => "on target",
+                ChallengeBand::Cruising => "cruising",
+            },
+            self.recent_rounds.len(),
+            average.wpm,
+            average.accuracy,
+            average.lives_lost,
+            self.level,
+            self.enemy_count_factor() * 100.0,
+            self.speed_factor() * 100.0,
+            self.spawn_interval_factor() * 100.0,
+            match self.word_tier() {
+                WordTier::Any => "any",
+                WordTier::Short => "short",
+                WordTier::Medium => "medium",
+                WordTier::Long => "long",
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TARGET_WPM: f64 = 60.0;
+
+    fn performance(wpm: f64, accuracy: f64, lives_lost: u32) -> RoundPerformance {
+        RoundPerformance {
+            wpm,
+            accuracy,
+            lives_lost,
+        }
+    }
+
+    #[test]
+    fn cruising_rounds_make_the_game_harder() {
+        let mut adaptive_difficulty = AdaptiveDifficulty::default();
+        adaptive_difficulty.record_round(performance(70.0, 97.0, 0), TARGET_WPM);
+
+        assert_eq!(adaptive_difficulty.band, ChallengeBand::Cruising);
+        assert_eq!(adaptive_difficulty.level, 1);
+    }
+
+    #[test]
+    fn struggling_rounds_make_the_game_easier() {
+        let mut adaptive_difficulty = AdaptiveDifficulty::default();
+        adaptive_difficulty.record_round(performance(70.0, 97.0, 1), TARGET_WPM);
+        assert_eq!(adaptive_difficulty.band, ChallengeBand::Struggling);
+        assert_eq!(adaptive_difficulty.level, -1);
+
+        adaptive_difficulty.record_round(performance(70.0, 80.0, 0), TARGET_WPM);
+        assert_eq!(adaptive_difficulty.level, -2);
+    }
+
+    #[test]
+    fn rounds_on_target_keep_the_level() {
+        let mut adaptive_difficulty = AdaptiveDifficulty {
+            level: 2,
+            ..default()
+        };
+        adaptive_difficulty.record_round(performance(50.0, 90.0, 0), TARGET_WPM);
+
+        assert_eq!(adaptive_difficulty.band, ChallengeBand::OnTarget);
+        assert_eq!(adaptive_difficulty.level, 2);
+    }
+
+    #[test]
+    fn only_the_last_rounds_are_averaged() {
+        let mut adaptive_difficulty = AdaptiveDifficulty::default();
+        adaptive_difficulty.record_round(performance(10.0, 50.0, 3), TARGET_WPM);
+        for _ in 0..ROLLING_WINDOW {
+            adaptive_difficulty.record_round(performance(70.0, 97.0, 0), TARGET_WPM);
+        }
+
+        assert_eq!(adaptive_difficulty.recent_rounds.len(), ROLLING_WINDOW);
+        assert_eq!(adaptive_difficulty.average().lives_lost, 0);
+        assert_eq!(adaptive_difficulty.band, ChallengeBand::Cruising);
+    }
+
+    #[test]
+    fn level_stays_within_its_bounds() {
+        let mut adaptive_difficulty = AdaptiveDifficulty::default();
+        for _ in 0..2 * MAX_ADJUSTMENT_LEVEL {
+            adaptive_difficulty.record_round(performance(70.0, 97.0, 0), TARGET_WPM);
+        }
+        assert_eq!(adaptive_difficulty.level, MAX_ADJUSTMENT_LEVEL);
+        assert_eq!(adaptive_difficulty.word_tier(), WordTier::Long);
+
+        for _ in 0..4 * MAX_ADJUSTMENT_LEVEL {
+            adaptive_difficulty.record_round(performance(10.0, 50.0, 2), TARGET_WPM);
+        }
+        assert_eq!(adaptive_difficulty.level, -MAX_ADJUSTMENT_LEVEL);
+        assert_eq!(adaptive_difficulty.word_tier(), WordTier::Short);
+    }
+}
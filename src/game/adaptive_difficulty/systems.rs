@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use crate::game::enemies::resources::EnemySpawnTimer;
+use crate::game::enemies::spawn_director::resources::SpawnDirector;
+use crate::game::rounds_and_indicators::resources::{
+    DifficultyIndicator, EnemyBaseSpeedCurrentRound, GameMode, GameModeIndicator,
+    MaxNumberOfEnemiesCurrentRound, RoundSummary, WordPerMinuteTypedIndicator,
+};
+use crate::game::waves::resources::CurrentWave;
+use crate::menu::systems::Restart;
+
+use super::*;
+
+/// Run condition that is true if the adaptive mode is turned on and the endless mode is played.
+/// The daily challenge and the campaign levels keep their fixed difficulty
+pub fn adaptive_difficulty_is_active(
+    adaptive_difficulty: Res<AdaptiveDifficulty>,
+    game_mode: Res<GameModeIndicator>,
+) -> bool {
+    adaptive_difficulty.enabled && game_mode.mode == GameMode::Endless
+}
+
+/// Records how the player performed in the round that just finished and adjusts the level for the next round
+pub fn record_round_performance(
+    mut adaptive_difficulty: ResMut<AdaptiveDifficulty>,
+    round_summary: Res<RoundSummary>,
+    wpm: Res<WordPerMinuteTypedIndicator>,
+    difficulty: Res<DifficultyIndicator>,
+) {
+    adaptive_difficulty.record_round(
+        RoundPerformance {
+            wpm: wpm.wpm,
+            accuracy: round_summary.accuracy(),
+            lives_lost: round_summary.lives_lost,
+        },
+        SpawnDirector::target_wpm_for_difficulty(&difficulty.difficulty) as f64,
+    );
+}
+
+/// Adjusts the round that just started according to the level of the adaptive mode. The number of
+/// enemies and their words are only adjusted in rounds generated by the endless mode
+pub fn apply_adaptive_difficulty(
+    adaptive_difficulty: Res<AdaptiveDifficulty>,
+    mut current_wave: ResMut<CurrentWave>,
+    mut max_number_of_enemies_this_round: ResMut<MaxNumberOfEnemiesCurrentRound>,
+    mut enemy_base_speed_this_round: ResMut<EnemyBaseSpeedCurrentRound>,
+    mut enemy_spawn_timer: ResMut<EnemySpawnTimer>,
+) {
+    enemy_base_speed_this_round.speed *= adaptive_difficulty.speed_factor();
+    let spawn_interval = enemy_spawn_timer.timer.duration().as_secs_f32()
+        * adaptive_difficulty.spawn_interval_factor();
+    enemy_spawn_timer
+        .timer
+        .set_duration(Duration::from_secs_f32(spawn_interval));
+    if !current_wave.scripted && !current_wave.boss {
+        max_number_of_enemies_this_round.number = ((max_number_of_enemies_this_round.number as f32
+            * adaptive_difficulty.enemy_count_factor())
+        .round() as u32)
+            .max(1);
+        current_wave.word_tier = adaptive_difficulty.word_tier();
+    }
+}
+
+/// Forgets the performance of the previous run on restart. Whether the adaptive mode is turned on is kept
+pub fn reset_adaptive_difficulty_on_restart(
+    mut restart_event_reader: EventReader<Restart>,
+    mut adaptive_difficulty: ResMut<AdaptiveDifficulty>,
+) {
+    for _ in restart_event_reader.read() {
+        *adaptive_difficulty = AdaptiveDifficulty {
+            enabled: adaptive_difficulty.enabled,
+            ..default()
+        };
+    }
+}
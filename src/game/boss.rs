@@ -13,7 +13,11 @@ impl Plugin for BossPlugin {
         app
             // Register types for debug
             .register_type::<Boss>()
-            // Initialize Resources
+            .register_type::<BossWord>()
+            .register_type::<BossHealthBar>()
+            .register_type::<BossType>()
+            .register_type::<SplitsInHalf>()
+            .register_type::<Flying>()
             // Add systems for when entering round
             .add_systems(
                 OnEnter(RoundState::InRound),
@@ -23,6 +27,25 @@ impl Plugin for BossPlugin {
             )
             // Add systems for when exiting rounds
             .add_systems(OnExit(RoundState::InRound), despawn_boss)
+            // Add update systems
+            .add_systems(
+                Update,
+                (
+                    update_boss_words,
+                    split_bosses,
+                    tick_boss_abilities,
+                    charge_bosses,
+                    summon_minions,
+                    shuffle_boss_words,
+                    fly_bosses,
+                    boss_collision_with_castle,
+                    move_boss_words_with_bosses,
+                    update_boss_health_bars,
+                )
+                    .chain()
+                    .before(super::enemies::text::systems::layout_enemy_labels)
+                    .in_set(super::InputHandlingSystemSet::AfterInputHandling),
+            )
             .add_systems(Update, despawn_boss_on_restart);
     }
 }
@@ -1,6 +1,345 @@
+use bevy::ecs::system::EntityCommands;
+use rand::{seq::SliceRandom, Rng};
+use std::fmt;
+
 use super::*;
+use crate::game::enemies::components::EnemyType;
+use crate::game::enemies::movement::components::{EnemySpawnPoint, PathProgress};
+use crate::game::map::resources::LoadedMap;
+use crate::game::waves::resources::ENDLESS_BOSS_ROUND_INTERVAL;
+
+/// Seconds between two uses of the ability of the current boss phase
+pub const BOSS_ABILITY_INTERVAL: f32 = 5.0;
+/// Share of the words left below which the boss starts its speed bursts
+pub const BOSS_CHARGING_THRESHOLD: f32 = 0.75;
+/// Share of the words left below which the boss starts to summon minions
+pub const BOSS_SUMMONING_THRESHOLD: f32 = 0.5;
+/// Share of the words left below which the boss starts to shuffle its words
+pub const BOSS_SHUFFLING_THRESHOLD: f32 = 0.25;
+
+/// Component used for the bosses. A boss is a single enemy working through a queue of words,
+/// of which only one or two are shown at a time. Its health is the number of words it has left
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct Boss {
+    /// Words that are not shown yet, the next one first
+    pub word_queue: Vec<String>,
+    /// Word entities of the boss that are currently shown
+    pub shown_words: Vec<Entity>,
+    pub total_words: u32,
+    pub phase: BossPhase,
+    pub base_speed: f32,
+    /// Timer after which the boss uses the ability of its phase
+    pub ability_timer: Timer,
+    pub speed_burst_seconds_left: f32,
+}
+
+impl Boss {
+    /// Creates a boss with the given words that walks at the given speed
+    pub fn new(word_queue: Vec<String>, base_speed: f32) -> Boss {
+        Boss {
+            total_words: word_queue.len() as u32,
+            word_queue,
+            shown_words: Vec::new(),
+            phase: BossPhase::Marching,
+            base_speed,
+            ability_timer: Timer::from_seconds(BOSS_ABILITY_INTERVAL, TimerMode::Repeating),
+            speed_burst_seconds_left: 0.0,
+        }
+    }
+
+    /// Returns the number of words the boss has left
+    pub fn words_left(&self) -> u32 {
+        (self.word_queue.len() + self.shown_words.len()) as u32
+    }
+}
+
+/// The phases of a boss fight. The boss enters the next phase once its words left drop below a threshold
+#[derive(Reflect, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BossPhase {
+    /// Walks at its base speed and shows one word
+    #[default]
+    Marching,
+    /// Runs in short speed bursts
+    Charging,
+    /// Summons minions that run ahead of it
+    Summoning,
+    /// Swaps the shown words that are not being typed for other words of its queue
+    Shuffling,
+}
+
+impl BossPhase {
+    /// Returns the phase of a boss with the given words left
+    pub fn from_words_left(words_left: u32, total_words: u32) -> BossPhase {
+        let health = words_left as f32 / total_words.max(1) as f32;
+        if health > BOSS_CHARGING_THRESHOLD {
+            BossPhase::Marching
+        } else if health > BOSS_SUMMONING_THRESHOLD {
+            BossPhase::Charging
+        } else if health > BOSS_SHUFFLING_THRESHOLD {
+            BossPhase::Summoning
+        } else {
+            BossPhase::Shuffling
+        }
+    }
+
+    /// Returns the number of words the boss shows at a time in this phase
+    pub fn shown_words(&self) -> usize {
+        match self {
+            BossPhase::Marching => 1,
+            _ => 2,
+        }
+    }
+}
 
-/// Component used to tag the bosses
+/// Component used to tag the word entities of bosses. They follow their boss and are typed like enemies
 #[derive(Reflect, Component, Default)]
 #[reflect(Component)]
-pub struct Boss {}
+pub struct BossWord {}
+
+/// Component used to tag the part of the health bar of a boss that shrinks with its words left
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct BossHealthBar {}
+
+/// The bosses of the roster. Each one has its own sprite and behaviour
+#[derive(Reflect, Component, Default, Debug, PartialEq, Eq, Copy, Clone, serde::Deserialize)]
+#[reflect(Component)]
+#[serde(rename_all = "snake_case")]
+pub enum BossType {
+    /// Walks along the boss route of the map
+    #[default]
+    KingSlime,
+    /// Runs fast along the shortest route of the map
+    RagingRino,
+    /// Splits its word queue into two halves walking on their own at half health
+    RockGolem,
+    /// Shows its words reversed
+    MirrorGhost,
+    /// Flies straight towards the castle, ignoring the roads
+    GiantBat,
+}
+
+/// Spritesheet of a boss
+pub struct BossSprite {
+    pub path: &'static str,
+    pub width: f32,
+    pub height: f32,
+    pub frames: usize,
+}
+
+impl BossType {
+    pub const ALL: [BossType; 5] = [
+        BossType::KingSlime,
+        BossType::RagingRino,
+        BossType::RockGolem,
+        BossType::MirrorGhost,
+        BossType::GiantBat,
+    ];
+
+    /// Returns the boss of the given round if the wave doesn't choose one. The roster rotates every boss round of the endless mode
+    pub fn for_round(round_number: u32) -> BossType {
+        let boss_round = round_number.saturating_sub(1) / ENDLESS_BOSS_ROUND_INTERVAL;
+        BossType::ALL[boss_round as usize % BossType::ALL.len()]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BossType::KingSlime => "King Slime",
+            BossType::RagingRino => "Raging Rino",
+            BossType::RockGolem => "Rock Golem",
+            BossType::MirrorGhost => "Mirror Ghost",
+            BossType::GiantBat => "Giant Bat",
+        }
+    }
+
+    pub fn sprite(&self) -> BossSprite {
+        match self {
+            BossType::KingSlime => BossSprite {
+                path: "sprites/enemies/slime.png",
+                width: 44.0,
+                height: 30.0,
+                frames: 10,
+            },
+            BossType::RagingRino => BossSprite {
+                path: "sprites/enemies/rino.png",
+                width: 52.0,
+                height: 34.0,
+                frames: 6,
+            },
+            BossType::RockGolem => BossSprite {
+                path: "sprites/enemies/rock_one.png",
+                width: 38.0,
+                height: 34.0,
+                frames: 14,
+            },
+            BossType::MirrorGhost => BossSprite {
+                path: "sprites/enemies/ghost.png",
+                width: 30.0,
+                height: 30.0,
+                frames: 1,
+            },
+            BossType::GiantBat => BossSprite {
+                path: "sprites/enemies/bat.png",
+                width: 46.0,
+                height: 30.0,
+                frames: 7,
+            },
+        }
+    }
+
+    /// Returns the sprite of the halves this boss splits into
+    pub fn split_sprite(&self) -> BossSprite {
+        match self {
+            BossType::RockGolem => BossSprite {
+                path: "sprites/enemies/rock_two.png",
+                width: 32.0,
+                height: 28.0,
+                frames: 14,
+            },
+            _ => self.sprite(),
+        }
+    }
+
+    /// Returns the factor the base speed of the round is multiplied with for this boss
+    pub fn speed_factor(&self) -> f32 {
+        match self {
+            BossType::RagingRino => 2.0,
+            BossType::GiantBat => 0.8,
+            _ => 1.0,
+        }
+    }
+
+    /// Returns the enemy types this boss summons as minions. Bosses without minion types don't summon
+    pub fn minion_types(&self) -> &'static [EnemyType] {
+        match self {
+            BossType::KingSlime => &[EnemyType::Mushroom, EnemyType::Radish, EnemyType::Snail],
+            BossType::RagingRino => &[EnemyType::Pig, EnemyType::Bunny],
+            BossType::RockGolem => &[EnemyType::RockTwo, EnemyType::RockThree],
+            BossType::MirrorGhost => &[EnemyType::Chicken, EnemyType::Trunk],
+            // Minions need a road to walk along
+            BossType::GiantBat => &[],
+        }
+    }
+
+    /// Returns the route the boss starts on
+    pub fn choose_route(&self, loaded_map: &LoadedMap, rng: &mut impl Rng) -> EnemySpawnPoint {
+        match self {
+            BossType::RagingRino => loaded_map
+                .spawn_routes
+                .iter()
+                .map(|&route| EnemySpawnPoint { route })
+                .min_by(|a, b| {
+                    loaded_map
+                        .route(a)
+                        .length()
+                        .total_cmp(&loaded_map.route(b).length())
+                })
+                .unwrap_or(loaded_map.boss_route),
+            BossType::GiantBat => loaded_map
+                .spawn_routes
+                .choose(rng)
+                .map_or(loaded_map.boss_route, |&route| EnemySpawnPoint { route }),
+            _ => loaded_map.boss_route,
+        }
+    }
+
+    /// Turns a word of the word list into a word of this boss
+    pub fn prepare_word(&self, word: &str) -> String {
+        match self {
+            BossType::MirrorGhost => word.chars().rev().collect(),
+            _ => word.to_string(),
+        }
+    }
+
+    /// Inserts the components for the movement and behaviour of this boss.
+    /// Bosses walking along the roads start on the given route
+    pub fn insert_behaviour(
+        &self,
+        boss: &mut EntityCommands,
+        spawn_point: EnemySpawnPoint,
+        path_progress: PathProgress,
+    ) {
+        match self {
+            BossType::RockGolem => {
+                boss.insert((spawn_point, path_progress, SplitsInHalf {}));
+            }
+            BossType::GiantBat => {
+                boss.insert(Flying {});
+            }
+            _ => {
+                boss.insert((spawn_point, path_progress));
+            }
+        }
+    }
+}
+
+impl fmt::Display for BossType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Component used to tag bosses that split into two halves once half of their words are typed
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct SplitsInHalf {}
+
+/// Component used to tag bosses that fly straight towards the castle instead of walking along a route
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct Flying {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn phase_changes_once_the_words_left_drop_below_the_thresholds() {
+        let phases: Vec<BossPhase> = [20, 16, 15, 11, 10, 6, 5, 0]
+            .iter()
+            .map(|&words_left| BossPhase::from_words_left(words_left, 20))
+            .collect();
+        assert_eq!(
+            phases,
+            vec![
+                BossPhase::Marching,
+                BossPhase::Marching,
+                BossPhase::Charging,
+                BossPhase::Charging,
+                BossPhase::Summoning,
+                BossPhase::Summoning,
+                BossPhase::Shuffling,
+                BossPhase::Shuffling,
+            ]
+        );
+    }
+
+    #[test]
+    fn phase_of_a_boss_without_words_is_the_last_one() {
+        assert_eq!(BossPhase::from_words_left(0, 0), BossPhase::Shuffling);
+    }
+
+    #[test]
+    fn words_left_count_queued_and_shown_words() {
+        let boss = Boss {
+            word_queue: vec!["two".to_string()],
+            shown_words: vec![Entity::PLACEHOLDER],
+            total_words: 2,
+            ..default()
+        };
+
+        assert_eq!(boss.words_left(), 2);
+        assert_eq!(boss.total_words, 2);
+    }
+
+    #[test]
+    fn boss_type_rotates_every_ten_rounds() {
+        assert_eq!(BossType::for_round(10), BossType::ALL[0]);
+        assert_eq!(BossType::for_round(20), BossType::ALL[1]);
+        assert_eq!(
+            BossType::for_round(10 * BossType::ALL.len() as u32 + 10),
+            BossType::ALL[0]
+        );
+    }
+}
@@ -1,62 +1,156 @@
-use bevy::window::PrimaryWindow;
-use rand::seq::SliceRandom;
+use bevy::sprite::Anchor;
+use rand::{seq::SliceRandom, Rng};
 
 use crate::menu::systems::Restart;
 
-use super::enemies::components::{Enemy, Speed, WalkingAnimation};
-use super::enemies::movement::components::{EnemySpawnPoint, PathCheckpointNumber};
-use super::enemies::resources::WordsHandle;
-use super::enemies::systems::Words;
-use super::enemies::text::components::CollidingWith;
-use super::rounds_and_indicators::resources::{EnemyBaseSpeedCurrentRound, RoundNumber};
+use super::castle::components::Castle;
+use super::castle::resources::NumberOfLivesLeft;
+use super::effects::components::DeathAnimation;
+use super::enemies::components::{CurrentlyBeingTyped, Enemy, Speed, WalkingAnimation};
+use super::enemies::movement::components::{EnemySpawnPoint, Frozen, PathProgress, Slowed};
+use super::enemies::movement::systems::{castle_hitbox, spawn_explosion};
+use super::enemies::resources::{EnemiesBeingTyped, WordsHandle};
+use super::enemies::spawn_director::resources::SpawnCandidate;
+use super::enemies::systems::{
+    spawn_enemy_from_candidate, turn_string_literal_into_vec_of_text_sections, Words,
+    STANDARD_TEXT_COLOR, TEXT_HEIGHT, TEXT_Z_VALUE,
+};
+use super::enemies::text::components::LabelLayout;
+use super::map::resources::LoadedMap;
+use super::randomness::resources::{GameRng, RngStream};
+use super::rounds_and_indicators::resources::{
+    EnemyBaseSpeedCurrentRound, MaxNumberOfEnemiesCurrentRound, NumberOfEnemiesSpawnedThisRound,
+    NumberOfEnemiesUnlivedThisRound, RoundSummary, StreakIndicator,
+};
+use super::waves::resources::{CurrentWave, WordTier};
 use super::*;
 
-/// The number of words per boss equals, the round number * this number
+/// The number of words per boss equals, the round number * this number, unless a wave script sets it
 pub const BOSS_WORD_COUNT_MULTIPLIER: u32 = 3;
+/// Size of the boss sprite relative to the size of the enemy sprites
+const BOSS_SPRITE_SCALE_FACTOR: f32 = 2.0 * super::enemies::systems::ENEMY_SPRITE_SCALE_FACTOR;
+/// Seconds a speed burst of a charging boss lasts
+const BOSS_SPEED_BURST_DURATION: f32 = 1.5;
+/// Factor the speed of a boss is multiplied with during a speed burst
+const BOSS_SPEED_BURST_FACTOR: f32 = 2.5;
+/// Number of minions a summoning boss summons at once
+const BOSS_MINIONS_PER_SUMMON: u32 = 2;
+/// Factor the enemy base speed of the round is multiplied with for minions of bosses
+const BOSS_MINION_SPEED_FACTOR: f32 = 1.5;
+/// Distance along the route the second half of a split boss starts behind the first one
+const BOSS_SPLIT_DISTANCE: f32 = 60.0;
+/// Z value of flying bosses, above all enemies on the roads
+const FLYING_BOSS_Z_VALUE: f32 = 0.5;
+/// A boss reaching the castle takes one life per this many words it has left, at least one
+const BOSS_WORDS_PER_LIFE: u32 = 5;
+/// Size of the health bar below the boss
+const BOSS_HEALTH_BAR_SIZE: Vec2 = Vec2::new(120.0, 10.0);
+/// Height of the health bar relative to the boss
+const BOSS_HEALTH_BAR_HEIGHT: f32 = -40.0;
+/// Color of the part of the health bar that shows the words already typed
+const BOSS_HEALTH_BAR_BACKGROUND_COLOR: Color = Color::rgba(0.1, 0.1, 0.1, 0.8);
+/// Color of the part of the health bar that shows the words left
+const BOSS_HEALTH_BAR_COLOR: Color = Color::CRIMSON;
 
-/// Spawns the boss (king slime)
+/// Spawns the boss of the current wave with its queue of words if the wave has one
 pub fn spawn_boss(
     mut commands: Commands,
-    round_number: Res<RoundNumber>,
+    current_wave: Res<CurrentWave>,
     enemy_base_speed_this_round: Res<EnemyBaseSpeedCurrentRound>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    loaded_map: Res<LoadedMap>,
     words_handle: Res<WordsHandle>,
     words: Res<Assets<Words>>,
     asset_server: Res<AssetServer>,
     mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    // Spawn boss if round number is multiple of 10
-    if round_number.number % 10 == 0 {
-        // Get thread rng once for better performance
-        let mut rng = rand::thread_rng();
-
-        // Get spawn point for all "ghost" enemies and the boss
-        let window = window_query.get_single().expect("Window should exist");
-        let spawn_point = EnemySpawnPoint::BottomLeft;
-        let spawn_point_transform =
-            super::enemies::movement::systems::generate_spawn_point_transform_from_enum(
-                spawn_point,
-                window,
-            );
+    if !current_wave.boss {
+        return;
+    }
+    let Some(word) = words.get(words_handle.0.id()) else {
+        return;
+    };
+    let boss_type = current_wave.boss_type;
+    let word_queue: Vec<String> = (0..current_wave.boss_words)
+        .map(|_| {
+            boss_type.prepare_word(
+                word.vec_of_words
+                    .choose(game_rng.stream(RngStream::Boss))
+                    .expect("The list of words shouldn't be empty"),
+            )
+        })
+        .collect();
 
-        // Get slime texture handle
-        let texture_handle: Handle<Image> = asset_server.load(format!("sprites/enemies/slime.png"));
-        let texture_atlas = TextureAtlasLayout::from_grid(Vec2::new(44.0, 30.0), 10, 1, None, None);
-        let texture_atlas_handle = texture_atlases.add(texture_atlas);
-
-        // Setup slime walking animation
-        let walking_animation: WalkingAnimation = WalkingAnimation {
-            length_of_animation: 10,
-            animation_timer: Timer::from_seconds(
-                super::enemies::systems::BASE_ANIMATION_SPEED / enemy_base_speed_this_round.speed,
-                TimerMode::Repeating,
-            ),
-        };
+    let spawn_point = boss_type.choose_route(&loaded_map, game_rng.stream(RngStream::Boss));
+    let spawn_point_transform = super::enemies::movement::systems::generate_spawn_point_transform(
+        &spawn_point,
+        &loaded_map,
+    );
+    let flip_on_y_axis =
+        super::enemies::movement::systems::check_if_sprite_needs_to_be_flipped_from_spawnpoint(
+            &spawn_point,
+            &loaded_map,
+        );
+    let path_progress = PathProgress {
+        branch_seed: game_rng.stream(RngStream::Junctions).gen(),
+        ..default()
+    };
+    let speed = enemy_base_speed_this_round.speed * boss_type.speed_factor();
+
+    let boss_entity = spawn_boss_entity(
+        &mut commands,
+        boss_type,
+        &boss_type.sprite(),
+        spawn_point_transform,
+        flip_on_y_axis,
+        Boss::new(word_queue, speed),
+        &asset_server,
+        &mut texture_atlases,
+    );
+    boss_type.insert_behaviour(
+        &mut commands.entity(boss_entity),
+        spawn_point,
+        path_progress,
+    );
+}
 
-        // Spawn boss (slime)
-        commands.spawn((
+/// Spawns a boss of the given type with the given sprite and its health bar below it.
+/// Its movement and behaviour are left to the boss type. Returns the spawned boss
+fn spawn_boss_entity(
+    commands: &mut Commands,
+    boss_type: BossType,
+    boss_sprite: &BossSprite,
+    transform: Transform,
+    flip_on_y_axis: bool,
+    boss: Boss,
+    asset_server: &AssetServer,
+    texture_atlases: &mut Assets<TextureAtlasLayout>,
+) -> Entity {
+    let texture_handle: Handle<Image> = asset_server.load(boss_sprite.path);
+    let texture_atlas = TextureAtlasLayout::from_grid(
+        Vec2::new(boss_sprite.width, boss_sprite.height),
+        boss_sprite.frames,
+        1,
+        None,
+        None,
+    );
+    let texture_atlas_handle = texture_atlases.add(texture_atlas);
+
+    let walking_animation: WalkingAnimation = WalkingAnimation {
+        length_of_animation: boss_sprite.frames,
+        animation_timer: Timer::from_seconds(
+            super::enemies::systems::BASE_ANIMATION_SPEED / boss.base_speed,
+            TimerMode::Repeating,
+        ),
+    };
+    let speed = Speed {
+        speed: boss.base_speed,
+    };
+
+    commands
+        .spawn((
             SpriteSheetBundle {
-                transform: spawn_point_transform,
+                transform,
                 atlas: TextureAtlas {
                     layout: texture_atlas_handle,
                     index: 0,
@@ -64,107 +158,459 @@ pub fn spawn_boss(
                 },
                 texture: texture_handle,
                 sprite: Sprite {
-                    flip_x: true,
+                    flip_x: flip_on_y_axis,
                     custom_size: Some(Vec2::new(
-                        super::enemies::systems::ENEMY_SPRITE_SCALE_FACTOR * 30.0,
-                        super::enemies::systems::ENEMY_SPRITE_SCALE_FACTOR * 30.0,
+                        BOSS_SPRITE_SCALE_FACTOR * boss_sprite.width,
+                        BOSS_SPRITE_SCALE_FACTOR * boss_sprite.height,
                     )),
                     ..default()
                 },
                 ..default()
             },
             walking_animation,
-            spawn_point,
-            Speed {
-                speed: enemy_base_speed_this_round.speed,
-            },
-            PathCheckpointNumber::default(),
-            Name::new("King Slime".to_string()),
-            Boss {},
-        ));
-
-        // BOSS_WORD_COUNT_MULTIPLIER * round number "ghost" enemies (without sprites) at the same
-        // spot to create the illusion of one enemy with a lot of words.
-        for _ in 0..BOSS_WORD_COUNT_MULTIPLIER * round_number.number {
-            if let Some(word) = words.get(words_handle.0.id()) {
-                // Get random word from list
-                let word_for_enemy = word
-                    .vec_of_words
-                    .choose(&mut rng)
-                    .expect("The list of words shouldn't be empty");
-
-                // Get ghost texture handle
-                let texture_handle: Handle<Image> =
-                    asset_server.load(format!("sprites/enemies/ghost.png"));
-                let texture_atlas =
-                    TextureAtlasLayout::from_grid(Vec2::new(30.0, 30.0), 1, 1, None, None);
-                let texture_atlas_handle: Handle<TextureAtlasLayout> =
-                    texture_atlases.add(texture_atlas);
-
-                commands
-                    .spawn((
-                        SpriteSheetBundle {
-                            transform: spawn_point_transform,
-                atlas: TextureAtlas {
-                    layout: texture_atlas_handle,
-                    index: 0,
-                    ..default()
-                },
-                texture: texture_handle,
+            speed,
+            boss_type,
+            Name::new(boss_type.name()),
+            boss,
+        ))
+        .with_children(|parent| {
+            parent.spawn(SpriteBundle {
                 sprite: Sprite {
-                    flip_x: true,
-                    custom_size: Some(Vec2::new(
-                        super::enemies::systems::ENEMY_SPRITE_SCALE_FACTOR * 30.0,
-                        super::enemies::systems::ENEMY_SPRITE_SCALE_FACTOR * 30.0,
-                    )),
+                    color: BOSS_HEALTH_BAR_BACKGROUND_COLOR,
+                    custom_size: Some(BOSS_HEALTH_BAR_SIZE),
                     ..default()
                 },
+                transform: Transform::from_xyz(0.0, BOSS_HEALTH_BAR_HEIGHT, 0.01),
                 ..default()
-                        },
-                        Enemy {},
-                        spawn_point,
-                        Speed {
-                            speed: enemy_base_speed_this_round.speed,
-                        },
-                        PathCheckpointNumber::default(),
-                        CollidingWith::default(),
-                        Name::new(word_for_enemy.clone()),
-                    ))
-                    .with_children(|parent| {
-                        parent.spawn(Text2dBundle {
-                            text: Text {
-                                sections: super::enemies::systems::turn_string_literal_into_vec_of_text_sections(
-                                    word_for_enemy,
-                                    super::enemies::systems::STANDARD_TEXT_COLOR,
-                                ),
-                                justify: JustifyText::Center,
-                                linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
-                            },
-                            // ensure the text is drawn on top of the box
-                            transform: Transform::from_xyz(0.0, super::enemies::systems::TEXT_HEIGHT, super::enemies::systems::TEXT_Z_VALUE),
-                            ..default()
-                        });
-                    });
+            });
+            parent.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: BOSS_HEALTH_BAR_COLOR,
+                        custom_size: Some(BOSS_HEALTH_BAR_SIZE),
+                        anchor: Anchor::CenterLeft,
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(
+                        -0.5 * BOSS_HEALTH_BAR_SIZE.x,
+                        BOSS_HEALTH_BAR_HEIGHT,
+                        0.02,
+                    ),
+                    ..default()
+                },
+                BossHealthBar {},
+            ));
+        })
+        .id()
+}
+
+/// Spawns a word of a boss at the given translation. It has no sprite and is typed like an enemy
+fn spawn_boss_word(commands: &mut Commands, word: &str, translation: Vec3) -> Entity {
+    commands
+        .spawn((
+            SpatialBundle::from_transform(Transform::from_translation(translation)),
+            Enemy {},
+            BossWord {},
+            Name::new(word.to_string()),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text2dBundle {
+                    text: Text {
+                        sections: turn_string_literal_into_vec_of_text_sections(
+                            word,
+                            STANDARD_TEXT_COLOR,
+                        ),
+                        justify: JustifyText::Center,
+                        linebreak_behavior: bevy::text::BreakLineOn::NoWrap,
+                    },
+                    // ensure the text is drawn on top of the box
+                    transform: Transform::from_xyz(0.0, TEXT_HEIGHT, TEXT_Z_VALUE),
+                    ..default()
+                },
+                LabelLayout::default(),
+            ));
+        })
+        .id()
+}
+
+/// Forgets the typed words of bosses, updates their phase and shows the next words of their queue.
+/// Bosses without words left die
+pub fn update_boss_words(
+    mut commands: Commands,
+    mut boss_query: Query<(Entity, &mut Boss, &Transform)>,
+    word_query: Query<(), (With<BossWord>, With<Enemy>)>,
+) {
+    for (boss_entity, mut boss, transform) in boss_query.iter_mut() {
+        boss.shown_words.retain(|&word| word_query.contains(word));
+
+        let phase = BossPhase::from_words_left(boss.words_left(), boss.total_words);
+        if boss.phase != phase {
+            boss.phase = phase;
+            boss.ability_timer.reset();
+        }
+
+        while boss.shown_words.len() < boss.phase.shown_words() && !boss.word_queue.is_empty() {
+            let word = boss.word_queue.remove(0);
+            let word_entity = spawn_boss_word(&mut commands, &word, transform.translation);
+            boss.shown_words.push(word_entity);
+        }
+
+        if boss.words_left() == 0 {
+            commands
+                .entity(boss_entity)
+                .despawn_descendants()
+                .remove::<Boss>()
+                .insert(DeathAnimation::default());
+        }
+    }
+}
+
+/// Counts down the abilities and speed bursts of the bosses and sets their speed accordingly.
+/// Slowdowns are applied on top of this speed when bosses move
+pub fn tick_boss_abilities(mut boss_query: Query<(&mut Boss, &mut Speed)>, time: Res<Time>) {
+    for (mut boss, mut speed) in boss_query.iter_mut() {
+        boss.ability_timer.tick(time.delta());
+        boss.speed_burst_seconds_left =
+            (boss.speed_burst_seconds_left - time.delta_seconds()).max(0.0);
+        speed.speed = if boss.speed_burst_seconds_left > 0.0 {
+            boss.base_speed * BOSS_SPEED_BURST_FACTOR
+        } else {
+            boss.base_speed
+        };
+    }
+}
+
+/// Lets charging bosses start a speed burst whenever their ability is ready
+pub fn charge_bosses(mut boss_query: Query<&mut Boss>) {
+    for mut boss in boss_query.iter_mut() {
+        if boss.phase == BossPhase::Charging && boss.ability_timer.just_finished() {
+            boss.speed_burst_seconds_left = BOSS_SPEED_BURST_DURATION;
+        }
+    }
+}
+
+/// Lets summoning bosses summon minions of their minion types with short words at their position
+/// whenever their ability is ready. The minions count towards the enemies of the round
+pub fn summon_minions(
+    mut commands: Commands,
+    boss_query: Query<(
+        &Boss,
+        &BossType,
+        &EnemySpawnPoint,
+        &PathProgress,
+        &Transform,
+    )>,
+    enemy_base_speed_this_round: Res<EnemyBaseSpeedCurrentRound>,
+    loaded_map: Res<LoadedMap>,
+    words_handle: Res<WordsHandle>,
+    words: Res<Assets<Words>>,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
+    mut game_rng: ResMut<GameRng>,
+    mut max_number_of_enemies: ResMut<MaxNumberOfEnemiesCurrentRound>,
+    mut number_of_enemies_spawned_this_round: ResMut<NumberOfEnemiesSpawnedThisRound>,
+) {
+    let Some(word) = words.get(words_handle.0.id()) else {
+        return;
+    };
+    // Take any word if the list has no short word
+    let short_words: Vec<&String> = word
+        .vec_of_words
+        .iter()
+        .filter(|word| WordTier::Short.contains(word))
+        .collect();
+    for (boss, boss_type, spawn_point, path_progress, transform) in boss_query.iter() {
+        if boss.phase != BossPhase::Summoning || !boss.ability_timer.just_finished() {
+            continue;
+        }
+        for _ in 0..BOSS_MINIONS_PER_SUMMON {
+            let rng = game_rng.stream(RngStream::Boss);
+            let Some(&enemy_type) = boss_type.minion_types().choose(rng) else {
+                break;
+            };
+            let word_for_minion = if short_words.is_empty() {
+                word.vec_of_words.choose(rng)
+            } else {
+                short_words.choose(rng).copied()
+            }
+            .expect("The list of words shouldn't be empty")
+            .clone();
+            let candidate = SpawnCandidate {
+                spawn_point: *spawn_point,
+                enemy_type,
+                speed: enemy_base_speed_this_round.speed * BOSS_MINION_SPEED_FACTOR,
+                word: word_for_minion,
+                branch_seed: path_progress.branch_seed,
+                fixed_route: true,
+            };
+            let minion = spawn_enemy_from_candidate(
+                &mut commands,
+                &candidate,
+                *spawn_point,
+                &loaded_map,
+                &asset_server,
+                &mut texture_atlases,
+            );
+            // Minions start where the boss is instead of at the start of its route
+            commands.entity(minion).insert((*path_progress, *transform));
+            max_number_of_enemies.number += 1;
+            number_of_enemies_spawned_this_round.number += 1;
+        }
+    }
+}
+
+/// Splits bosses that split in half into two halves once half of their words are typed.
+/// The first half keeps the shown words and the front of the word queue, the second half starts a bit behind
+/// with the back of the word queue and may take other branches
+pub fn split_bosses(
+    mut commands: Commands,
+    mut boss_query: Query<
+        (
+            Entity,
+            &mut Boss,
+            &BossType,
+            &EnemySpawnPoint,
+            &PathProgress,
+            &Transform,
+        ),
+        With<SplitsInHalf>,
+    >,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    for (boss_entity, mut boss, &boss_type, &spawn_point, path_progress, transform) in
+        boss_query.iter_mut()
+    {
+        if boss.words_left() * 2 > boss.total_words {
+            continue;
+        }
+        if boss.word_queue.len() < 2 {
+            // Not enough words for a second half
+            commands.entity(boss_entity).remove::<SplitsInHalf>();
+            continue;
+        }
+        let middle_of_queue = boss.word_queue.len() / 2;
+        let back_of_queue = boss.word_queue.split_off(middle_of_queue);
+        let mut first_half = Boss::new(std::mem::take(&mut boss.word_queue), boss.base_speed);
+        first_half.shown_words = std::mem::take(&mut boss.shown_words);
+        first_half.total_words = boss.total_words - boss.total_words / 2;
+        let mut second_half = Boss::new(back_of_queue, boss.base_speed);
+        second_half.total_words = boss.total_words / 2;
+        commands.entity(boss_entity).despawn_recursive();
+
+        let halves = [
+            (
+                first_half,
+                path_progress.distance,
+                path_progress.branch_seed,
+            ),
+            (
+                second_half,
+                (path_progress.distance - BOSS_SPLIT_DISTANCE).max(0.0),
+                game_rng.stream(RngStream::Junctions).gen(),
+            ),
+        ];
+        for (half, distance, branch_seed) in halves {
+            let half_entity = spawn_boss_entity(
+                &mut commands,
+                boss_type,
+                &boss_type.split_sprite(),
+                *transform,
+                false,
+                half,
+                &asset_server,
+                &mut texture_atlases,
+            );
+            commands.entity(half_entity).insert((
+                spawn_point,
+                PathProgress {
+                    distance,
+                    branch_seed,
+                    junctions_passed: path_progress.junctions_passed,
+                },
+            ));
+        }
+    }
+}
+
+/// Lets flying bosses fly straight towards the castle. Freezes and slowdowns apply like on the road
+pub fn fly_bosses(
+    mut flying_query: Query<
+        (
+            &Speed,
+            Option<&Slowed>,
+            Has<Frozen>,
+            &mut Transform,
+            &mut Sprite,
+        ),
+        With<Flying>,
+    >,
+    castle_query: Query<&Transform, (With<Castle>, Without<Flying>)>,
+    time: Res<Time>,
+) {
+    let Ok(castle_transform) = castle_query.get_single() else {
+        return;
+    };
+    for (speed, slowed, frozen, mut transform, mut sprite) in flying_query.iter_mut() {
+        let position = transform.translation.truncate();
+        let direction = (castle_transform.translation.truncate() - position).normalize_or_zero();
+        let slowdown = if frozen {
+            0.0
+        } else {
+            slowed.map_or(1.0, |slowed| slowed.factor)
+        };
+        transform.translation = (position
+            + direction * speed.speed * slowdown * time.delta_seconds())
+        .extend(FLYING_BOSS_Z_VALUE);
+        if direction.x != 0.0 {
+            sprite.flip_x = direction.x > 0.0;
+        }
+    }
+}
+
+/// Lets shuffling bosses swap their shown words that are not being typed for the next words of
+/// their queue whenever their ability is ready
+pub fn shuffle_boss_words(
+    mut boss_query: Query<&mut Boss>,
+    mut word_query: Query<(&Children, &mut Name, Has<CurrentlyBeingTyped>), With<BossWord>>,
+    mut text_query: Query<&mut Text>,
+) {
+    for mut boss in boss_query.iter_mut() {
+        if boss.phase != BossPhase::Shuffling || !boss.ability_timer.just_finished() {
+            continue;
+        }
+        for word_entity in boss.shown_words.clone() {
+            let Ok((children, mut name, currently_being_typed)) = word_query.get_mut(word_entity)
+            else {
+                continue;
+            };
+            if currently_being_typed || boss.word_queue.is_empty() {
+                continue;
+            }
+            let new_word = boss.word_queue.remove(0);
+            let mut text_iter = text_query.iter_many_mut(children);
+            while let Some(mut text) = text_iter.fetch_next() {
+                // Towers may have taken letters from the shown word, so the text is put back in the queue
+                let old_word: String = text
+                    .sections
+                    .iter()
+                    .map(|section| section.value.as_str())
+                    .collect();
+                boss.word_queue.push(old_word);
+                text.sections =
+                    turn_string_literal_into_vec_of_text_sections(&new_word, STANDARD_TEXT_COLOR);
             }
+            *name = Name::new(new_word);
         }
     }
 }
 
-/// Despawns the boss once all the words are typed
-pub fn despawn_boss(mut commands: Commands, boss_query: Query<Entity, With<Boss>>) {
-    if let Ok(boss_entity) = boss_query.get_single() {
+/// Lets a boss that reaches the castle take one life per few words it has left and despawns it with its words
+pub fn boss_collision_with_castle(
+    mut commands: Commands,
+    boss_query: Query<(Entity, &Boss, &Transform)>,
+    castle_query: Query<&Transform, With<Castle>>,
+    mut number_of_lives_left: ResMut<NumberOfLivesLeft>,
+    mut number_of_enemies_unlived_current_round: ResMut<NumberOfEnemiesUnlivedThisRound>,
+    mut streak_indicator: ResMut<StreakIndicator>,
+    mut enemies_being_typed: ResMut<EnemiesBeingTyped>,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
+    word_name_query: Query<&Name, With<BossWord>>,
+    mut round_summary: ResMut<RoundSummary>,
+) {
+    let Ok(castle_transform) = castle_query.get_single() else {
+        return;
+    };
+    let castle_hitbox = castle_hitbox(castle_transform);
+    for (boss_entity, boss, transform) in boss_query.iter() {
+        if !castle_hitbox.contains(transform.translation.truncate()) {
+            continue;
+        }
+        let words_left = boss.words_left();
+        let damage = words_left
+            .div_ceil(BOSS_WORDS_PER_LIFE)
+            .clamp(1, u8::MAX as u32) as u8;
+        round_summary.lives_lost += damage.min(number_of_lives_left.number) as u32;
+        number_of_lives_left.number = number_of_lives_left.number.saturating_sub(damage);
+        number_of_enemies_unlived_current_round.number += words_left;
+        streak_indicator.number = 0;
+        round_summary.words_missed += words_left;
+        round_summary.words_at_castle.extend(
+            word_name_query
+                .iter_many(&boss.shown_words)
+                .map(|name| name.to_string())
+                .chain(boss.word_queue.iter().cloned()),
+        );
+
+        for &word_entity in boss.shown_words.iter() {
+            commands.entity(word_entity).despawn_recursive();
+            enemies_being_typed
+                .vec_of_enemies
+                .retain(|&x| x != word_entity);
+        }
+        if enemies_being_typed.vec_of_enemies.is_empty() {
+            enemies_being_typed.indicator = false;
+        }
         commands.entity(boss_entity).despawn_recursive();
+        spawn_explosion(
+            &mut commands,
+            transform.translation.truncate().extend(10.0),
+            &asset_server,
+            &mut texture_atlases,
+        );
+    }
+}
+
+/// Moves the shown words of bosses along with them
+pub fn move_boss_words_with_bosses(
+    boss_query: Query<(&Boss, &Transform)>,
+    mut word_query: Query<&mut Transform, Without<Boss>>,
+) {
+    for (boss, boss_transform) in boss_query.iter() {
+        for &word_entity in boss.shown_words.iter() {
+            if let Ok(mut word_transform) = word_query.get_mut(word_entity) {
+                word_transform.translation = boss_transform.translation;
+            }
+        }
+    }
+}
+
+/// Shrinks the health bars of bosses to their share of words left
+pub fn update_boss_health_bars(
+    boss_query: Query<(&Boss, &Children)>,
+    mut health_bar_query: Query<&mut Transform, With<BossHealthBar>>,
+) {
+    for (boss, children) in boss_query.iter() {
+        let health = boss.words_left() as f32 / boss.total_words.max(1) as f32;
+        let mut health_bar_iter = health_bar_query.iter_many_mut(children);
+        while let Some(mut health_bar_transform) = health_bar_iter.fetch_next() {
+            health_bar_transform.scale.x = health;
+        }
+    }
+}
+
+/// Despawns the bosses and their words at the end of a round
+pub fn despawn_boss(
+    mut commands: Commands,
+    boss_query: Query<Entity, With<Boss>>,
+    word_query: Query<Entity, With<BossWord>>,
+) {
+    for entity in boss_query.iter().chain(word_query.iter()) {
+        commands.entity(entity).despawn_recursive();
     }
 }
 
-/// Despawns the boss on restart
+/// Despawns the bosses on restart. Their words are despawned with the other enemies
 pub fn despawn_boss_on_restart(
     mut commands: Commands,
     boss_query: Query<Entity, With<Boss>>,
     mut restart_event_reader: EventReader<Restart>,
 ) {
     for _ in restart_event_reader.read() {
-        if let Ok(boss_entity) = boss_query.get_single() {
+        for boss_entity in boss_query.iter() {
             commands.entity(boss_entity).despawn_recursive();
         }
     }
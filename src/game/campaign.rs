@@ -0,0 +1,46 @@
+pub mod resources;
+use resources::*;
+
+pub mod systems;
+use systems::*;
+
+use super::*;
+
+use bevy_common_assets::toml::TomlAssetPlugin;
+
+pub struct CampaignPlugin;
+
+impl Plugin for CampaignPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Register types for debug
+            .register_type::<CampaignProgress>()
+            .register_type::<CurrentLevel>()
+            // Add events
+            .add_event::<StartLevelEvent>()
+            // Initialize Resources
+            .init_resource::<CurrentLevel>()
+            .init_resource::<LevelAssetHandles>()
+            // Setup the campaign as asset
+            .add_plugins(TomlAssetPlugin::<Campaign>::new(&["campaign.toml"]))
+            .add_systems(Startup, setup_campaign)
+            // The level has to be set up before entering the game (e.g. for the map)
+            .add_systems(
+                Update,
+                (
+                    load_level_assets,
+                    start_level.after(crate::menu::systems::menu_action),
+                    count_lives_lost.run_if(
+                        in_campaign
+                            .and_then(resource_changed::<castle::resources::NumberOfLivesLeft>),
+                    ),
+                    leave_level_on_restart,
+                ),
+            )
+            .add_systems(OnEnter(LoosingState::Lost), fail_level.run_if(in_campaign))
+            .add_systems(
+                OnEnter(RoundState::InBetweenRounds),
+                finish_level_after_last_round.run_if(in_campaign),
+            );
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use super::*;
+
+use crate::game::rounds_and_indicators::resources::{Difficulty, RunStatistics};
+use crate::storage::{read_save_file, write_save_file};
+
+/// Path of the campaign with all its levels
+pub const CAMPAIGN_PATH: &str = "campaign/default.campaign.toml";
+/// Name of the save file the stars of the campaign levels are stored in
+pub const CAMPAIGN_SAVE_FILE: &str = "campaign_progress.txt";
+/// Maximum number of stars per level. One for finishing the level and one per objective met
+pub const MAX_STARS: u8 = 3;
+
+/// Asset holding the handcrafted levels of the campaign in the order they are unlocked
+#[derive(serde::Deserialize, Asset, TypePath, Clone)]
+pub struct Campaign {
+    pub levels: Vec<Level>,
+}
+
+/// A level of the campaign. It is finished by surviving its number of rounds.
+/// Every objective met on top of that gives another star
+#[derive(serde::Deserialize, Clone)]
+pub struct Level {
+    pub name: String,
+    /// Path of the map, has to be one of the maps in MAP_PATHS
+    pub map: String,
+    /// Path of the wave script. Rounds beyond its waves are generated by the endless mode
+    pub waves: String,
+    /// Path of the word pack
+    pub words: String,
+    pub difficulty: Difficulty,
+    pub rounds: u32,
+    #[serde(default)]
+    pub objectives: Vec<Objective>,
+}
+
+/// Objectives of a level that give extra stars
+#[derive(serde::Deserialize, Reflect, Clone, Copy, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Objective {
+    /// Finish with at least this accuracy in percent
+    Accuracy { min: f64 },
+    /// Finish with at least this many words per minute
+    Wpm { min: f64 },
+    /// Finish with at least this score
+    Score { min: u64 },
+    /// Finish without the castle losing a life
+    NoLivesLost,
+}
+
+impl Objective {
+    /// Returns whether the objective is met by the given run
+    pub fn is_met(&self, run_statistics: &RunStatistics, score: u64, lives_lost: u32) -> bool {
+        match *self {
+            Objective::Accuracy { min } => run_statistics.accuracy() >= min,
+            Objective::Wpm { min } => run_statistics.wpm() >= min,
+            Objective::Score { min } => score >= min,
+            Objective::NoLivesLost => lives_lost == 0,
+        }
+    }
+}
+
+impl fmt::Display for Objective {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Objective::Accuracy { min } => write!(f, "Finish with at least {min:.0}% accuracy"),
+            Objective::Wpm { min } => write!(f, "Finish with at least {min:.0} wpm"),
+            Objective::Score { min } => write!(f, "Finish with a score of at least {min}"),
+            Objective::NoLivesLost => write!(f, "Don't lose a life"),
+        }
+    }
+}
+
+/// Resource holding the handle of the campaign asset
+#[derive(Resource)]
+pub struct CampaignHandle(pub Handle<Campaign>);
+
+/// Resource holding the handles of the wave scripts and word packs of all levels, such that they
+/// are loaded before a level is started
+#[derive(Resource, Default)]
+pub struct LevelAssetHandles(pub Vec<UntypedHandle>);
+
+/// Resource for the best number of stars reached in each level by the name of the level
+#[derive(Reflect, Resource, Default)]
+#[reflect(Resource)]
+pub struct CampaignProgress {
+    pub stars: HashMap<String, u8>,
+}
+
+impl CampaignProgress {
+    /// Loads the progress from the save file
+    pub fn load() -> CampaignProgress {
+        CampaignProgress::from_save_file_contents(
+            &read_save_file(CAMPAIGN_SAVE_FILE).unwrap_or_default(),
+        )
+    }
+
+    /// Writes the progress into the save file
+    pub fn save(&self) {
+        write_save_file(CAMPAIGN_SAVE_FILE, &self.save_file_contents());
+    }
+
+    /// Parses the contents of the save file. Each line is a number of stars followed by the name of
+    /// the level. Lines that can't be parsed are skipped
+    fn from_save_file_contents(contents: &str) -> CampaignProgress {
+        let stars = contents
+            .lines()
+            .filter_map(|line| {
+                let (stars, name) = line.split_once(' ')?;
+                Some((name.to_string(), stars.parse().ok()?))
+            })
+            .collect();
+        CampaignProgress { stars }
+    }
+
+    /// Returns the contents of the save file, sorted such that the file doesn't change needlessly
+    fn save_file_contents(&self) -> String {
+        let mut lines: Vec<String> = self
+            .stars
+            .iter()
+            .map(|(name, stars)| format!("{stars} {name}\n"))
+            .collect();
+        lines.sort();
+        lines.concat()
+    }
+
+    /// Returns the best number of stars reached in the level with the given name
+    pub fn stars(&self, level_name: &str) -> u8 {
+        self.stars.get(level_name).copied().unwrap_or_default()
+    }
+
+    /// Returns whether the level with the given index is unlocked, which is the case for the first level
+    /// and every level whose previous level was finished
+    pub fn is_unlocked(&self, campaign: &Campaign, level_index: usize) -> bool {
+        level_index == 0
+            || campaign
+                .levels
+                .get(level_index - 1)
+                .is_some_and(|previous_level| self.stars(&previous_level.name) > 0)
+    }
+}
+
+/// Resource for the level of the campaign that is currently played
+#[derive(Reflect, Resource, Default)]
+#[reflect(Resource)]
+pub struct CurrentLevel {
+    /// Index of the level in the campaign
+    pub index: usize,
+    pub name: String,
+    pub rounds: u32,
+    pub objectives: Vec<Objective>,
+    /// Whether the castle was destroyed. A level that was continued after that gives no stars
+    pub failed: bool,
+    pub lives_lost: u32,
+    /// Lives left when they were last checked, used to count the lives lost
+    pub lives_left: u8,
+    /// Result once the level is finished
+    pub result: Option<LevelResult>,
+}
+
+/// The result of a finished level
+#[derive(Reflect, Clone, Debug, Default)]
+pub struct LevelResult {
+    pub stars: u8,
+    /// Whether each objective of the level was met
+    pub objectives_met: Vec<bool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(name: &str) -> Level {
+        Level {
+            name: name.to_string(),
+            map: String::new(),
+            waves: String::new(),
+            words: String::new(),
+            difficulty: Difficulty::Easy,
+            rounds: 1,
+            objectives: Vec::new(),
+        }
+    }
+
+    fn run_statistics(correct_keystrokes: u32, wrong_keystrokes: u32) -> RunStatistics {
+        RunStatistics {
+            words_typed: 30,
+            seconds_in_rounds: 60.0,
+            correct_keystrokes,
+            wrong_keystrokes,
+        }
+    }
+
+    #[test]
+    fn objectives_are_met_from_their_minimum_on() {
+        let run_statistics = run_statistics(90, 10);
+
+        assert!(Objective::Accuracy { min: 90.0 }.is_met(&run_statistics, 0, 0));
+        assert!(!Objective::Accuracy { min: 95.0 }.is_met(&run_statistics, 0, 0));
+        assert!(Objective::Wpm { min: 30.0 }.is_met(&run_statistics, 0, 0));
+        assert!(!Objective::Wpm { min: 31.0 }.is_met(&run_statistics, 0, 0));
+        assert!(Objective::Score { min: 500 }.is_met(&run_statistics, 500, 0));
+        assert!(!Objective::Score { min: 500 }.is_met(&run_statistics, 499, 0));
+        assert!(Objective::NoLivesLost.is_met(&run_statistics, 0, 0));
+        assert!(!Objective::NoLivesLost.is_met(&run_statistics, 0, 1));
+    }
+
+    #[test]
+    fn progress_survives_saving_and_loading() {
+        let mut progress = CampaignProgress::default();
+        progress.stars.insert("First Steps".to_string(), 3);
+        progress.stars.insert("Golem Pass".to_string(), 1);
+
+        let contents = progress.save_file_contents();
+        assert_eq!(contents, "1 Golem Pass\n3 First Steps\n");
+
+        let loaded_progress = CampaignProgress::from_save_file_contents(&contents);
+        assert_eq!(loaded_progress.stars, progress.stars);
+    }
+
+    #[test]
+    fn loading_skips_lines_that_can_not_be_parsed() {
+        let progress =
+            CampaignProgress::from_save_file_contents("2 Side Roads\nthree Crossroads\n\n");
+
+        assert_eq!(progress.stars("Side Roads"), 2);
+        assert_eq!(progress.stars("Crossroads"), 0);
+        assert_eq!(progress.stars.len(), 1);
+    }
+
+    #[test]
+    fn levels_are_unlocked_once_the_previous_level_is_finished() {
+        let campaign = Campaign {
+            levels: vec![level("First"), level("Second"), level("Third")],
+        };
+        let mut progress = CampaignProgress::default();
+
+        assert!(progress.is_unlocked(&campaign, 0));
+        assert!(!progress.is_unlocked(&campaign, 1));
+
+        progress.stars.insert("First".to_string(), 1);
+        assert!(progress.is_unlocked(&campaign, 1));
+        assert!(!progress.is_unlocked(&campaign, 2));
+        assert!(!progress.is_unlocked(&campaign, 3));
+    }
+}
@@ -0,0 +1,201 @@
+use crate::game::castle::resources::NumberOfLivesLeft;
+use crate::game::enemies::resources::WordsHandle;
+use crate::game::enemies::systems::Words;
+use crate::game::enemies::text::systems::DEFAULT_WORD_PACK;
+use crate::game::map::resources::{LoadedMap, Map, MapHandles, SelectedMap, MAP_PATHS};
+use crate::game::rounds_and_indicators::resources::{
+    DifficultyIndicator, GameMode, GameModeIndicator, PlayerSettings, RoundNumber, RunStatistics,
+    ScoreIndicator,
+};
+use crate::game::waves::resources::{WaveScript, WaveScriptHandle, WAVE_SCRIPT_PATH};
+use crate::menu::systems::Restart;
+use crate::menu::MenuState;
+
+use super::*;
+
+/// Event that is sent when a level is started from the level select screen. Holds the index of the level
+#[derive(Event)]
+pub struct StartLevelEvent(pub usize);
+
+/// Run condition that is true if a level of the campaign is played
+pub fn in_campaign(game_mode: Res<GameModeIndicator>) -> bool {
+    game_mode.mode == GameMode::Campaign
+}
+
+/// Sets up the campaign as asset and loads the progress from the save file
+pub fn setup_campaign(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(CampaignHandle(asset_server.load(CAMPAIGN_PATH)));
+    commands.insert_resource(CampaignProgress::load());
+}
+
+/// Loads the wave scripts and word packs of all levels once the campaign is loaded, such that the
+/// first round of a level doesn't start before they are available
+pub fn load_level_assets(
+    mut asset_events: EventReader<AssetEvent<Campaign>>,
+    campaigns: Res<Assets<Campaign>>,
+    mut level_asset_handles: ResMut<LevelAssetHandles>,
+    asset_server: Res<AssetServer>,
+) {
+    for asset_event in asset_events.read() {
+        let AssetEvent::LoadedWithDependencies { id } = asset_event else {
+            continue;
+        };
+        let Some(campaign) = campaigns.get(*id) else {
+            continue;
+        };
+        level_asset_handles.0 = campaign
+            .levels
+            .iter()
+            .flat_map(|level| {
+                [
+                    asset_server.load::<WaveScript>(&level.waves).untyped(),
+                    asset_server.load::<Words>(&level.words).untyped(),
+                ]
+            })
+            .collect();
+    }
+}
+
+/// Sets up the level that was started: fixes difficulty, map, wave script and word pack of the level.
+/// The difficulty and map of the player are given back on restart
+pub fn start_level(
+    mut start_level_event_reader: EventReader<StartLevelEvent>,
+    campaign_handle: Res<CampaignHandle>,
+    campaigns: Res<Assets<Campaign>>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut game_mode: ResMut<GameModeIndicator>,
+    mut difficulty: ResMut<DifficultyIndicator>,
+    mut words_handle: ResMut<WordsHandle>,
+    mut wave_script_handle: ResMut<WaveScriptHandle>,
+    mut selected_map: ResMut<SelectedMap>,
+    mut player_settings: ResMut<PlayerSettings>,
+    map_handles: Res<MapHandles>,
+    maps: Res<Assets<Map>>,
+    mut loaded_map: ResMut<LoadedMap>,
+    number_of_lives_left: Res<NumberOfLivesLeft>,
+    asset_server: Res<AssetServer>,
+) {
+    for start_level_event in start_level_event_reader.read() {
+        let Some(level) = campaigns
+            .get(campaign_handle.0.id())
+            .and_then(|campaign| campaign.levels.get(start_level_event.0))
+        else {
+            continue;
+        };
+
+        player_settings.save(difficulty.difficulty, selected_map.index);
+        game_mode.mode = GameMode::Campaign;
+        difficulty.difficulty = level.difficulty;
+        words_handle.0 = asset_server.load(&level.words);
+        wave_script_handle.0 = asset_server.load(&level.waves);
+        selected_map.index = MAP_PATHS
+            .iter()
+            .position(|map_path| *map_path == level.map)
+            .unwrap_or_else(|| {
+                warn!(
+                    "Map {} of level {} is not in MAP_PATHS. Using first map",
+                    level.map, level.name
+                );
+                0
+            });
+        // The first round is prepared before the selected map would be loaded otherwise
+        if let Some(map) = map_handles
+            .handles
+            .get(selected_map.index)
+            .and_then(|map_handle| maps.get(map_handle))
+        {
+            let new_loaded_map = LoadedMap::from_map(map);
+            if new_loaded_map.is_playable() {
+                *loaded_map = new_loaded_map;
+            } else {
+                warn!(
+                    "Map {} of level {} has no route enemies can spawn on. Keeping the previous map",
+                    map.name, level.name
+                );
+            }
+        }
+
+        *current_level = CurrentLevel {
+            index: start_level_event.0,
+            name: level.name.clone(),
+            rounds: level.rounds,
+            objectives: level.objectives.clone(),
+            lives_left: number_of_lives_left.number,
+            ..default()
+        };
+    }
+}
+
+/// Counts the lives the castle lost during the level
+pub fn count_lives_lost(
+    number_of_lives_left: Res<NumberOfLivesLeft>,
+    mut current_level: ResMut<CurrentLevel>,
+) {
+    if number_of_lives_left.number < current_level.lives_left {
+        current_level.lives_lost += (current_level.lives_left - number_of_lives_left.number) as u32;
+    }
+    current_level.lives_left = number_of_lives_left.number;
+}
+
+/// Marks the level as failed once the castle is destroyed
+pub fn fail_level(mut current_level: ResMut<CurrentLevel>) {
+    current_level.failed = true;
+}
+
+/// Finishes the level once all its rounds are survived: awards the stars, saves the progress and
+/// pauses the game to show the result
+pub fn finish_level_after_last_round(
+    round_number: Res<RoundNumber>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut campaign_progress: ResMut<CampaignProgress>,
+    run_statistics: Res<RunStatistics>,
+    score: Res<ScoreIndicator>,
+    mut simulation_state_next_state: ResMut<NextState<SimulationState>>,
+    mut menu_state_next_state: ResMut<NextState<MenuState>>,
+) {
+    if round_number.number < current_level.rounds || current_level.result.is_some() {
+        return;
+    }
+    let objectives_met: Vec<bool> = current_level
+        .objectives
+        .iter()
+        .map(|objective| {
+            !current_level.failed
+                && objective.is_met(&run_statistics, score.score, current_level.lives_lost)
+        })
+        .collect();
+    let stars = if current_level.failed {
+        0
+    } else {
+        (1 + objectives_met.iter().filter(|&&met| met).count() as u8).min(MAX_STARS)
+    };
+
+    if stars > campaign_progress.stars(&current_level.name) {
+        campaign_progress
+            .stars
+            .insert(current_level.name.clone(), stars);
+        campaign_progress.save();
+    }
+    current_level.result = Some(LevelResult {
+        stars,
+        objectives_met,
+    });
+
+    simulation_state_next_state.set(SimulationState::Paused);
+    menu_state_next_state.set(MenuState::LevelComplete);
+}
+
+/// Goes back to the default wave script and word pack on restart, e.g. when a level is left
+pub fn leave_level_on_restart(
+    mut restart_event_reader: EventReader<Restart>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut words_handle: ResMut<WordsHandle>,
+    mut wave_script_handle: ResMut<WaveScriptHandle>,
+    asset_server: Res<AssetServer>,
+) {
+    for _ in restart_event_reader.read() {
+        *current_level = CurrentLevel::default();
+        words_handle.0 = asset_server.load(DEFAULT_WORD_PACK);
+        wave_script_handle.0 = asset_server.load(WAVE_SCRIPT_PATH);
+    }
+}
@@ -17,6 +17,8 @@ impl Plugin for CastlePlugin {
             // Register types for debug
             .register_type::<Castle>()
             .register_type::<DestroyedCastle>()
+            .register_type::<CastleHitFlash>()
+            .register_type::<CastleDamageOverlay>()
             .register_type::<NumberOfLivesLeft>()
             // Initialize Resources
             .init_resource::<NumberOfLivesLeft>()
@@ -29,6 +31,17 @@ impl Plugin for CastlePlugin {
                     .run_if(in_state(RoundState::InRound))
                     .after(enemies::movement::systems::enemy_collision_with_castle),
             )
+            .add_systems(
+                Update,
+                (
+                    flash_castle_when_a_life_is_lost,
+                    update_castle_damage_state.run_if(in_state(AppState::InGame)),
+                    update_castle_damage_overlays.run_if(in_state(AppState::InGame)),
+                    animate_castle_damage_overlays.run_if(in_state(AppState::InGame)),
+                )
+                    .chain()
+                    .after(enemies::movement::systems::enemy_collision_with_castle),
+            )
             .add_systems(Update, despawn_castle_and_reset_lives_on_restart);
     }
 }
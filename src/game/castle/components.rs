@@ -1,5 +1,10 @@
 use super::*;
 
+/// Duration of the red flash of the castle after losing a life - in seconds
+pub const CASTLE_HIT_FLASH_DURATION: f32 = 0.4;
+/// Seconds each frame of the smoke and fire on a damaged castle is shown
+pub const CASTLE_DAMAGE_OVERLAY_FRAME_DURATION: f32 = 0.12;
+
 /// Component used to tag the castle
 #[derive(Reflect, Component, Default)]
 #[reflect(Component)]
@@ -9,3 +14,27 @@ pub struct Castle {}
 #[derive(Reflect, Component, Default)]
 #[reflect(Component)]
 pub struct DestroyedCastle {}
+
+/// Component used to flash the castle red after it lost a life
+#[derive(Reflect, Component)]
+#[reflect(Component)]
+pub struct CastleHitFlash {
+    pub timer: Timer,
+}
+
+impl Default for CastleHitFlash {
+    fn default() -> CastleHitFlash {
+        CastleHitFlash {
+            timer: Timer::from_seconds(CASTLE_HIT_FLASH_DURATION, TimerMode::Once),
+        }
+    }
+}
+
+/// Component used for the smoke and fire shown on the castle from the given damage stage on
+#[derive(Reflect, Component)]
+#[reflect(Component)]
+pub struct CastleDamageOverlay {
+    pub stage: usize,
+    pub length_of_animation: usize,
+    pub animation_timer: Timer,
+}
@@ -1,19 +1,21 @@
 use super::*;
 
 /// Number of lives the player starts with
-const NUMBER_OF_LIVES_AT_START: u8 = 5;
+pub const NUMBER_OF_LIVES_AT_START: u8 = 5;
 
-/// Resource used to track the number of lives the player has left
+/// Resource used to track the number of lives the player has left and how many the castle can have
 #[derive(Reflect, Resource)]
 #[reflect(Resource)]
 pub struct NumberOfLivesLeft {
     pub number: u8,
+    pub maximum: u8,
 }
 
 impl Default for NumberOfLivesLeft {
     fn default() -> NumberOfLivesLeft {
         NumberOfLivesLeft {
             number: NUMBER_OF_LIVES_AT_START,
+            maximum: NUMBER_OF_LIVES_AT_START,
         }
     }
 }
@@ -1,23 +1,45 @@
-use bevy::window::PrimaryWindow;
 use rand::Rng;
 
+use crate::game::map::resources::LoadedMap;
+use crate::game::randomness::resources::{GameRng, RngStream};
 use crate::menu::{systems::Restart, MenuState};
 
 use super::*;
 
-/// Spawns the castle in the middle of the screen
+/// Color the castle is tinted with once it has lost all but its last life
+const HEAVILY_DAMAGED_CASTLE_COLOR: Color = Color::rgb(0.55, 0.42, 0.38);
+/// Color the castle flashes in when it loses a life
+const CASTLE_HIT_FLASH_COLOR: Color = Color::rgb(1.0, 0.25, 0.25);
+/// Fractions of lives left at or below which the castle reaches the next damage stage
+const CASTLE_DAMAGE_STAGE_THRESHOLDS: [f32; 2] = [0.7, 0.4];
+/// Overlays of the damage stages, given by the stage they appear from, their offset from the center
+/// of the castle, their scale and their color. Tinted explosions are used as smoke
+const CASTLE_DAMAGE_OVERLAYS: [(usize, Vec2, f32, Color); 4] = [
+    (1, Vec2::new(-70.0, 70.0), 0.6, CASTLE_SMOKE_COLOR),
+    (2, Vec2::new(80.0, 85.0), 0.7, CASTLE_SMOKE_COLOR),
+    (2, Vec2::new(-20.0, 90.0), 0.8, CASTLE_SMOKE_COLOR),
+    (2, Vec2::new(30.0, 20.0), 0.4, Color::WHITE),
+];
+/// Color the smoke rising from a damaged castle is tinted with
+const CASTLE_SMOKE_COLOR: Color = Color::rgba(0.25, 0.25, 0.25, 0.75);
+/// Size of a frame of the explosion sprite sheet - in pixels
+const EXPLOSION_FRAME_SIZE: f32 = 192.0;
+/// Number of frames of the explosion sprite sheet
+const EXPLOSION_FRAMES: usize = 9;
+
+/// Spawns the castle at the castle position of the loaded map
 pub fn spawn_castle(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    loaded_map: Res<LoadedMap>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    let castle_number = i32::to_string(&rand::thread_rng().gen_range(0..4));
+    let castle_number = i32::to_string(&game_rng.stream(RngStream::Castle).gen_range(0..4));
     let castle_sprite_path = format!("sprites/castle/castle{}.png", castle_number);
-    let window = window_query.get_single().unwrap();
 
     commands.spawn((
         SpriteBundle {
-            transform: Transform::from_xyz(0.0, window.height() * 0.05, 1.0),
+            transform: Transform::from_translation(loaded_map.castle_position.extend(1.0)),
             texture: asset_server.load(castle_sprite_path),
             ..default()
         },
@@ -30,15 +52,20 @@ pub fn spawn_castle(
 pub fn despawn_castle_if_all_lives_are_gone_and_spawn_destroyed_castle(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    mut castle_query: Query<(Entity, &mut Handle<Image>), With<Castle>>,
+    mut castle_query: Query<(Entity, &mut Handle<Image>, &mut Sprite), With<Castle>>,
     number_of_lives_left: Res<NumberOfLivesLeft>,
     mut next_is_lost_state: ResMut<NextState<LoosingState>>,
     mut simulation_state_next_state: ResMut<NextState<SimulationState>>,
     mut menu_state_next_state: ResMut<NextState<MenuState>>,
 ) {
     if number_of_lives_left.number == 0 {
-        if let Ok((castle_entity, mut castle_image)) = castle_query.get_single_mut() {
-            commands.entity(castle_entity).remove::<Castle>();
+        if let Ok((castle_entity, mut castle_image, mut castle_sprite)) =
+            castle_query.get_single_mut()
+        {
+            commands
+                .entity(castle_entity)
+                .remove::<(Castle, CastleHitFlash)>();
+            castle_sprite.color = Color::WHITE;
             commands.entity(castle_entity).insert(DestroyedCastle {});
             *castle_image = asset_server.load("sprites/castle/castleDestroyed.png");
             next_is_lost_state.set(LoosingState::Lost);
@@ -57,7 +84,7 @@ pub fn despawn_castle_and_reset_lives_on_restart(
     mut restart_event_reader: EventReader<Restart>,
 ) {
     for _ in restart_event_reader.read() {
-        number_of_lives_left.number = 5;
+        *number_of_lives_left = NumberOfLivesLeft::default();
         if let Ok(castle_entity) = castle_query.get_single() {
             commands.entity(castle_entity).despawn_recursive();
         }
@@ -66,3 +93,149 @@ pub fn despawn_castle_and_reset_lives_on_restart(
         }
     }
 }
+
+/// Lets the castle flash when it lost a life since the last frame
+pub fn flash_castle_when_a_life_is_lost(
+    mut commands: Commands,
+    number_of_lives_left: Res<NumberOfLivesLeft>,
+    mut previous_number_of_lives: Local<u8>,
+    castle_query: Query<Entity, With<Castle>>,
+) {
+    if number_of_lives_left.number < *previous_number_of_lives {
+        for castle_entity in castle_query.iter() {
+            commands
+                .entity(castle_entity)
+                .insert(CastleHitFlash::default());
+        }
+    }
+    *previous_number_of_lives = number_of_lives_left.number;
+}
+
+/// Tints the castle darker the more lives are lost and blends in the red flash after a hit
+pub fn update_castle_damage_state(
+    mut commands: Commands,
+    time: Res<Time>,
+    number_of_lives_left: Res<NumberOfLivesLeft>,
+    mut castle_query: Query<(Entity, &mut Sprite, Option<&mut CastleHitFlash>), With<Castle>>,
+) {
+    // Zero with all lives left and one with a single life left
+    let damage = (number_of_lives_left
+        .maximum
+        .saturating_sub(number_of_lives_left.number) as f32
+        / number_of_lives_left.maximum.saturating_sub(1).max(1) as f32)
+        .clamp(0.0, 1.0);
+    let damage_color = mix_colors(Color::WHITE, HEAVILY_DAMAGED_CASTLE_COLOR, damage);
+
+    for (castle_entity, mut castle_sprite, castle_hit_flash) in castle_query.iter_mut() {
+        let mut color = damage_color;
+        if let Some(mut castle_hit_flash) = castle_hit_flash {
+            castle_hit_flash.timer.tick(time.delta());
+            if castle_hit_flash.timer.finished() {
+                commands.entity(castle_entity).remove::<CastleHitFlash>();
+            } else {
+                color = mix_colors(
+                    damage_color,
+                    CASTLE_HIT_FLASH_COLOR,
+                    castle_hit_flash.timer.fraction_remaining(),
+                );
+            }
+        }
+        if castle_sprite.color != color {
+            castle_sprite.color = color;
+        }
+    }
+}
+
+/// Shows the smoke and fire of the damage stage the castle is in and removes the ones of stages
+/// it was repaired out of
+pub fn update_castle_damage_overlays(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
+    number_of_lives_left: Res<NumberOfLivesLeft>,
+    castle_query: Query<Entity, With<Castle>>,
+    overlay_query: Query<(Entity, &CastleDamageOverlay)>,
+) {
+    let Ok(castle_entity) = castle_query.get_single() else {
+        return;
+    };
+    let lives_left =
+        number_of_lives_left.number as f32 / number_of_lives_left.maximum.max(1) as f32;
+    let stage = CASTLE_DAMAGE_STAGE_THRESHOLDS
+        .iter()
+        .filter(|&&threshold| lives_left <= threshold)
+        .count();
+    let shown_stage = overlay_query
+        .iter()
+        .map(|(_, overlay)| overlay.stage)
+        .max()
+        .unwrap_or(0);
+
+    if stage < shown_stage {
+        for (overlay_entity, overlay) in overlay_query.iter() {
+            if overlay.stage > stage {
+                commands.entity(overlay_entity).despawn_recursive();
+            }
+        }
+    } else if stage > shown_stage {
+        let texture_handle: Handle<Image> = asset_server.load("sprites/effects/explosion.png");
+        let texture_atlas_handle = texture_atlases.add(TextureAtlasLayout::from_grid(
+            Vec2::splat(EXPLOSION_FRAME_SIZE),
+            EXPLOSION_FRAMES,
+            1,
+            None,
+            None,
+        ));
+        commands.entity(castle_entity).with_children(|parent| {
+            for (index, &(overlay_stage, offset, scale, color)) in
+                CASTLE_DAMAGE_OVERLAYS.iter().enumerate()
+            {
+                if overlay_stage <= shown_stage || overlay_stage > stage {
+                    continue;
+                }
+                parent.spawn((
+                    SpriteSheetBundle {
+                        // Slightly in front of the castle
+                        transform: Transform::from_translation(offset.extend(0.1))
+                            .with_scale(Vec3::splat(scale)),
+                        sprite: Sprite { color, ..default() },
+                        atlas: TextureAtlas {
+                            layout: texture_atlas_handle.clone(),
+                            // Start the overlays at different frames, so they don't pulse together
+                            index: index * 2 % EXPLOSION_FRAMES,
+                        },
+                        texture: texture_handle.clone(),
+                        ..default()
+                    },
+                    CastleDamageOverlay {
+                        stage: overlay_stage,
+                        length_of_animation: EXPLOSION_FRAMES,
+                        animation_timer: Timer::from_seconds(
+                            CASTLE_DAMAGE_OVERLAY_FRAME_DURATION,
+                            TimerMode::Repeating,
+                        ),
+                    },
+                    Name::new("Castle Damage Overlay"),
+                ));
+            }
+        });
+    }
+}
+
+/// Loops the animation of the smoke and fire on the castle
+pub fn animate_castle_damage_overlays(
+    time: Res<Time>,
+    mut overlay_query: Query<(&mut CastleDamageOverlay, &mut TextureAtlas)>,
+) {
+    for (mut overlay, mut atlas_sprite) in overlay_query.iter_mut() {
+        overlay.animation_timer.tick(time.delta());
+        if overlay.animation_timer.just_finished() {
+            atlas_sprite.index = (atlas_sprite.index + 1) % overlay.length_of_animation;
+        }
+    }
+}
+
+/// Linearly interpolates between two colors
+fn mix_colors(from: Color, to: Color, fraction: f32) -> Color {
+    Color::rgba_from_array(from.rgba_to_vec4().lerp(to.rgba_to_vec4(), fraction))
+}
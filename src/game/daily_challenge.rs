@@ -0,0 +1,32 @@
+pub mod resources;
+use resources::*;
+
+pub mod systems;
+use systems::*;
+
+use super::*;
+
+pub struct DailyChallengePlugin;
+
+impl Plugin for DailyChallengePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Register types for debug
+            .register_type::<DailyChallenge>()
+            // Add events
+            .add_event::<StartDailyChallengeEvent>()
+            // Initialize Resources
+            .init_resource::<DailyChallenge>()
+            // The daily challenge has to be set up before entering the game (e.g. for the castle)
+            .add_systems(
+                Update,
+                start_daily_challenge.after(crate::menu::systems::menu_action),
+            )
+            .add_systems(
+                OnEnter(LoosingState::Lost),
+                record_daily_challenge_result
+                    .before(crate::menu::systems::spawn_lost_menu)
+                    .run_if(in_daily_challenge),
+            );
+    }
+}
@@ -0,0 +1,25 @@
+use super::*;
+
+use crate::game::enemies::text::systems::DEFAULT_WORD_PACK;
+use crate::game::rounds_and_indicators::resources::Difficulty;
+
+/// Difficulty of the daily challenge, the same for everybody
+pub const DAILY_CHALLENGE_DIFFICULTY: Difficulty = Difficulty::Medium;
+/// Word pack of the daily challenge, the same for everybody
+pub const DAILY_CHALLENGE_WORD_PACK: &str = DEFAULT_WORD_PACK;
+/// Index in MAP_PATHS of the map of the daily challenge, the same for everybody
+pub const DAILY_CHALLENGE_MAP: usize = 0;
+/// Name of the save file the best scores of the daily challenges are stored in
+pub const DAILY_CHALLENGE_SAVE_FILE: &str = "daily_challenge_best_scores.txt";
+
+/// Resource for tracking the daily challenge that is currently played
+#[derive(Reflect, Resource, Default)]
+#[reflect(Resource)]
+pub struct DailyChallenge {
+    /// Date of the challenge in the format YYYY-MM-DD
+    pub date: String,
+    /// Best score reached on this date, including the current run once it is over
+    pub best_score: Option<u64>,
+    /// Shareable result string of the current run, is set once the run is over
+    pub result: Option<String>,
+}
@@ -0,0 +1,128 @@
+use bevy::utils::SystemTime;
+
+use crate::game::enemies::resources::WordsHandle;
+use crate::game::map::resources::SelectedMap;
+use crate::game::randomness::resources::GameRng;
+use crate::game::rounds_and_indicators::resources::{
+    DifficultyIndicator, GameMode, GameModeIndicator, PlayerSettings, RunStatistics, ScoreIndicator,
+};
+use crate::storage::{read_save_file, write_save_file};
+
+use super::*;
+
+/// Event that is sent when the daily challenge is started from the main menu
+#[derive(Event)]
+pub struct StartDailyChallengeEvent;
+
+/// Run condition that is true if the daily challenge is played
+pub fn in_daily_challenge(game_mode: Res<GameModeIndicator>) -> bool {
+    game_mode.mode == GameMode::Daily
+}
+
+/// Sets up the daily challenge: fixes difficulty, map and word pack and seeds the rng with today's date.
+/// The difficulty and map of the player are given back on restart and the rng is reseeded then
+pub fn start_daily_challenge(
+    mut start_daily_challenge_event_reader: EventReader<StartDailyChallengeEvent>,
+    mut daily_challenge: ResMut<DailyChallenge>,
+    mut game_mode: ResMut<GameModeIndicator>,
+    mut difficulty: ResMut<DifficultyIndicator>,
+    mut game_rng: ResMut<GameRng>,
+    mut words_handle: ResMut<WordsHandle>,
+    mut selected_map: ResMut<SelectedMap>,
+    mut player_settings: ResMut<PlayerSettings>,
+    asset_server: Res<AssetServer>,
+) {
+    for _ in start_daily_challenge_event_reader.read() {
+        let days_since_epoch = days_since_unix_epoch();
+        let date = date_from_days_since_unix_epoch(days_since_epoch);
+
+        player_settings.save(difficulty.difficulty, selected_map.index);
+        game_mode.mode = GameMode::Daily;
+        difficulty.difficulty = DAILY_CHALLENGE_DIFFICULTY;
+        words_handle.0 = asset_server.load(DAILY_CHALLENGE_WORD_PACK);
+        selected_map.index = DAILY_CHALLENGE_MAP;
+        game_rng.reseed(days_since_epoch);
+
+        *daily_challenge = DailyChallenge {
+            best_score: load_best_scores()
+                .into_iter()
+                .find(|(saved_date, _)| saved_date == &date)
+                .map(|(_, score)| score),
+            date,
+            result: None,
+        };
+    }
+}
+
+/// Creates the shareable result string once the daily challenge is lost and saves the best score of the day
+pub fn record_daily_challenge_result(
+    mut daily_challenge: ResMut<DailyChallenge>,
+    score: Res<ScoreIndicator>,
+    run_statistics: Res<RunStatistics>,
+) {
+    let result = format!(
+        "Typing Defense Daily {} | Score: {} | WPM: {:.0} | Accuracy: {:.0}%",
+        daily_challenge.date,
+        score.score,
+        run_statistics.wpm(),
+        run_statistics.accuracy(),
+    );
+    info!("{result}");
+    daily_challenge.result = Some(result);
+
+    if daily_challenge
+        .best_score
+        .map_or(true, |best_score| score.score > best_score)
+    {
+        daily_challenge.best_score = Some(score.score);
+        let mut best_scores = load_best_scores();
+        best_scores.retain(|(date, _)| date != &daily_challenge.date);
+        best_scores.push((daily_challenge.date.clone(), score.score));
+        let contents: String = best_scores
+            .iter()
+            .map(|(date, score)| format!("{date} {score}\n"))
+            .collect();
+        write_save_file(DAILY_CHALLENGE_SAVE_FILE, &contents);
+    }
+}
+
+/// Loads the best scores of the daily challenges. Each line of the save file is a date and a score
+fn load_best_scores() -> Vec<(String, u64)> {
+    read_save_file(DAILY_CHALLENGE_SAVE_FILE)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let (date, score) = line.split_once(' ')?;
+            Some((date.to_string(), score.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+/// Returns the number of days since the 1st of January 1970 (UTC)
+fn days_since_unix_epoch() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_secs() / 86_400)
+        .unwrap_or_default()
+}
+
+/// Returns the date (YYYY-MM-DD) given the number of days since the 1st of January 1970.
+///
+/// See <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+fn date_from_days_since_unix_epoch(days_since_epoch: u64) -> String {
+    let days = days_since_epoch as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{year:04}-{month:02}-{day:02}")
+}
@@ -14,8 +14,13 @@ impl Plugin for DecorationsPlugin {
             // Register types for debug
             .register_type::<Tree>()
             // Initialize Resources
-            // Add systems for startup into the game
-            .add_systems(Startup, spawn_trees)
+            // Spawn the trees whenever another map is loaded
+            .add_systems(
+                Update,
+                spawn_trees
+                    .after(map::systems::update_loaded_map)
+                    .run_if(resource_changed::<map::resources::LoadedMap>),
+            )
             // Add update systems
             .add_systems(Update, animate_trees.run_if(in_state(RoundState::InRound)));
     }
@@ -1,60 +1,30 @@
-use bevy::window::PrimaryWindow;
+use rand::Rng;
+
+use crate::game::map::resources::LoadedMap;
+use crate::game::randomness::resources::{GameRng, RngStream};
 
 use super::*;
 
 /// The animation speed of the tree wiggle animation
 const TREE_ANIMATION_SPEED: f32 = 0.1;
+/// Z value of the trees (in order to be in front of the background)
+const TREE_Z_VALUE: f32 = 0.5;
 
-/// The coordinates in ratios of screen width and height of the trees that are in the background
-const TREE_SPAWN_POINTS_SCALES: [(f32, f32); 33] = [
-    (-0.477083333, 0.436111111),
-    (-0.4375, 0.127777778),
-    (-0.416145833, 0.386111111),
-    (-0.426041667, 0.293518519),
-    (-0.3625, 0.342592593),
-    (-0.34375, 0.175925926),
-    (-0.226041667, 0.219444444),
-    (-0.177083333, 0.157407407),
-    (-0.163541667, 0.434259259),
-    (-0.057291667, 0.14537037),
-    (-0.034375, 0.467592593),
-    (0.14375, 0.12962963),
-    (0.175, 0.410185185),
-    (0.301041667, 0.125),
-    (0.363541667, 0.355555556),
-    (0.408854167, 0.475925926),
-    (0.434895833, 0.161111111),
-    (0.463541667, 0.361111111),
-    (-0.446354167, -0.140740741),
-    (-0.219791667, -0.419444444),
-    (-0.317708333, -0.067592593),
-    (-0.177083333, -0.388888889),
-    (-0.145833333, -0.062037037),
-    (-0.129166667, -0.268518519),
-    (-0.057291667, -0.347222222),
-    (0.038541667, -0.37962963),
-    (0.09375, -0.092592593),
-    (0.1265625, -0.34537037),
-    (0.133333333, -0.166666667),
-    (0.192708333, -0.061111111),
-    (0.271875, -0.231481481),
-    (0.289583333, -0.092592593),
-    (0.413020833, -0.12037037),
-];
-
-/// Spawns the trees
+/// Spawns the trees of the loaded map and despawns the previous ones
 pub fn spawn_trees(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    tree_query: Query<Entity, With<Tree>>,
+    loaded_map: Res<LoadedMap>,
     asset_server: Res<AssetServer>,
     mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    let window = window_query
-        .get_single()
-        .expect("Primary window should exist");
+    for tree_entity in tree_query.iter() {
+        commands.entity(tree_entity).despawn_recursive();
+    }
 
-    for (x_scale, y_scale) in TREE_SPAWN_POINTS_SCALES {
-        let tree_spawnpoint = generate_spawn_point_transform_from_x_y(x_scale, y_scale, &window);
+    for tree_position in &loaded_map.tree_positions {
+        let tree_spawnpoint = Transform::from_translation(tree_position.extend(TREE_Z_VALUE));
 
         let texture_handle: Handle<Image> = asset_server.load("sprites/decorations/tree.png");
         let texture_atlas =
@@ -68,7 +38,7 @@ pub fn spawn_trees(
         tree_wiggle_animation
             .animation_timer
             .set_elapsed(std::time::Duration::from_secs_f32(
-                rand::random::<f32>() * TREE_ANIMATION_SPEED,
+                game_rng.stream(RngStream::Decorations).gen::<f32>() * TREE_ANIMATION_SPEED,
             ));
 
         commands.spawn((
@@ -83,6 +53,7 @@ pub fn spawn_trees(
                 ..default()
             },
             tree_wiggle_animation,
+            Tree {},
             Name::new("Tree"),
         ));
     }
@@ -104,12 +75,3 @@ pub fn animate_trees(
         }
     }
 }
-
-/// Returns a transform given coordinates in ratio of width and height of screen
-fn generate_spawn_point_transform_from_x_y(
-    x_scale: f32,
-    y_scale: f32,
-    window: &Window,
-) -> Transform {
-    Transform::from_xyz(window.width() * x_scale, window.height() * y_scale, 0.5)
-}
@@ -4,6 +4,8 @@ use components::*;
 mod systems;
 use systems::*;
 
+use crate::menu::MenuState;
+
 use super::*;
 
 pub struct EffectsPlugin;
@@ -13,9 +15,29 @@ impl Plugin for EffectsPlugin {
         app
             // Register types for debug
             .register_type::<Explosion>()
+            .register_type::<DeathAnimation>()
+            .register_type::<PoofParticle>()
+            .register_type::<ScorePopup>()
+            .register_type::<HitFlash>()
             // Initialize Resources
             // Add systems for startup into the game
             // Add update systems
-            .add_systems(Update, animate_explosions);
+            .add_systems(
+                Update,
+                (
+                    animate_explosions,
+                    animate_death_animations,
+                    animate_poof_particles,
+                    animate_score_popups,
+                    // Runs after the label layout so the shake is not overwritten
+                    animate_hit_flashes.after(super::InputHandlingSystemSet::AfterInputHandling),
+                ),
+            )
+            .add_systems(
+                Update,
+                (spawn_poof_particles_on_enemy_typed, spawn_score_popups)
+                    .after(super::InputHandlingSystemSet::AfterInputHandling)
+                    .run_if(in_state(AppState::InGame).and_then(in_state(MenuState::NotInTheMenu))),
+            );
     }
 }
@@ -2,6 +2,14 @@ use super::*;
 
 // Length of explosion animation - higher is slower
 pub const EXPLOSION_ANIMATION_SPEED: f32 = 0.1;
+/// Duration of the fade and shrink animation of typed enemies - in seconds
+pub const DEATH_ANIMATION_DURATION: f32 = 0.3;
+/// Duration of the poof particles of typed enemies - in seconds
+pub const POOF_PARTICLE_DURATION: f32 = 0.4;
+/// Duration of the floating score popup - in seconds
+pub const SCORE_POPUP_DURATION: f32 = 0.8;
+/// Duration of the flash and shake of a label when a wrong key is pressed - in seconds
+pub const HIT_FLASH_DURATION: f32 = 0.25;
 
 /// Component used to tag explosion Entities
 #[derive(Reflect, Component, Default)]
@@ -15,3 +23,56 @@ pub struct ExplosionAnimation {
     pub length_of_animation: usize,
     pub animation_timer: Timer,
 }
+
+/// Component that fades and shrinks an entity and despawns it once the animation is finished
+#[derive(Reflect, Component)]
+#[reflect(Component)]
+pub struct DeathAnimation {
+    pub animation_timer: Timer,
+}
+
+impl Default for DeathAnimation {
+    fn default() -> DeathAnimation {
+        DeathAnimation {
+            animation_timer: Timer::from_seconds(DEATH_ANIMATION_DURATION, TimerMode::Once),
+        }
+    }
+}
+
+/// Component used for the small particles that fly apart when an enemy is typed
+#[derive(Reflect, Component)]
+#[reflect(Component)]
+pub struct PoofParticle {
+    pub velocity: Vec2,
+    pub animation_timer: Timer,
+}
+
+/// Component used for the text that floats upwards showing the score gained
+#[derive(Reflect, Component)]
+#[reflect(Component)]
+pub struct ScorePopup {
+    pub animation_timer: Timer,
+}
+
+impl Default for ScorePopup {
+    fn default() -> ScorePopup {
+        ScorePopup {
+            animation_timer: Timer::from_seconds(SCORE_POPUP_DURATION, TimerMode::Once),
+        }
+    }
+}
+
+/// Component that makes a text flash in the hit color and shake sideways, e.g. when a wrong key is pressed
+#[derive(Reflect, Component)]
+#[reflect(Component)]
+pub struct HitFlash {
+    pub animation_timer: Timer,
+}
+
+impl Default for HitFlash {
+    fn default() -> HitFlash {
+        HitFlash {
+            animation_timer: Timer::from_seconds(HIT_FLASH_DURATION, TimerMode::Once),
+        }
+    }
+}
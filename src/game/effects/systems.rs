@@ -1,5 +1,33 @@
+use rand::Rng;
+
+use super::enemies::systems::{ENEMY_TEXT_FONT_SIZE, STANDARD_TEXT_COLOR, TEXT_HEIGHT};
+use super::enemies::text::components::LabelLayout;
+use super::enemies::text::systems::EnemyTypedEvent;
+use super::randomness::resources::{GameRng, RngStream};
+use super::rounds_and_indicators::systems::ScoreGainedEvent;
 use super::*;
 
+/// Number of particles spawned when an enemy is typed
+const NUMBER_OF_POOF_PARTICLES: usize = 10;
+/// Size of the poof particles in pixels
+const POOF_PARTICLE_SIZE: f32 = 8.0;
+/// Maximum speed of the poof particles in pixels per second
+const POOF_PARTICLE_MAX_SPEED: f32 = 160.0;
+/// Color of the poof particles
+const POOF_PARTICLE_COLOR: Color = Color::WHITE;
+/// Color of the score popup
+const SCORE_POPUP_COLOR: Color = Color::GOLD;
+/// Speed with which the score popup floats upwards in pixels per second
+const SCORE_POPUP_SPEED: f32 = 60.0;
+/// Color the text flashes in when a wrong key is pressed
+const HIT_FLASH_COLOR: Color = Color::CRIMSON;
+/// Maximum sideways offset of a shaking text in pixels
+const HIT_SHAKE_AMPLITUDE: f32 = 8.0;
+/// Frequency of the shaking of a text in radians per second
+const HIT_SHAKE_FREQUENCY: f32 = 80.0;
+/// Z value of effects (in order to be in front of enemies and decorations)
+const EFFECTS_Z_VALUE: f32 = 10.0;
+
 /// Animates the explosions
 pub fn animate_explosions(
     time: Res<Time>,
@@ -17,3 +45,174 @@ pub fn animate_explosions(
         }
     }
 }
+
+/// Fades and shrinks entities with a death animation and despawns them once the animation is finished.
+///
+/// Entities without a sprite (e.g. the enemy on the how to play screen) are only shrunk
+pub fn animate_death_animations(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut death_animation_query: Query<(
+        Entity,
+        &mut DeathAnimation,
+        &mut Transform,
+        Option<&mut Sprite>,
+    )>,
+) {
+    for (entity_id, mut death_animation, mut transform, sprite) in &mut death_animation_query {
+        death_animation.animation_timer.tick(time.delta());
+        if death_animation.animation_timer.finished() {
+            commands.entity(entity_id).despawn_recursive();
+            continue;
+        }
+        let remaining = death_animation.animation_timer.fraction_remaining();
+        transform.scale = Vec3::splat(remaining);
+        if let Some(mut sprite) = sprite {
+            sprite.color.set_a(remaining);
+        }
+    }
+}
+
+/// Spawns poof particles at the position of enemies that were typed
+pub fn spawn_poof_particles_on_enemy_typed(
+    mut commands: Commands,
+    mut enemy_typed_event: EventReader<EnemyTypedEvent>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let rng = game_rng.stream(RngStream::Effects);
+    for enemy_typed in enemy_typed_event.read() {
+        for _ in 0..NUMBER_OF_POOF_PARTICLES {
+            let direction = Vec2::from_angle(rng.gen_range(0.0..std::f32::consts::TAU));
+            let speed = rng.gen_range(0.3..1.0) * POOF_PARTICLE_MAX_SPEED;
+            commands.spawn((
+                SpriteBundle {
+                    transform: Transform::from_xyz(
+                        enemy_typed.translation.x,
+                        enemy_typed.translation.y,
+                        EFFECTS_Z_VALUE,
+                    ),
+                    sprite: Sprite {
+                        color: POOF_PARTICLE_COLOR,
+                        custom_size: Some(Vec2::splat(POOF_PARTICLE_SIZE)),
+                        ..default()
+                    },
+                    ..default()
+                },
+                PoofParticle {
+                    velocity: direction * speed,
+                    animation_timer: Timer::from_seconds(POOF_PARTICLE_DURATION, TimerMode::Once),
+                },
+                Name::new("Poof Particle"),
+            ));
+        }
+    }
+}
+
+/// Moves, fades and shrinks the poof particles and despawns them once they are finished
+pub fn animate_poof_particles(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut particle_query: Query<(Entity, &mut PoofParticle, &mut Transform, &mut Sprite)>,
+) {
+    for (entity_id, mut particle, mut transform, mut sprite) in &mut particle_query {
+        particle.animation_timer.tick(time.delta());
+        if particle.animation_timer.finished() {
+            commands.entity(entity_id).despawn_recursive();
+            continue;
+        }
+        let remaining = particle.animation_timer.fraction_remaining();
+        transform.translation += (particle.velocity * time.delta_seconds()).extend(0.0);
+        transform.scale = Vec3::splat(remaining);
+        sprite.color.set_a(remaining);
+    }
+}
+
+/// Spawns a text floating above typed enemies showing the score that was gained
+pub fn spawn_score_popups(
+    mut commands: Commands,
+    mut score_gained_event: EventReader<ScoreGainedEvent>,
+) {
+    for score_gained in score_gained_event.read() {
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(
+                    format!("+{}", score_gained.points),
+                    TextStyle {
+                        font_size: ENEMY_TEXT_FONT_SIZE * 0.6,
+                        color: SCORE_POPUP_COLOR,
+                        ..default()
+                    },
+                ),
+                transform: Transform::from_xyz(
+                    score_gained.translation.x,
+                    score_gained.translation.y + TEXT_HEIGHT,
+                    EFFECTS_Z_VALUE,
+                ),
+                ..default()
+            },
+            ScorePopup::default(),
+            Name::new("Score Popup"),
+        ));
+    }
+}
+
+/// Lets the score popups float upwards and fade out and despawns them once they are finished
+pub fn animate_score_popups(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut score_popup_query: Query<(Entity, &mut ScorePopup, &mut Transform, &mut Text)>,
+) {
+    for (entity_id, mut score_popup, mut transform, mut text) in &mut score_popup_query {
+        score_popup.animation_timer.tick(time.delta());
+        if score_popup.animation_timer.finished() {
+            commands.entity(entity_id).despawn_recursive();
+            continue;
+        }
+        let remaining = score_popup.animation_timer.fraction_remaining();
+        transform.translation.y += SCORE_POPUP_SPEED * time.delta_seconds();
+        for section in text.sections.iter_mut() {
+            section.style.color.set_a(remaining);
+        }
+    }
+}
+
+/// Lets texts with a hit flash flash in the hit color and shake sideways.
+///
+/// Only letters that are not being typed are colored, such that typing the word again during the
+/// flash is still visible.
+pub fn animate_hit_flashes(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut hit_flash_query: Query<(
+        Entity,
+        &mut HitFlash,
+        &mut Transform,
+        &mut Text,
+        Option<&LabelLayout>,
+    )>,
+) {
+    for (entity_id, mut hit_flash, mut transform, mut text, label_layout) in &mut hit_flash_query {
+        // Shake around the position assigned by the label layout
+        let resting_x = label_layout.map_or(0.0, |label_layout| label_layout.offset.x);
+        hit_flash.animation_timer.tick(time.delta());
+        if hit_flash.animation_timer.finished() {
+            for section in text.sections.iter_mut() {
+                if section.style.color == HIT_FLASH_COLOR {
+                    section.style.color = STANDARD_TEXT_COLOR;
+                }
+            }
+            transform.translation.x = resting_x;
+            commands.entity(entity_id).remove::<HitFlash>();
+            continue;
+        }
+        for section in text.sections.iter_mut() {
+            if section.style.color == STANDARD_TEXT_COLOR {
+                section.style.color = HIT_FLASH_COLOR;
+            }
+        }
+        transform.translation.x = resting_x
+            + HIT_SHAKE_AMPLITUDE
+                * hit_flash.animation_timer.fraction_remaining()
+                * (hit_flash.animation_timer.elapsed_secs() * HIT_SHAKE_FREQUENCY).sin();
+    }
+}
@@ -8,6 +8,9 @@ pub mod systems;
 use systems::*;
 
 pub mod movement;
+pub mod spatial_index;
+pub mod spawn_director;
+pub mod spawn_warning;
 pub mod text;
 
 use super::*;
@@ -30,42 +33,87 @@ impl Plugin for EnemiesPlugin {
             .register_type::<WordsHandle>()
             .register_type::<LastEnemySpawnPoint>()
             .register_type::<movement::components::EnemySpawnPoint>()
-            .register_type::<movement::components::PathCheckpointNumber>()
-            .register_type::<text::components::CollidingWith>()
+            .register_type::<movement::components::PathProgress>()
+            .register_type::<movement::components::LaneOffset>()
+            .register_type::<movement::components::Slowed>()
+            .register_type::<movement::components::Frozen>()
+            .register_type::<spawn_director::resources::SpawnDirector>()
+            .register_type::<spawn_director::resources::SpawnDirectorOverlay>()
+            .register_type::<spawn_director::components::SpawnDirectorOverlayText>()
+            .register_type::<spawn_warning::components::SpawnWarning>()
+            .register_type::<spawn_warning::resources::SpawnWarningSettings>()
+            .register_type::<text::components::LabelLayout>()
             // Add events
             .add_event::<text::systems::EnemyTypedEvent>()
+            .add_event::<text::systems::LetterTypedEvent>()
             // Initialize Resources
             .init_resource::<EnemiesBeingTyped>()
             .init_resource::<EnemySpawnTimer>()
             .init_resource::<LastEnemySpawnPoint>()
+            .init_resource::<spatial_index::resources::EnemySpatialIndex>()
+            .init_resource::<spawn_director::resources::SpawnDirector>()
+            .init_resource::<spawn_director::resources::SpawnDirectorOverlay>()
+            .init_resource::<spawn_warning::resources::SpawnWarningSettings>()
             // Setup list of words as asset
             .add_plugins(TomlAssetPlugin::<Words>::new(&["words.toml"]))
             .add_systems(Startup, text::systems::setup_list_of_words_asset)
             // Add update systems that only run if currently in_game and simulation is running
             .add_systems(
                 Update,
-                (
-                    // Reset colliding text if necessary
-                    text::systems::lower_text_stepwise_when_colliding_enemy_is_removed,
-                    text::systems::reset_text_height_when_enemies_passed_each_other,
-                    text::systems::check_if_colliding_text_has_moved,
-                )
+                // Place the labels after enemies have moved and words were typed
+                text::systems::layout_enemy_labels
                     .in_set(super::InputHandlingSystemSet::AfterInputHandling),
             )
             .add_systems(
                 Update,
                 (
-                    randomly_spawn_enemies_over_time,
+                    spawn_director::systems::update_spawn_plan
+                        .before(roll_random_enemies_over_time),
+                    // Rounds without a wave script roll random enemies
+                    roll_random_enemies_over_time
+                        .run_if(not(super::waves::systems::wave_is_scripted))
+                        .before(spawn_pending_enemies),
+                    spawn_pending_enemies,
+                    spawn_warning::systems::spawn_enemies_after_warnings,
                     text::systems::update_text_from_enemies_on_button_press,
-                    text::systems::handle_text_when_enemies_collide,
                     tick_enemy_spawn_timer,
                     movement::systems::update_position_of_enemies_and_bosses,
+                    movement::systems::wear_off_slowdowns,
                     animate_enemies,
-                    movement::systems::enemy_collision_with_castle,
+                    // Rebuild the spatial index once per frame after enemies moved
+                    spatial_index::systems::update_enemy_spatial_index
+                        .after(movement::systems::update_position_of_enemies_and_bosses),
+                    movement::systems::enemy_collision_with_castle
+                        .after(spatial_index::systems::update_enemy_spatial_index),
+                    movement::systems::separate_enemies_on_the_road
+                        .after(spatial_index::systems::update_enemy_spatial_index),
                     movement::systems::despawn_enemy_if_out_of_screen,
                 )
                     .in_set(super::InputHandlingSystemSet::InputHandling),
             )
-            .add_systems(Update, movement::systems::despawn_enemies_on_restart);
+            .add_systems(
+                OnExit(AppState::InGame),
+                spawn_director::systems::hide_spawn_director_overlay,
+            )
+            .add_systems(
+                OnEnter(RoundState::InRound),
+                (
+                    spawn_director::systems::reset_spawn_director,
+                    spawn_warning::systems::update_spawn_warning_settings,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    movement::systems::despawn_enemies_on_restart,
+                    spawn_director::systems::reset_spawn_director_on_restart,
+                    spawn_director::systems::toggle_spawn_director_overlay
+                        .run_if(in_state(AppState::InGame)),
+                    spawn_director::systems::draw_spawn_director_overlay
+                        .run_if(in_state(AppState::InGame)),
+                    spawn_warning::systems::despawn_spawn_warnings_on_restart,
+                    spawn_warning::systems::draw_spawn_warnings.run_if(in_state(AppState::InGame)),
+                ),
+            );
     }
 }
@@ -1,9 +1,8 @@
-use rand::{
-    distributions::{Distribution, Standard},
-    Rng,
-};
+use rand::{seq::SliceRandom, Rng};
+use std::fmt;
 
 use super::*;
+use crate::game::rounds_and_indicators::resources::Difficulty;
 
 /// Component used to tag enemies
 #[derive(Reflect, Component, Default)]
@@ -42,8 +41,9 @@ pub struct CurrentlyBeingTyped {
 }
 
 /// Component used to track the type of an enemy
-#[derive(Reflect, Component, Default)]
+#[derive(Reflect, Component, Default, Debug, PartialEq, Eq, Copy, Clone, serde::Deserialize)]
 #[reflect(Component)]
+#[serde(rename_all = "snake_case")]
 pub enum EnemyType {
     #[default]
     Pig,
@@ -62,26 +62,138 @@ pub enum EnemyType {
     Snail,
 }
 
-impl Distribution<EnemyType> for Standard {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> EnemyType {
-        // match rng.gen_range(0, 3) { // rand 0.5, 0.6, 0.7
-        match rng.gen_range(0..=11) {
-            0 => EnemyType::Pig,
-            1 => EnemyType::Bat,
-            2 => EnemyType::Bee,
-            3 => EnemyType::Bunny,
-            4 => EnemyType::Chicken,
-            5 => EnemyType::Mushroom,
-            6 => EnemyType::BlueBird,
-            7 => EnemyType::Radish,
-            8 => EnemyType::Rino,
-            9 => match rng.gen_range(0..=2) {
-                0 => EnemyType::RockOne,
-                1 => EnemyType::RockTwo,
-                _ => EnemyType::RockThree,
-            },
-            10 => EnemyType::Snail,
-            _ => EnemyType::Trunk,
+impl EnemyType {
+    /// Returns a random enemy type according to the spawn table of the given difficulty.
+    ///
+    /// Only enemy types that are introduced in the given round or earlier can be chosen.
+    pub fn random_for_round(
+        round_number: u32,
+        difficulty: &Difficulty,
+        rng: &mut impl Rng,
+    ) -> EnemyType {
+        let available_entries: Vec<&EnemySpawnTableEntry> = spawn_table_from_difficulty(difficulty)
+            .iter()
+            .filter(|entry| entry.first_round <= round_number.max(1))
+            .collect();
+        available_entries
+            .choose_weighted(rng, |entry| entry.weight)
+            .expect("Spawn table should contain enemies for the first round")
+            .enemy_type
+    }
+
+    /// Returns the enemy types that are introduced in the given round for the given difficulty
+    pub fn introduced_in_round(round_number: u32, difficulty: &Difficulty) -> Vec<EnemyType> {
+        spawn_table_from_difficulty(difficulty)
+            .iter()
+            .filter(|entry| entry.first_round == round_number)
+            .map(|entry| entry.enemy_type)
+            .collect()
+    }
+}
+
+impl fmt::Display for EnemyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                EnemyType::Pig => "Pig",
+                EnemyType::Bat => "Bat",
+                EnemyType::Bee => "Bee",
+                EnemyType::Bunny => "Bunny",
+                EnemyType::Chicken => "Chicken",
+                EnemyType::Mushroom => "Mushroom",
+                EnemyType::Trunk => "Trunk",
+                EnemyType::BlueBird => "Blue Bird",
+                EnemyType::Radish => "Radish",
+                EnemyType::Rino => "Rino",
+                EnemyType::RockOne | EnemyType::RockTwo | EnemyType::RockThree => "Rock",
+                EnemyType::Snail => "Snail",
+            }
+        )
+    }
+}
+
+/// Entry of a spawn table. The enemy type can be spawned from the first round on and is chosen
+/// with a probability proportional to its weight among the enemy types that are available
+pub struct EnemySpawnTableEntry {
+    pub enemy_type: EnemyType,
+    pub first_round: u32,
+    pub weight: u32,
+}
+
+impl EnemySpawnTableEntry {
+    const fn new(enemy_type: EnemyType, first_round: u32, weight: u32) -> EnemySpawnTableEntry {
+        EnemySpawnTableEntry {
+            enemy_type,
+            first_round,
+            weight,
         }
     }
 }
+
+// Spawn tables for the different difficulties. Snails and pigs are there from the start, rinos and
+// bats come last. The three rocks share the weight of one enemy type.
+
+/// Spawn table for the easy difficulty
+const EASY_ENEMY_SPAWN_TABLE: [EnemySpawnTableEntry; 14] = [
+    EnemySpawnTableEntry::new(EnemyType::Snail, 1, 3),
+    EnemySpawnTableEntry::new(EnemyType::Pig, 1, 3),
+    EnemySpawnTableEntry::new(EnemyType::Chicken, 3, 3),
+    EnemySpawnTableEntry::new(EnemyType::Mushroom, 5, 3),
+    EnemySpawnTableEntry::new(EnemyType::Bunny, 7, 3),
+    EnemySpawnTableEntry::new(EnemyType::Radish, 9, 3),
+    EnemySpawnTableEntry::new(EnemyType::BlueBird, 12, 3),
+    EnemySpawnTableEntry::new(EnemyType::RockOne, 14, 1),
+    EnemySpawnTableEntry::new(EnemyType::RockTwo, 14, 1),
+    EnemySpawnTableEntry::new(EnemyType::RockThree, 14, 1),
+    EnemySpawnTableEntry::new(EnemyType::Trunk, 16, 3),
+    EnemySpawnTableEntry::new(EnemyType::Bee, 18, 3),
+    EnemySpawnTableEntry::new(EnemyType::Rino, 21, 3),
+    EnemySpawnTableEntry::new(EnemyType::Bat, 23, 3),
+];
+
+/// Spawn table for the medium difficulty
+const MEDIUM_ENEMY_SPAWN_TABLE: [EnemySpawnTableEntry; 14] = [
+    EnemySpawnTableEntry::new(EnemyType::Snail, 1, 3),
+    EnemySpawnTableEntry::new(EnemyType::Pig, 1, 3),
+    EnemySpawnTableEntry::new(EnemyType::Chicken, 2, 3),
+    EnemySpawnTableEntry::new(EnemyType::Mushroom, 3, 3),
+    EnemySpawnTableEntry::new(EnemyType::Bunny, 4, 3),
+    EnemySpawnTableEntry::new(EnemyType::Radish, 5, 3),
+    EnemySpawnTableEntry::new(EnemyType::BlueBird, 6, 3),
+    EnemySpawnTableEntry::new(EnemyType::RockOne, 7, 1),
+    EnemySpawnTableEntry::new(EnemyType::RockTwo, 7, 1),
+    EnemySpawnTableEntry::new(EnemyType::RockThree, 7, 1),
+    EnemySpawnTableEntry::new(EnemyType::Trunk, 8, 3),
+    EnemySpawnTableEntry::new(EnemyType::Bee, 9, 3),
+    EnemySpawnTableEntry::new(EnemyType::Rino, 11, 3),
+    EnemySpawnTableEntry::new(EnemyType::Bat, 12, 3),
+];
+
+/// Spawn table for the hard difficulty
+const HARD_ENEMY_SPAWN_TABLE: [EnemySpawnTableEntry; 14] = [
+    EnemySpawnTableEntry::new(EnemyType::Snail, 1, 3),
+    EnemySpawnTableEntry::new(EnemyType::Pig, 1, 3),
+    EnemySpawnTableEntry::new(EnemyType::Chicken, 1, 3),
+    EnemySpawnTableEntry::new(EnemyType::Mushroom, 2, 3),
+    EnemySpawnTableEntry::new(EnemyType::Bunny, 2, 3),
+    EnemySpawnTableEntry::new(EnemyType::Radish, 3, 3),
+    EnemySpawnTableEntry::new(EnemyType::BlueBird, 3, 3),
+    EnemySpawnTableEntry::new(EnemyType::RockOne, 4, 1),
+    EnemySpawnTableEntry::new(EnemyType::RockTwo, 4, 1),
+    EnemySpawnTableEntry::new(EnemyType::RockThree, 4, 1),
+    EnemySpawnTableEntry::new(EnemyType::Trunk, 5, 3),
+    EnemySpawnTableEntry::new(EnemyType::Bee, 5, 3),
+    EnemySpawnTableEntry::new(EnemyType::Rino, 6, 3),
+    EnemySpawnTableEntry::new(EnemyType::Bat, 6, 3),
+];
+
+/// Returns the spawn table of the given difficulty
+fn spawn_table_from_difficulty(difficulty: &Difficulty) -> &'static [EnemySpawnTableEntry] {
+    match difficulty {
+        Difficulty::Easy => &EASY_ENEMY_SPAWN_TABLE,
+        Difficulty::Medium => &MEDIUM_ENEMY_SPAWN_TABLE,
+        Difficulty::Hard => &HARD_ENEMY_SPAWN_TABLE,
+    }
+}
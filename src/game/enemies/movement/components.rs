@@ -2,39 +2,64 @@ use super::*;
 
 use rand::{seq::SliceRandom, Rng};
 
-/// Component used to track where an enemy entity was spawned
+/// Component used to track which route of the map an enemy entity currently walks along
 #[derive(Reflect, Component, Debug, Default, PartialEq, Copy, Clone)]
 #[reflect(Component)]
-pub enum EnemySpawnPoint {
-    #[default]
-    TopLeft,
-    TopRight,
-    Left,
-    Right,
-    BottomLeft,
-    BottomRight,
+pub struct EnemySpawnPoint {
+    pub route: usize,
 }
 
-/// Component used to tag the number of turns an enemy entity made so far (for pathfinding)
+/// Component used to track the distance an enemy entity walked along its current route so far
 #[derive(Reflect, Component, Default, Debug, PartialEq, Copy, Clone)]
 #[reflect(Component)]
-pub struct PathCheckpointNumber {
-    pub number: usize,
+pub struct PathProgress {
+    pub distance: f32,
+    /// Seed for choosing the branches at junctions. Enemies with the same seed take the same branches
+    pub branch_seed: u64,
+    pub junctions_passed: u32,
+}
+
+/// Component used to track how far an enemy walks beside the centerline of its route, so
+/// enemies on the same road can spread out and pass each other. Positive is left of the direction of travel
+#[derive(Reflect, Component, Default, Debug, PartialEq, Copy, Clone)]
+#[reflect(Component)]
+pub struct LaneOffset {
+    pub offset: f32,
+}
+
+/// Component used to slow an enemy down for a while
+#[derive(Reflect, Component, Default, Debug)]
+#[reflect(Component)]
+pub struct Slowed {
+    /// Factor the speed of the enemy is multiplied with
+    pub factor: f32,
+    pub timer: Timer,
+}
+
+/// Component used to stop an enemy for a while, e.g. by the freeze power-up.
+/// A slowdown of the enemy is paused until it thaws
+#[derive(Reflect, Component, Default, Debug)]
+#[reflect(Component)]
+pub struct Frozen {
+    pub timer: Timer,
 }
 
 impl EnemySpawnPoint {
-    pub fn next_spawn_point_excluding_self(self, rng: &mut impl Rng) -> EnemySpawnPoint {
-        use EnemySpawnPoint::*;
-        match self {
-            TopLeft => [BottomLeft, BottomRight, TopRight, Right, Left],
-            TopRight => [BottomLeft, BottomRight, TopLeft, Right, Left],
-            Left => [BottomLeft, BottomRight, TopRight, TopLeft, Right],
-            Right => [BottomLeft, BottomRight, TopRight, TopLeft, Left],
-            BottomLeft => [Right, BottomRight, TopRight, TopLeft, Left],
-            BottomRight => [BottomLeft, Right, TopRight, TopLeft, Left],
+    /// Returns a random one of the given spawn routes. The own route is only returned if it is the only one
+    pub fn next_spawn_point_excluding_self(
+        self,
+        spawn_routes: &[usize],
+        rng: &mut impl Rng,
+    ) -> EnemySpawnPoint {
+        let other_routes: Vec<usize> = spawn_routes
+            .iter()
+            .copied()
+            .filter(|&route| route != self.route || spawn_routes.len() == 1)
+            .collect();
+        EnemySpawnPoint {
+            route: *other_routes
+                .choose(rng)
+                .expect("Map should have at least one spawn route"),
         }
-        .choose(rng)
-        .copied()
-        .unwrap()
     }
 }
@@ -1,238 +1,203 @@
 use effects::components::{Explosion, ExplosionAnimation};
-use enemies::rounds_and_indicators::resources::{NumberOfEnemiesUnlivedThisRound, StreakIndicator};
+use enemies::rounds_and_indicators::resources::{
+    NumberOfEnemiesUnlivedThisRound, RoundSummary, StreakIndicator,
+};
 
+use crate::game::boss::components::BossWord;
+use crate::game::enemies::spatial_index::resources::EnemySpatialIndex;
+use crate::game::map::resources::LoadedMap;
 use crate::menu::systems::Restart;
+use crate::{WORLD_HEIGHT, WORLD_WIDTH};
 
 use super::*;
 
-/// The movement is calculated relatively to the screen height/width in order to work for multiple resolutions
-/// One grid block on the background corresponds to:
-/// In width: 0.03333
-/// In height: 0.05263
-#[derive(PartialEq, Eq)]
-pub enum TurnInstruction {
-    Left,
-    Right,
-    Up,
-    Down,
-}
-
-use bevy::window::PrimaryWindow;
-use TurnInstruction::*;
-
-/// Window width in dev
-const WINDOW_WIDTH_IN_DEV: f32 = 1856.0;
-/// Window height in dev
-const WINDOW_HEIGHT_IN_DEV: f32 = 1018.0;
-
-/// Checkpoints for the route of the enemies coming from the top left of the screen in ratio of screen width / height coordinates
-const TOP_LEFT_ROUTE_CHECKPOINTS: [(f32, f32); 6] = [
-    (-0.31, 0.5),
-    (-0.31, 0.31578),
-    (0.0, 0.31778),
-    (0.0, -0.31178),
-    (0.20899, -0.31178),
-    (0.20899, -1.0),
-];
-/// Turn instructions of the enemies coming from the top left of the screen
-const TOP_LEFT_ROUTE_TURN_INSTRUCTIONS: [TurnInstruction; 6] =
-    [Down, Right, Down, Right, Down, Down];
-
-/// Checkpoints for the route of the enemies coming from the bottom right of the screen in ratio of screen width / height coordinates
-const BOTTOM_RIGHT_ROUTE_CHECKPOINTS: [(f32, f32); 6] = [
-    (0.20899, -0.5),
-    (0.20899, -0.31178),
-    (0.0, -0.31178),
-    (0.0, 0.31778),
-    (-0.31, 0.31578),
-    (-0.31, 1.0),
-];
-/// Turn instructions of the enemies coming from the bottom right of the screen
-const BOTTOM_RIGHT_ROUTE_TURN_INSTRUCTIONS: [TurnInstruction; 6] = [Up, Left, Up, Left, Up, Up];
-
-/// Checkpoints for the route of the enemies coming from the top right of the screen in ratio of screen width / height coordinates
-const TOP_RIGHT_ROUTE_CHECKPOINTS: [(f32, f32); 9] = [
-    (0.27664, 0.5),
-    (0.27664, 0.20252),
-    (0.10299, 0.20252),
-    (0.10299, 0.31778),
-    (0.0, 0.31778),
-    (0.0, -0.18552),
-    (-0.20698, -0.18552),
-    (-0.20698, -0.36841),
-    (-1.0, -0.36841),
-];
-/// Turn instructions of the enemies coming from the top right of the screen
-const TOP_RIGHT_ROUTE_TURN_INSTRUCTIONS: [TurnInstruction; 9] =
-    [Down, Left, Up, Left, Down, Left, Down, Left, Left];
-
-/// Checkpoints for the route of the enemies coming from the bottom left of the screen in ratio of screen width / height coordinates
-const BOTTOM_LEFT_ROUTE_CHECKPOINTS: [(f32, f32); 9] = [
-    (-0.5, -0.36841),
-    (-0.20698, -0.36841),
-    (-0.20698, -0.18552),
-    (0.0, -0.18552),
-    (0.0, 0.31778),
-    (0.10299, 0.31778),
-    (0.10299, 0.20252),
-    (0.27664, 0.20252),
-    (0.27664, 1.0),
-];
-/// Turn instructions of the enemies coming from the bottom left of the screen
-const BOTTOM_LEFT_ROUTE_TURN_INSTRUCTIONS: [TurnInstruction; 9] =
-    [Right, Up, Right, Up, Right, Down, Right, Up, Up];
-
-/// Checkpoints for the route of the enemies coming from the left of the screen in ratio of screen width / height coordinates
-const LEFT_ROUTE_CHECKPOINTS: [(f32, f32); 2] = [(-0.5, 0.01), (1.0, 0.01)];
-/// Turn instructions of the enemies coming from the left of the screen
-const LEFT_ROUTE_TURN_INSTRUCTIONS: [TurnInstruction; 2] = [Right, Right];
+/// Left edge of the hitbox of the castle relative to its center
+const CASTLE_HITBOX_LEFT: f32 = -150.0;
+/// Right edge of the hitbox of the castle relative to its center
+const CASTLE_HITBOX_RIGHT: f32 = 150.0;
+/// Bottom edge of the hitbox of the castle relative to its center
+const CASTLE_HITBOX_BOTTOM: f32 = -134.0;
+/// Top edge of the hitbox of the castle relative to its center
+const CASTLE_HITBOX_TOP: f32 = 71.0;
+/// Height of the side gates of the castle relative to its center
+const CASTLE_GATE_HEIGHT: f32 = -54.0;
+/// Minimum horizontal part of the direction of travel for sprites to be flipped (to not flip on vertical roads)
+const SPRITE_FLIP_THRESHOLD: f32 = 0.1;
+/// Maximum distance enemies walk beside the centerline of their route, about half the road width
+const MAX_LANE_OFFSET: f32 = 20.0;
+/// Distance up to which enemies push each other to the side
+const SEPARATION_RADIUS: f32 = 45.0;
+/// Speed with which enemies that are too close move sideways - in units per second
+const SEPARATION_SPEED: f32 = 90.0;
+/// Speed with which enemies without neighbours return to the centerline - fraction per second
+const LANE_RETURN_SPEED: f32 = 1.0;
+/// Enemies are drawn between this z value and zero, the lower on the screen the further in front
+const ENEMY_Y_SORT_DEPTH: f32 = 0.4;
+/// Distance to the castle gates within which an explosion is spawned at the gate
+const CASTLE_GATE_TOLERANCE: f32 = 15.0 + MAX_LANE_OFFSET;
 
-/// Checkpoints for the route of the enemies coming from the right of the screen in ratio of screen width / height coordinates
-const RIGHT_ROUTE_CHECKPOINTS: [(f32, f32); 2] = [(0.5, 0.01), (-1.0, 0.01)];
-/// Turn instructions of the enemies coming from the right of the screen
-const RIGHT_ROUTE_TURN_INSTRUCTIONS: [TurnInstruction; 2] = [Left, Left];
-
-/// System for the movement of enemies and bosses based on the checkpoints and turn instructions
+/// System for the movement of enemies and bosses along their route by arc length.
+/// At the end of a route they continue on one of its branches.
+/// Sprites are flipped to face the direction of travel and sorted by their height on screen
 pub fn update_position_of_enemies_and_bosses(
     mut enemy_query: Query<(
         &Speed,
-        &EnemySpawnPoint,
-        &mut PathCheckpointNumber,
+        &mut EnemySpawnPoint,
+        &mut PathProgress,
         &mut Transform,
+        Option<&mut Sprite>,
+        Option<&LaneOffset>,
+        Option<&Slowed>,
+        Has<Frozen>,
     )>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    loaded_map: Res<LoadedMap>,
     time: Res<Time>,
 ) {
-    for (speed, spawn_point, mut path_checkpoint_number, mut transform) in enemy_query.iter_mut() {
-        let window = window_query
-            .get_single()
-            .expect("Primary window should exist");
-
-        let (x_scale_next_checkpoint, y_scale_next_checkpoint) =
-            get_x_y_scale_of_checkpoint(spawn_point, path_checkpoint_number.number + 1, &window);
-        let next_checkpoint_vec = Vec3::new(
-            window.width() * x_scale_next_checkpoint,
-            window.height() * y_scale_next_checkpoint,
-            0.0,
-        );
-        let turn_instruction =
-            get_current_turn_instruction(spawn_point, path_checkpoint_number.number);
-        let translation = speed.speed
-            * time.delta_seconds()
-            * get_translation_from_turn_instruction(&turn_instruction);
-        let new_position = transform.translation + translation;
-        // Check if next path checkpoint is reached
-        if match turn_instruction {
-            // Who does this?
-            Down => new_position.y < next_checkpoint_vec.y,
-            Up => new_position.y > next_checkpoint_vec.y,
-            Left => new_position.x < next_checkpoint_vec.x,
-            Right => new_position.x > next_checkpoint_vec.x,
-        } {
-            // next path checkpoint is reached
-            let distance_to_go =
-                translation.length() - transform.translation.distance(next_checkpoint_vec);
-            path_checkpoint_number.number += 1;
-            let turn_instruction =
-                get_current_turn_instruction(spawn_point, path_checkpoint_number.number);
-            transform.translation +=
-                distance_to_go * get_translation_from_turn_instruction(&turn_instruction);
+    for (
+        speed,
+        mut spawn_point,
+        mut path_progress,
+        mut transform,
+        sprite,
+        lane_offset,
+        slowed,
+        frozen,
+    ) in enemy_query.iter_mut()
+    {
+        let mut route = loaded_map.route(&spawn_point);
+        let slowdown = if frozen {
+            0.0
         } else {
-            // next path checkpoint is not yet reached
-            transform.translation = new_position;
+            slowed.map_or(1.0, |slowed| slowed.factor)
+        };
+        path_progress.distance += speed.speed * slowdown * time.delta_seconds();
+        // Continue on a branch once the end of the route is reached. The number of branches taken
+        // per frame is bounded in case of routes without length branching into each other
+        for _ in 0..loaded_map.routes.len() {
+            if path_progress.distance <= route.length() {
+                break;
+            }
+            let Some(branch) =
+                route.choose_branch(path_progress.branch_seed, path_progress.junctions_passed)
+            else {
+                break;
+            };
+            path_progress.distance -= route.length();
+            path_progress.junctions_passed += 1;
+            spawn_point.route = branch;
+            route = loaded_map.route(&spawn_point);
+        }
+        let direction = route.direction_at(path_progress.distance);
+        let lane_offset = lane_offset.map_or(0.0, |lane_offset| lane_offset.offset);
+        let position = route.position_at(path_progress.distance) + direction.perp() * lane_offset;
+        transform.translation = position.extend(y_sorted_z_value(position.y));
+
+        if let Some(mut sprite) = sprite {
+            if direction.x.abs() > SPRITE_FLIP_THRESHOLD {
+                sprite.flip_x = direction.x > 0.0;
+            }
         }
     }
 }
 
-/// Translates the spawn point enum to a transform
-pub fn generate_spawn_point_transform_from_enum(
-    enemy_spawn_point_enum: EnemySpawnPoint,
-    window: &Window,
-) -> Transform {
-    let (x_scale, y_scale) = get_x_y_scale_of_checkpoint(&enemy_spawn_point_enum, 0, window);
-    Transform::from_xyz(window.width() * x_scale, window.height() * y_scale, 0.0)
+/// System that removes slowdowns from enemies once they wore off
+pub fn wear_off_slowdowns(
+    mut commands: Commands,
+    mut frozen_query: Query<(Entity, &mut Frozen)>,
+    mut slowed_query: Query<(Entity, &mut Slowed), Without<Frozen>>,
+    time: Res<Time>,
+) {
+    for (entity, mut frozen) in frozen_query.iter_mut() {
+        frozen.timer.tick(time.delta());
+        if frozen.timer.finished() {
+            commands.entity(entity).remove::<Frozen>();
+        }
+    }
+    // Slowdowns of frozen enemies only continue to wear off once they thawed
+    for (entity, mut slowed) in slowed_query.iter_mut() {
+        slowed.timer.tick(time.delta());
+        if slowed.timer.finished() {
+            commands.entity(entity).remove::<Slowed>();
+        }
+    }
 }
 
-/// Returns bool with whether sprite needs to be flipped from spawnpoint depending if spawn point is towards left or right of screen
-pub fn check_if_sprite_needs_to_be_flipped_from_spawnpoint(spawn_point: EnemySpawnPoint) -> bool {
-    match spawn_point {
-        EnemySpawnPoint::TopLeft => true,
-        EnemySpawnPoint::TopRight => false,
-        EnemySpawnPoint::Left => true,
-        EnemySpawnPoint::Right => false,
-        EnemySpawnPoint::BottomLeft => true,
-        EnemySpawnPoint::BottomRight => false,
-    }
+/// Returns the z value for an enemy at the given height so that lower enemies are drawn in front
+fn y_sorted_z_value(y: f32) -> f32 {
+    ENEMY_Y_SORT_DEPTH * (0.5 - y / WORLD_HEIGHT).clamp(0.0, 1.0)
 }
 
-/// Returns a translation vector with the direction to go in based on the given turn instruction
-fn get_translation_from_turn_instruction(turn_instruction: &TurnInstruction) -> Vec3 {
-    use TurnInstruction::*;
-    match turn_instruction {
-        Down => Vec3::new(0.0, -1.0, 0.0),
-        Up => Vec3::new(0.0, 1.0, 0.0),
-        Left => Vec3::new(-1.0, 0.0, 0.0),
-        Right => Vec3::new(1.0, 0.0, 0.0),
+/// System that pushes enemies that are close to each other to different sides of their road.
+/// Enemies on the same centerline are split up by their entity, so faster enemies visibly pass slower ones.
+/// Enemies without neighbours slowly return to the centerline
+pub fn separate_enemies_on_the_road(
+    mut enemy_query: Query<(
+        Entity,
+        &Transform,
+        &EnemySpawnPoint,
+        &PathProgress,
+        &mut LaneOffset,
+    )>,
+    other_enemy_query: Query<&Transform, With<Enemy>>,
+    enemy_spatial_index: Res<EnemySpatialIndex>,
+    loaded_map: Res<LoadedMap>,
+    time: Res<Time>,
+) {
+    for (entity, transform, spawn_point, path_progress, mut lane_offset) in enemy_query.iter_mut() {
+        let position = transform.translation.truncate();
+        let left = loaded_map
+            .route(spawn_point)
+            .direction_at(path_progress.distance)
+            .perp();
+
+        let mut push = 0.0;
+        let neighbourhood = Rect::from_center_size(position, Vec2::splat(2.0 * SEPARATION_RADIUS));
+        for neighbour in enemy_spatial_index.grid.query(neighbourhood) {
+            if neighbour == entity {
+                continue;
+            }
+            let Ok(neighbour_transform) = other_enemy_query.get(neighbour) else {
+                continue;
+            };
+            let away_from_neighbour = position - neighbour_transform.translation.truncate();
+            let distance = away_from_neighbour.length();
+            if distance >= SEPARATION_RADIUS {
+                continue;
+            }
+            let sideways = away_from_neighbour.dot(left);
+            let side = if sideways.abs() > f32::EPSILON {
+                sideways.signum()
+            } else if entity > neighbour {
+                1.0
+            } else {
+                -1.0
+            };
+            push += side * (1.0 - distance / SEPARATION_RADIUS);
+        }
+
+        let new_offset = if push == 0.0 {
+            lane_offset.offset * (1.0 - LANE_RETURN_SPEED * time.delta_seconds()).max(0.0)
+        } else {
+            lane_offset.offset + push.clamp(-1.0, 1.0) * SEPARATION_SPEED * time.delta_seconds()
+        };
+        lane_offset.offset = new_offset.clamp(-MAX_LANE_OFFSET, MAX_LANE_OFFSET);
     }
 }
 
-/// Returns the xy scale of checkpoints given the spawn point and checkpoint number an enemy has reached
-fn get_x_y_scale_of_checkpoint(
+/// Translates the spawn point to a transform at the start of its route
+pub fn generate_spawn_point_transform(
     spawn_point: &EnemySpawnPoint,
-    check_point_number: usize,
-    window: &Window,
-) -> (f32, f32) {
-    let (x_scale, y_scale) = *match spawn_point {
-        EnemySpawnPoint::TopLeft => TOP_LEFT_ROUTE_CHECKPOINTS
-            .get(check_point_number)
-            .expect("Turning Point should exist"),
-        EnemySpawnPoint::TopRight => TOP_RIGHT_ROUTE_CHECKPOINTS
-            .get(check_point_number)
-            .expect("Turning Point should exist"),
-        EnemySpawnPoint::Right => RIGHT_ROUTE_CHECKPOINTS
-            .get(check_point_number)
-            .expect("Turning Point should exist"),
-        EnemySpawnPoint::Left => LEFT_ROUTE_CHECKPOINTS
-            .get(check_point_number)
-            .expect("Turning Point should exist"),
-        EnemySpawnPoint::BottomLeft => BOTTOM_LEFT_ROUTE_CHECKPOINTS
-            .get(check_point_number)
-            .expect("Turning Point should exist"),
-        EnemySpawnPoint::BottomRight => BOTTOM_RIGHT_ROUTE_CHECKPOINTS
-            .get(check_point_number)
-            .expect("Turning Point should exist"),
-    };
-    (
-        x_scale * (WINDOW_WIDTH_IN_DEV / window.width()),
-        y_scale * (WINDOW_HEIGHT_IN_DEV / window.height()),
-    )
+    loaded_map: &LoadedMap,
+) -> Transform {
+    Transform::from_translation(loaded_map.route(spawn_point).position_at(0.0).extend(0.0))
 }
 
-/// Returns the current turn instruction based on the spawn point and current check point number of an enemy
-pub fn get_current_turn_instruction(
+/// Returns bool with whether sprite needs to be flipped from spawnpoint depending if spawn point is towards left or right of screen
+pub fn check_if_sprite_needs_to_be_flipped_from_spawnpoint(
     spawn_point: &EnemySpawnPoint,
-    check_point_number: usize,
-) -> &TurnInstruction {
-    match spawn_point {
-        EnemySpawnPoint::TopLeft => TOP_LEFT_ROUTE_TURN_INSTRUCTIONS
-            .get(check_point_number)
-            .expect("Turn instruction should exist"),
-        EnemySpawnPoint::TopRight => TOP_RIGHT_ROUTE_TURN_INSTRUCTIONS
-            .get(check_point_number)
-            .expect("Turn instruction should exist"),
-        EnemySpawnPoint::Left => LEFT_ROUTE_TURN_INSTRUCTIONS
-            .get(check_point_number)
-            .expect("Turn instruction should exist"),
-        EnemySpawnPoint::Right => RIGHT_ROUTE_TURN_INSTRUCTIONS
-            .get(check_point_number)
-            .expect("Turn instruction should exist"),
-        EnemySpawnPoint::BottomLeft => BOTTOM_LEFT_ROUTE_TURN_INSTRUCTIONS
-            .get(check_point_number)
-            .expect("Turn instruction should exist"),
-        EnemySpawnPoint::BottomRight => BOTTOM_RIGHT_ROUTE_TURN_INSTRUCTIONS
-            .get(check_point_number)
-            .expect("Turn instruction should exist"),
-    }
+    loaded_map: &LoadedMap,
+) -> bool {
+    loaded_map.route(spawn_point).starts_on_the_left()
 }
 
 /// System for despawning enemies when they are out of screen
@@ -241,108 +206,161 @@ pub fn despawn_enemy_if_out_of_screen(
     enemy_query: Query<(Entity, &Transform), With<Enemy>>,
     mut number_of_enemies_unlived_current_round: ResMut<NumberOfEnemiesUnlivedThisRound>,
     mut streak_indicator: ResMut<StreakIndicator>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut round_summary: ResMut<RoundSummary>,
 ) {
-    let window = window_query.get_single().expect("Window should exist");
     for (enemy_entity, enemy_transform) in &enemy_query {
-        if enemy_transform.translation.x > window.width() * 0.7
-            || enemy_transform.translation.x < -window.width() * 0.7
-            || enemy_transform.translation.y > window.height() * 0.7
-            || enemy_transform.translation.y < -window.height() * 0.7
+        if enemy_transform.translation.x > WORLD_WIDTH * 0.7
+            || enemy_transform.translation.x < -WORLD_WIDTH * 0.7
+            || enemy_transform.translation.y > WORLD_HEIGHT * 0.7
+            || enemy_transform.translation.y < -WORLD_HEIGHT * 0.7
         {
             // Despawn enemy and set resources accordingly
             commands.entity(enemy_entity).despawn_recursive();
             number_of_enemies_unlived_current_round.number += 1;
             streak_indicator.number = 0;
+            round_summary.words_missed += 1;
         }
     }
 }
 
-/// System for tracking the collision of enemies with the castle
+/// System for tracking the collision of enemies with the castle.
+/// Words of bosses don't collide on their own, the boss hits the castle with all of them
 pub fn enemy_collision_with_castle(
     mut commands: Commands,
-    mut enemy_query: Query<(Entity, &Transform), With<Enemy>>,
+    enemy_query: Query<(&Transform, &Name), (With<Enemy>, Without<BossWord>)>,
     castle_query: Query<&Transform, With<castle::components::Castle>>,
+    enemy_spatial_index: Res<EnemySpatialIndex>,
     mut number_of_enemies_unlived_current_round: ResMut<NumberOfEnemiesUnlivedThisRound>,
     mut streak_indicator: ResMut<StreakIndicator>,
     mut number_of_lives_left: ResMut<castle::resources::NumberOfLivesLeft>,
     asset_server: Res<AssetServer>,
     mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
     mut enemies_being_typed: ResMut<EnemiesBeingTyped>,
+    mut round_summary: ResMut<RoundSummary>,
 ) {
-    if let Ok(_) = castle_query.get_single() {
-        for (entity, transform) in enemy_query.iter_mut() {
-            if transform.translation.y > -80.0
-                && transform.translation.y < 125.0
-                && transform.translation.x > -150.0
-                && transform.translation.x < 150.0
+    if let Ok(castle_transform) = castle_query.get_single() {
+        // Only enemies that are inside the hitbox of the castle are returned by the spatial index
+        for entity in enemy_spatial_index
+            .grid
+            .query(castle_hitbox(castle_transform))
+        {
+            let Ok((transform, name)) = enemy_query.get(entity) else {
+                continue;
+            };
+            // Position of the enemy relative to the castle
+            let relative_translation = transform.translation - castle_transform.translation;
+            // Enemy hit castle. Check where collision happened
+            let castle_gate_height = castle_transform.translation.y + CASTLE_GATE_HEIGHT;
+            let explosion_transform_option = if (transform.translation.y - castle_gate_height).abs()
+                < CASTLE_GATE_TOLERANCE
+                && relative_translation.x < 0.0
             {
-                // Enemy hit castle. Check where collision happened
-                let explosion_transform_option = if transform.translation.y.abs() < 15.0
-                    && transform.translation.x < 0.0
-                {
-                    Some(Vec3::new(-150.0, 0.0, 10.0))
-                } else if transform.translation.y.abs() < 15.0 && transform.translation.x > 0.0 {
-                    Some(Vec3::new(150.0, 0.0, 10.0))
-                } else if transform.translation.x.abs() < 15.0 && transform.translation.y < 0.0 {
-                    Some(Vec3::new(0.0, -80.0, 10.0))
-                } else if transform.translation.x.abs() < 15.0 && transform.translation.y > 0.0 {
-                    Some(Vec3::new(0.0, 125.0, 10.0))
-                } else {
-                    None
-                };
-
-                if let Some(explosion_translation) = explosion_transform_option {
-                    let explosion_transform = Transform::from_translation(explosion_translation);
-                    // Spawn explosion/death animation
-                    let texture_handle: Handle<Image> =
-                        asset_server.load("sprites/effects/explosion.png");
-                    let texture_atlas =
-                        TextureAtlasLayout::from_grid(Vec2::new(192.0, 192.0), 9, 1, None, None);
-                    let texture_atlas_handle: Handle<TextureAtlasLayout> =
-                        texture_atlases.add(texture_atlas);
-
-                    let explosion_animation: ExplosionAnimation = ExplosionAnimation {
-                        length_of_animation: 9,
-                        animation_timer: Timer::from_seconds(
-                            effects::components::EXPLOSION_ANIMATION_SPEED,
-                            TimerMode::Repeating,
-                        ),
-                    };
+                Some(Vec3::new(
+                    castle_transform.translation.x + CASTLE_HITBOX_LEFT,
+                    castle_gate_height,
+                    10.0,
+                ))
+            } else if (transform.translation.y - castle_gate_height).abs() < CASTLE_GATE_TOLERANCE
+                && relative_translation.x > 0.0
+            {
+                Some(Vec3::new(
+                    castle_transform.translation.x + CASTLE_HITBOX_RIGHT,
+                    castle_gate_height,
+                    10.0,
+                ))
+            } else if relative_translation.x.abs() < CASTLE_GATE_TOLERANCE
+                && relative_translation.y < 0.0
+            {
+                Some(Vec3::new(
+                    castle_transform.translation.x,
+                    castle_transform.translation.y + CASTLE_HITBOX_BOTTOM,
+                    10.0,
+                ))
+            } else if relative_translation.x.abs() < CASTLE_GATE_TOLERANCE
+                && relative_translation.y > 0.0
+            {
+                Some(Vec3::new(
+                    castle_transform.translation.x,
+                    castle_transform.translation.y + CASTLE_HITBOX_TOP,
+                    10.0,
+                ))
+            } else {
+                None
+            };
 
-                    commands.spawn((
-                        SpriteSheetBundle {
-                            transform: explosion_transform,
-                            atlas: TextureAtlas {
-                                layout: texture_atlas_handle,
-                                index: 0,
-                                ..default()
-                            },
-                            texture: texture_handle,
-                            ..default()
-                        },
-                        Explosion {},
-                        explosion_animation,
-                    ));
-                }
-                // Despawn enemy and set resources accordingly
-                commands.entity(entity).despawn_recursive();
-                number_of_enemies_unlived_current_round.number += 1;
-                streak_indicator.number = 0;
-                if enemies_being_typed.vec_of_enemies.contains(&entity) {
-                    enemies_being_typed.vec_of_enemies.retain(|&x| x != entity);
-                    if enemies_being_typed.vec_of_enemies.len() == 0 {
-                        enemies_being_typed.indicator = false;
-                    }
-                }
-                if let Some(val) = number_of_lives_left.number.checked_sub(1) {
-                    number_of_lives_left.number = val;
+            if let Some(explosion_translation) = explosion_transform_option {
+                spawn_explosion(
+                    &mut commands,
+                    explosion_translation,
+                    &asset_server,
+                    &mut texture_atlases,
+                );
+            }
+            // Despawn enemy and set resources accordingly
+            commands.entity(entity).despawn_recursive();
+            number_of_enemies_unlived_current_round.number += 1;
+            streak_indicator.number = 0;
+            if enemies_being_typed.vec_of_enemies.contains(&entity) {
+                enemies_being_typed.vec_of_enemies.retain(|&x| x != entity);
+                if enemies_being_typed.vec_of_enemies.len() == 0 {
+                    enemies_being_typed.indicator = false;
                 }
             }
+            if let Some(val) = number_of_lives_left.number.checked_sub(1) {
+                number_of_lives_left.number = val;
+                round_summary.lives_lost += 1;
+            }
+            round_summary.words_missed += 1;
+            round_summary.words_at_castle.push(name.to_string());
         }
     }
 }
 
+/// Returns the hitbox of the castle with the given transform
+pub fn castle_hitbox(castle_transform: &Transform) -> Rect {
+    Rect::new(
+        castle_transform.translation.x + CASTLE_HITBOX_LEFT,
+        castle_transform.translation.y + CASTLE_HITBOX_BOTTOM,
+        castle_transform.translation.x + CASTLE_HITBOX_RIGHT,
+        castle_transform.translation.y + CASTLE_HITBOX_TOP,
+    )
+}
+
+/// Spawns an explosion at the given translation
+pub fn spawn_explosion(
+    commands: &mut Commands,
+    translation: Vec3,
+    asset_server: &AssetServer,
+    texture_atlases: &mut Assets<TextureAtlasLayout>,
+) {
+    let texture_handle: Handle<Image> = asset_server.load("sprites/effects/explosion.png");
+    let texture_atlas = TextureAtlasLayout::from_grid(Vec2::new(192.0, 192.0), 9, 1, None, None);
+    let texture_atlas_handle: Handle<TextureAtlasLayout> = texture_atlases.add(texture_atlas);
+
+    let explosion_animation: ExplosionAnimation = ExplosionAnimation {
+        length_of_animation: 9,
+        animation_timer: Timer::from_seconds(
+            effects::components::EXPLOSION_ANIMATION_SPEED,
+            TimerMode::Repeating,
+        ),
+    };
+
+    commands.spawn((
+        SpriteSheetBundle {
+            transform: Transform::from_translation(translation),
+            atlas: TextureAtlas {
+                layout: texture_atlas_handle,
+                index: 0,
+                ..default()
+            },
+            texture: texture_handle,
+            ..default()
+        },
+        Explosion {},
+        explosion_animation,
+    ));
+}
+
 /// Despawn the enemies on restart event
 pub fn despawn_enemies_on_restart(
     mut commands: Commands,
@@ -42,7 +42,7 @@ impl Default for EnemiesBeingTyped {
 pub struct WordsHandle(pub Handle<Words>);
 
 /// Resource for keeping track of where the last enemy was spawned.
-/// Default spawn point is left
+/// Default spawn point is the first route of the map
 #[derive(Reflect, Resource, Default)]
 #[reflect(Resource)]
 pub struct LastEnemySpawnPoint {
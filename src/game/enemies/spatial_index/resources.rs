@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use super::*;
+
+/// Size of the cells of the enemy spatial index in world units
+pub const ENEMY_GRID_CELL_SIZE: f32 = 100.0;
+
+/// Uniform grid that stores items together with the area they cover.
+/// Lookups only visit the cells an area covers instead of every stored item
+#[derive(Debug, Clone)]
+pub struct SpatialGrid<T> {
+    cell_size: f32,
+    cells: HashMap<IVec2, Vec<(T, Rect)>>,
+}
+
+impl<T: Copy> SpatialGrid<T> {
+    /// Creates an empty grid with cells of the given size
+    pub fn new(cell_size: f32) -> SpatialGrid<T> {
+        SpatialGrid {
+            cell_size,
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Removes all items while keeping the allocated cells
+    pub fn clear(&mut self) {
+        self.cells.values_mut().for_each(Vec::clear);
+    }
+
+    /// Adds an item to every cell its area covers
+    pub fn insert(&mut self, item: T, area: Rect) {
+        for cell in self.cells_of(area) {
+            self.cells.entry(cell).or_default().push((item, area));
+        }
+    }
+
+    /// Returns the items whose area overlaps the given area.
+    /// Items covering several cells may be returned more than once
+    pub fn query(&self, area: Rect) -> impl Iterator<Item = T> + '_ {
+        self.cells_of(area)
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .filter(move |(_, item_area)| overlaps(*item_area, area))
+            .map(|(item, _)| *item)
+    }
+
+    /// Returns whether any stored item overlaps the given area
+    pub fn overlaps_any(&self, area: Rect) -> bool {
+        self.query(area).next().is_some()
+    }
+
+    /// Returns the cells that an area covers
+    fn cells_of(&self, area: Rect) -> impl Iterator<Item = IVec2> {
+        let min = (area.min / self.cell_size).floor().as_ivec2();
+        let max = (area.max / self.cell_size).floor().as_ivec2();
+        (min.x..=max.x).flat_map(move |x| (min.y..=max.y).map(move |y| IVec2::new(x, y)))
+    }
+}
+
+/// Returns whether the interiors of two areas overlap. Areas that only touch don't overlap,
+/// a point (an area of size zero) overlaps an area if it lies strictly inside
+fn overlaps(first: Rect, second: Rect) -> bool {
+    first.min.x < second.max.x
+        && second.min.x < first.max.x
+        && first.min.y < second.max.y
+        && second.min.y < first.max.y
+}
+
+/// Resource holding the positions of all enemies, rebuilt once per frame after enemies moved
+#[derive(Resource)]
+pub struct EnemySpatialIndex {
+    pub grid: SpatialGrid<Entity>,
+}
+
+impl Default for EnemySpatialIndex {
+    fn default() -> EnemySpatialIndex {
+        EnemySpatialIndex {
+            grid: SpatialGrid::new(ENEMY_GRID_CELL_SIZE),
+        }
+    }
+}
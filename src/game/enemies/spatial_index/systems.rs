@@ -0,0 +1,16 @@
+use super::resources::*;
+use super::*;
+
+/// Rebuilds the enemy spatial index from the current positions of all enemies
+pub fn update_enemy_spatial_index(
+    mut enemy_spatial_index: ResMut<EnemySpatialIndex>,
+    enemy_query: Query<(Entity, &Transform), With<Enemy>>,
+) {
+    enemy_spatial_index.grid.clear();
+    for (entity, transform) in enemy_query.iter() {
+        let position = transform.translation.truncate();
+        enemy_spatial_index
+            .grid
+            .insert(entity, Rect::from_corners(position, position));
+    }
+}
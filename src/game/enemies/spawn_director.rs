@@ -0,0 +1,7 @@
+pub mod components;
+
+pub mod resources;
+
+pub mod systems;
+
+use super::*;
@@ -0,0 +1,6 @@
+use super::*;
+
+/// Component used to identify the text of the spawn director debug overlay
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct SpawnDirectorOverlayText {}
@@ -0,0 +1,173 @@
+use super::*;
+
+use crate::game::enemies::movement::components::EnemySpawnPoint;
+use crate::game::map::resources::LoadedMap;
+use crate::game::rounds_and_indicators::resources::Difficulty;
+
+/// Typing speed the spawn director plans for on easy difficulty - in words per minute
+pub const TARGET_WPM_EASY_DIFFICULTY: f32 = 30.0;
+/// Typing speed the spawn director plans for on medium difficulty - in words per minute
+pub const TARGET_WPM_MEDIUM_DIFFICULTY: f32 = 45.0;
+/// Typing speed the spawn director plans for on hard difficulty - in words per minute
+pub const TARGET_WPM_HARD_DIFFICULTY: f32 = 60.0;
+/// Number of letters counted as one word for words per minute
+const LETTERS_PER_WORD: f32 = 5.0;
+/// Time needed to react to an enemy before typing its word - in seconds
+const REACTION_TIME: f32 = 0.4;
+/// Length of the windows before and after an arrival in which the typing load is limited - in seconds
+pub const FAIRNESS_WINDOW: f32 = 4.0;
+/// Share of a fairness window that may be spent typing
+const MAX_TYPING_LOAD: f32 = 0.8;
+
+/// An enemy that was rolled by the spawner and waits for the spawn director to let it spawn
+#[derive(Reflect, Clone, Debug, Default)]
+pub struct SpawnCandidate {
+    pub spawn_point: EnemySpawnPoint,
+    pub enemy_type: EnemyType,
+    pub speed: f32,
+    pub word: String,
+    pub branch_seed: u64,
+    /// Whether the enemy has to spawn on the route of its spawn point, e.g. because a wave script says so
+    pub fixed_route: bool,
+}
+
+/// An enemy on the field together with when it is expected to arrive at the castle
+/// and how long typing its word takes. Times are in seconds since the start of the round
+#[derive(Reflect, Clone, Debug)]
+pub struct PlannedArrival {
+    pub enemy: Entity,
+    pub route: usize,
+    pub arrival_time: f32,
+    pub typing_time: f32,
+}
+
+/// Resource for the spawn director which decides on which route and when enemies spawn,
+/// such that the typing work needed to stop them is spread evenly instead of arriving all at once
+#[derive(Reflect, Resource, Default)]
+#[reflect(Resource)]
+pub struct SpawnDirector {
+    /// Seconds since the start of the round
+    pub round_time: f32,
+    pub target_wpm: f32,
+    pub pending_spawn: Option<SpawnCandidate>,
+    pub planned_arrivals: Vec<PlannedArrival>,
+}
+
+impl SpawnDirector {
+    /// Returns the typing speed the director plans for on the given difficulty
+    pub fn target_wpm_for_difficulty(difficulty: &Difficulty) -> f32 {
+        match difficulty {
+            Difficulty::Easy => TARGET_WPM_EASY_DIFFICULTY,
+            Difficulty::Medium => TARGET_WPM_MEDIUM_DIFFICULTY,
+            Difficulty::Hard => TARGET_WPM_HARD_DIFFICULTY,
+        }
+    }
+
+    /// Estimates the time needed to react to and type the given word at the target typing speed
+    pub fn typing_time(&self, word: &str) -> f32 {
+        let letters_per_second = self.target_wpm.max(1.0) * LETTERS_PER_WORD / 60.0;
+        REACTION_TIME + word.chars().count() as f32 / letters_per_second
+    }
+
+    /// Estimates when an enemy spawned at the given time on the given route would arrive at the castle
+    pub fn arrival_time(
+        &self,
+        loaded_map: &LoadedMap,
+        route: usize,
+        speed: f32,
+        spawn_time: f32,
+    ) -> f32 {
+        spawn_time + loaded_map.expected_length_from(route) / speed.max(1.0)
+    }
+
+    /// Returns the typing time of the planned arrivals in the given time span
+    pub fn typing_load_between(&self, start: f32, end: f32) -> f32 {
+        self.planned_arrivals
+            .iter()
+            .filter(|arrival| arrival.arrival_time >= start && arrival.arrival_time <= end)
+            .map(|arrival| arrival.typing_time)
+            .sum()
+    }
+
+    /// Checks whether an enemy arriving at the given time fits into the plan, i.e. the
+    /// typing load in the windows before and after its arrival stays below the limit
+    pub fn fits(&self, arrival_time: f32, typing_time: f32) -> bool {
+        let capacity = MAX_TYPING_LOAD * FAIRNESS_WINDOW;
+        [
+            (arrival_time - FAIRNESS_WINDOW, arrival_time),
+            (arrival_time, arrival_time + FAIRNESS_WINDOW),
+        ]
+        .iter()
+        .all(|&(start, end)| {
+            let load = self.typing_load_between(start, end);
+            // A single word always fits, even if it takes longer than the window allows
+            load == 0.0 || load + typing_time <= capacity
+        })
+    }
+
+    /// Chooses the spawn point for the candidate. The rolled spawn point is preferred,
+    /// otherwise the first spawn route on which the candidate fits into the plan is taken.
+    /// Candidates with a fixed route only spawn on their own route.
+    /// Returns None if the candidate fits nowhere right now and has to wait
+    pub fn choose_spawn_point(
+        &self,
+        candidate: &SpawnCandidate,
+        loaded_map: &LoadedMap,
+        spawn_time: f32,
+    ) -> Option<EnemySpawnPoint> {
+        let typing_time = self.typing_time(&candidate.word);
+        let other_routes: &[usize] = if candidate.fixed_route {
+            &[]
+        } else {
+            &loaded_map.spawn_routes
+        };
+        std::iter::once(candidate.spawn_point.route)
+            .chain(other_routes.iter().copied())
+            .find(|&route| {
+                self.fits(
+                    self.arrival_time(loaded_map, route, candidate.speed, spawn_time),
+                    typing_time,
+                )
+            })
+            .map(|route| EnemySpawnPoint { route })
+    }
+
+    /// Adds an enemy spawning at the given time to the plan
+    pub fn plan_arrival(
+        &mut self,
+        enemy: Entity,
+        candidate: &SpawnCandidate,
+        spawn_point: EnemySpawnPoint,
+        loaded_map: &LoadedMap,
+        spawn_time: f32,
+    ) {
+        let arrival = PlannedArrival {
+            enemy,
+            route: spawn_point.route,
+            arrival_time: self.arrival_time(
+                loaded_map,
+                spawn_point.route,
+                candidate.speed,
+                spawn_time,
+            ),
+            typing_time: self.typing_time(&candidate.word),
+        };
+        self.planned_arrivals.push(arrival);
+    }
+
+    /// Hands the planned arrival of a spawn warning over to the enemy spawned from it
+    pub fn replace_planned_enemy(&mut self, old_enemy: Entity, new_enemy: Entity) {
+        for arrival in self.planned_arrivals.iter_mut() {
+            if arrival.enemy == old_enemy {
+                arrival.enemy = new_enemy;
+            }
+        }
+    }
+}
+
+/// Resource for tracking whether the spawn director debug overlay is shown
+#[derive(Reflect, Resource, Default)]
+#[reflect(Resource)]
+pub struct SpawnDirectorOverlay {
+    pub visible: bool,
+}
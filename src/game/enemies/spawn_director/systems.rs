@@ -0,0 +1,199 @@
+use super::components::*;
+use super::resources::*;
+use super::*;
+
+use crate::game::enemies::spawn_warning::components::SpawnWarning;
+use crate::game::rounds_and_indicators::resources::DifficultyIndicator;
+use crate::menu::systems::Restart;
+
+/// Seconds of the plan shown on the timeline of the debug overlay
+const OVERLAY_TIMELINE_SECONDS: f32 = 30.0;
+/// Left end of the timeline of the debug overlay
+const OVERLAY_TIMELINE_START: Vec2 = Vec2::new(-800.0, -480.0);
+/// Length of the timeline of the debug overlay
+const OVERLAY_TIMELINE_WIDTH: f32 = 1600.0;
+/// Height of one row of the timeline of the debug overlay
+const OVERLAY_ROW_HEIGHT: f32 = 12.0;
+/// Colors the routes are drawn with in the debug overlay
+const OVERLAY_ROUTE_COLORS: [Color; 6] = [
+    Color::ORANGE_RED,
+    Color::AZURE,
+    Color::LIME_GREEN,
+    Color::GOLD,
+    Color::VIOLET,
+    Color::TURQUOISE,
+];
+
+/// Forgets the plan of the last round and sets the target typing speed for the current difficulty
+pub fn reset_spawn_director(
+    mut spawn_director: ResMut<SpawnDirector>,
+    difficulty_indicator: Res<DifficultyIndicator>,
+) {
+    *spawn_director = SpawnDirector {
+        target_wpm: SpawnDirector::target_wpm_for_difficulty(&difficulty_indicator.difficulty),
+        ..default()
+    };
+}
+
+/// Resets the spawn director and hides its debug overlay on restart
+pub fn reset_spawn_director_on_restart(
+    mut commands: Commands,
+    mut spawn_director: ResMut<SpawnDirector>,
+    mut spawn_director_overlay: ResMut<SpawnDirectorOverlay>,
+    overlay_text_query: Query<Entity, With<SpawnDirectorOverlayText>>,
+    mut restart_event_reader: EventReader<Restart>,
+) {
+    for _ in restart_event_reader.read() {
+        spawn_director.pending_spawn = None;
+        spawn_director.planned_arrivals.clear();
+        spawn_director_overlay.visible = false;
+        for entity in overlay_text_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Advances the clock of the spawn director and removes enemies that were typed or reached the castle
+/// from the plan, so their time is free again
+pub fn update_spawn_plan(
+    mut spawn_director: ResMut<SpawnDirector>,
+    enemy_query: Query<(), With<Enemy>>,
+    spawn_warning_query: Query<(), With<SpawnWarning>>,
+    time: Res<Time>,
+) {
+    spawn_director.round_time += time.delta_seconds();
+    // Enemies that are still announced by a warning are planned as well
+    let is_planned =
+        |entity: Entity| enemy_query.contains(entity) || spawn_warning_query.contains(entity);
+    if spawn_director
+        .planned_arrivals
+        .iter()
+        .any(|arrival| !is_planned(arrival.enemy))
+    {
+        spawn_director
+            .planned_arrivals
+            .retain(|arrival| is_planned(arrival.enemy));
+    }
+}
+
+/// Shows and hides the spawn director debug overlay when F3 is pressed
+pub fn toggle_spawn_director_overlay(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut spawn_director_overlay: ResMut<SpawnDirectorOverlay>,
+    overlay_text_query: Query<Entity, With<SpawnDirectorOverlayText>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F3) {
+        return;
+    }
+    spawn_director_overlay.visible = !spawn_director_overlay.visible;
+    if spawn_director_overlay.visible {
+        commands.spawn((
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font_size: 20.0,
+                    ..default()
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Percent(7.0),
+                left: Val::Percent(8.0),
+                ..default()
+            }),
+            SpawnDirectorOverlayText {},
+            Name::new("Spawn Director Overlay"),
+        ));
+    } else {
+        for entity in overlay_text_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Hides the spawn director debug overlay and despawns its text
+pub fn hide_spawn_director_overlay(
+    mut commands: Commands,
+    mut spawn_director_overlay: ResMut<SpawnDirectorOverlay>,
+    overlay_text_query: Query<Entity, With<SpawnDirectorOverlayText>>,
+) {
+    spawn_director_overlay.visible = false;
+    for entity in overlay_text_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Draws the plan of the spawn director as a timeline. Every enemy is a bar from when typing its word
+/// should start until its expected arrival at the castle, colored by the route it spawned on
+pub fn draw_spawn_director_overlay(
+    mut gizmos: Gizmos,
+    spawn_director: Res<SpawnDirector>,
+    spawn_director_overlay: Res<SpawnDirectorOverlay>,
+    mut overlay_text_query: Query<&mut Text, With<SpawnDirectorOverlayText>>,
+) {
+    if !spawn_director_overlay.visible {
+        return;
+    }
+    let now = spawn_director.round_time;
+    let to_timeline_x = |time: f32| {
+        OVERLAY_TIMELINE_START.x
+            + ((time - now) / OVERLAY_TIMELINE_SECONDS).clamp(0.0, 1.0) * OVERLAY_TIMELINE_WIDTH
+    };
+
+    gizmos.line_2d(
+        OVERLAY_TIMELINE_START,
+        OVERLAY_TIMELINE_START + Vec2::new(OVERLAY_TIMELINE_WIDTH, 0.0),
+        Color::WHITE,
+    );
+    // Mark the fairness windows along the timeline
+    let mut window_start = 0.0;
+    while window_start <= OVERLAY_TIMELINE_SECONDS {
+        let x = to_timeline_x(now + window_start);
+        gizmos.line_2d(
+            Vec2::new(x, OVERLAY_TIMELINE_START.y - 5.0),
+            Vec2::new(x, OVERLAY_TIMELINE_START.y + 5.0),
+            Color::WHITE,
+        );
+        window_start += FAIRNESS_WINDOW;
+    }
+
+    let mut arrivals: Vec<&PlannedArrival> = spawn_director.planned_arrivals.iter().collect();
+    arrivals.sort_by(|first, second| first.arrival_time.total_cmp(&second.arrival_time));
+    // Bars are put into the first row they don't overlap in
+    let mut row_ends: Vec<f32> = Vec::new();
+    for arrival in arrivals {
+        let start = arrival.arrival_time - arrival.typing_time;
+        let row = match row_ends.iter().position(|&row_end| row_end <= start) {
+            Some(row) => row,
+            None => {
+                row_ends.push(f32::MIN);
+                row_ends.len() - 1
+            }
+        };
+        row_ends[row] = arrival.arrival_time;
+
+        let left = to_timeline_x(start);
+        let right = to_timeline_x(arrival.arrival_time);
+        let y = OVERLAY_TIMELINE_START.y + (row as f32 + 1.0) * OVERLAY_ROW_HEIGHT;
+        gizmos.line_2d(
+            Vec2::new(left, y),
+            Vec2::new(right, y),
+            OVERLAY_ROUTE_COLORS[arrival.route % OVERLAY_ROUTE_COLORS.len()],
+        );
+    }
+
+    for mut text in overlay_text_query.iter_mut() {
+        text.sections[0].value = format!(
+            "Spawn director | target {:.0} wpm | typing load next {:.0}s: {:.1}s | planned: {} | waiting: {}",
+            spawn_director.target_wpm,
+            FAIRNESS_WINDOW,
+            spawn_director.typing_load_between(now, now + FAIRNESS_WINDOW),
+            spawn_director.planned_arrivals.len(),
+            spawn_director
+                .pending_spawn
+                .as_ref()
+                .map_or("-", |candidate| candidate.word.as_str()),
+        );
+    }
+}
@@ -0,0 +1,16 @@
+use super::*;
+
+use crate::game::enemies::movement::components::EnemySpawnPoint;
+use crate::game::enemies::spawn_director::resources::SpawnCandidate;
+
+/// Component of the warning shown at the start of a route before an enemy spawns there.
+/// Holds the enemy that spawns once the timer is finished
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct SpawnWarning {
+    pub candidate: SpawnCandidate,
+    pub spawn_point: EnemySpawnPoint,
+    /// Direction the enemy walks in when entering the screen
+    pub direction: Vec2,
+    pub timer: Timer,
+}
@@ -0,0 +1,46 @@
+use super::*;
+
+use crate::game::rounds_and_indicators::resources::Difficulty;
+
+/// Seconds a spawn is announced in advance on easy difficulty
+pub const SPAWN_WARNING_DURATION_EASY_DIFFICULTY: f32 = 2.0;
+/// Seconds a spawn is announced in advance on medium difficulty
+pub const SPAWN_WARNING_DURATION_MEDIUM_DIFFICULTY: f32 = 1.5;
+/// Seconds a spawn is announced in advance on hard difficulty
+pub const SPAWN_WARNING_DURATION_HARD_DIFFICULTY: f32 = 1.0;
+
+/// Resource for how spawns are announced. Is set from the difficulty at the start of each round
+#[derive(Reflect, Resource)]
+#[reflect(Resource)]
+pub struct SpawnWarningSettings {
+    /// Seconds between the warning and the spawn of the enemy
+    pub duration: f32,
+    /// Whether the word of the incoming enemy is shown with the warning
+    pub show_word: bool,
+}
+
+impl Default for SpawnWarningSettings {
+    fn default() -> SpawnWarningSettings {
+        SpawnWarningSettings::from_difficulty(&Difficulty::default())
+    }
+}
+
+impl SpawnWarningSettings {
+    /// Returns the settings for the given difficulty. On hard the incoming word is not shown
+    pub fn from_difficulty(difficulty: &Difficulty) -> SpawnWarningSettings {
+        match difficulty {
+            Difficulty::Easy => SpawnWarningSettings {
+                duration: SPAWN_WARNING_DURATION_EASY_DIFFICULTY,
+                show_word: true,
+            },
+            Difficulty::Medium => SpawnWarningSettings {
+                duration: SPAWN_WARNING_DURATION_MEDIUM_DIFFICULTY,
+                show_word: true,
+            },
+            Difficulty::Hard => SpawnWarningSettings {
+                duration: SPAWN_WARNING_DURATION_HARD_DIFFICULTY,
+                show_word: false,
+            },
+        }
+    }
+}
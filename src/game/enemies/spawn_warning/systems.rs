@@ -0,0 +1,166 @@
+use super::components::*;
+use super::resources::*;
+use super::*;
+
+use crate::game::enemies::movement::components::EnemySpawnPoint;
+use crate::game::enemies::spawn_director::resources::{SpawnCandidate, SpawnDirector};
+use crate::game::enemies::text::systems::HUD_BANNER_HEIGHT;
+use crate::game::map::resources::LoadedMap;
+use crate::game::rounds_and_indicators::resources::DifficultyIndicator;
+use crate::menu::systems::Restart;
+use crate::{WORLD_HEIGHT, WORLD_WIDTH};
+
+/// Distance of warnings to the edges of the screen and the hud banner
+const SPAWN_WARNING_MARGIN: f32 = 40.0;
+/// Distance of the incoming word to the warning, in the direction the enemy walks in
+const SPAWN_WARNING_WORD_DISTANCE: f32 = 70.0;
+/// Color of the warnings
+const SPAWN_WARNING_COLOR: Color = Color::ORANGE_RED;
+/// Font size of the incoming word shown with a warning
+const SPAWN_WARNING_FONT_SIZE: f32 = 36.0;
+/// Length of the arrow of a warning
+const SPAWN_WARNING_ARROW_LENGTH: f32 = 50.0;
+/// Radius of the pulse around a warning
+const SPAWN_WARNING_PULSE_RADIUS: f32 = 30.0;
+/// Number of pulses per second
+const SPAWN_WARNING_PULSE_FREQUENCY: f32 = 3.0;
+/// Z value of warnings, in front of enemies and decorations
+const SPAWN_WARNING_Z_VALUE: f32 = 5.0;
+
+/// Sets the spawn warning settings for the current difficulty
+pub fn update_spawn_warning_settings(
+    mut spawn_warning_settings: ResMut<SpawnWarningSettings>,
+    difficulty_indicator: Res<DifficultyIndicator>,
+) {
+    *spawn_warning_settings =
+        SpawnWarningSettings::from_difficulty(&difficulty_indicator.difficulty);
+}
+
+/// Spawns a warning for the candidate at the start of the route of the spawn point.
+/// The warning is kept on screen and below the hud banner. Returns the spawned warning
+pub fn spawn_warning(
+    commands: &mut Commands,
+    loaded_map: &LoadedMap,
+    spawn_warning_settings: &SpawnWarningSettings,
+    candidate: SpawnCandidate,
+    spawn_point: EnemySpawnPoint,
+) -> Entity {
+    let route = loaded_map.route(&spawn_point);
+    let direction = route.direction_at(0.0);
+    let position = route.position_at(0.0).clamp(
+        Vec2::new(
+            -0.5 * WORLD_WIDTH + SPAWN_WARNING_MARGIN,
+            -0.5 * WORLD_HEIGHT + SPAWN_WARNING_MARGIN,
+        ),
+        Vec2::new(
+            0.5 * WORLD_WIDTH - SPAWN_WARNING_MARGIN,
+            0.5 * WORLD_HEIGHT - HUD_BANNER_HEIGHT - SPAWN_WARNING_MARGIN,
+        ),
+    );
+    let word = candidate.word.clone();
+    let mut spawn_warning = commands.spawn((
+        SpatialBundle::from_transform(Transform::from_translation(
+            position.extend(SPAWN_WARNING_Z_VALUE),
+        )),
+        SpawnWarning {
+            candidate,
+            spawn_point,
+            direction,
+            timer: Timer::from_seconds(spawn_warning_settings.duration, TimerMode::Once),
+        },
+        Name::new("Spawn Warning"),
+    ));
+    if spawn_warning_settings.show_word {
+        spawn_warning.with_children(|parent| {
+            parent.spawn(Text2dBundle {
+                text: Text::from_section(
+                    word,
+                    TextStyle {
+                        font_size: SPAWN_WARNING_FONT_SIZE,
+                        color: SPAWN_WARNING_COLOR,
+                        ..default()
+                    },
+                ),
+                transform: Transform::from_translation(
+                    (direction * SPAWN_WARNING_WORD_DISTANCE).extend(0.0),
+                ),
+                ..default()
+            });
+        });
+    }
+    spawn_warning.id()
+}
+
+/// Spawns the announced enemies once their warning is over
+pub fn spawn_enemies_after_warnings(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut spawn_warning_query: Query<(Entity, &mut SpawnWarning)>,
+    loaded_map: Res<LoadedMap>,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
+    mut spawn_director: ResMut<SpawnDirector>,
+) {
+    for (entity, mut spawn_warning) in spawn_warning_query.iter_mut() {
+        spawn_warning.timer.tick(time.delta());
+        if !spawn_warning.timer.finished() {
+            continue;
+        }
+        let enemy = spawn_enemy_from_candidate(
+            &mut commands,
+            &spawn_warning.candidate,
+            spawn_warning.spawn_point,
+            &loaded_map,
+            &asset_server,
+            &mut texture_atlases,
+        );
+        spawn_director.replace_planned_enemy(entity, enemy);
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Draws a pulsing circle and an arrow pointing in the direction the enemy will walk in for every warning
+pub fn draw_spawn_warnings(
+    mut gizmos: Gizmos,
+    spawn_warning_query: Query<(&Transform, &SpawnWarning)>,
+) {
+    for (transform, spawn_warning) in spawn_warning_query.iter() {
+        let position = transform.translation.truncate();
+        let pulse = (spawn_warning.timer.elapsed_secs()
+            * SPAWN_WARNING_PULSE_FREQUENCY
+            * std::f32::consts::TAU)
+            .sin()
+            * 0.5
+            + 0.5;
+        gizmos.circle_2d(
+            position,
+            SPAWN_WARNING_PULSE_RADIUS * (0.6 + 0.4 * pulse),
+            SPAWN_WARNING_COLOR,
+        );
+
+        let tip = position + spawn_warning.direction * SPAWN_WARNING_ARROW_LENGTH * 0.5;
+        let tail = position - spawn_warning.direction * SPAWN_WARNING_ARROW_LENGTH * 0.5;
+        gizmos.line_2d(tail, tip, SPAWN_WARNING_COLOR);
+        for side in [-1.0, 1.0] {
+            let head = Vec2::from_angle(side * 2.5).rotate(spawn_warning.direction);
+            gizmos.line_2d(
+                tip,
+                tip + head * SPAWN_WARNING_ARROW_LENGTH * 0.3,
+                SPAWN_WARNING_COLOR,
+            );
+        }
+    }
+}
+
+/// Despawns the warnings on restart
+pub fn despawn_spawn_warnings_on_restart(
+    mut commands: Commands,
+    spawn_warning_query: Query<Entity, With<SpawnWarning>>,
+    mut restart_event_reader: EventReader<Restart>,
+) {
+    for _ in restart_event_reader.read() {
+        for entity in spawn_warning_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -1,12 +1,15 @@
-use self::movement::components::{EnemySpawnPoint, PathCheckpointNumber};
-use self::text::components::CollidingWith;
+use self::movement::components::{EnemySpawnPoint, LaneOffset, PathProgress};
+use self::spawn_director::resources::{SpawnCandidate, SpawnDirector};
+use self::spawn_warning::resources::SpawnWarningSettings;
+use self::text::components::LabelLayout;
 
 use super::*;
 
+use crate::game::map::resources::LoadedMap;
+use crate::game::randomness::resources::{GameRng, RngStream};
 use crate::game::rounds_and_indicators::resources::*;
+use crate::game::waves::resources::CurrentWave;
 
-use bevy::window::PrimaryWindow;
-use rand::seq::SliceRandom;
 use rand::Rng;
 
 /// Chance of spawning an enemy every super::resources::ENEMY_SPAWN_TIME seconds
@@ -42,126 +45,215 @@ pub struct EnemyBundle {
     pub speed: Speed,
     pub walking_animation: WalkingAnimation,
     pub enemy_type: EnemyType,
-    pub path_checkpoint_number: PathCheckpointNumber,
-    pub text_collision: CollidingWith,
+    pub path_progress: PathProgress,
+    pub lane_offset: LaneOffset,
     pub name: Name,
 }
 
-/// System that spawns enemies over time according to the enemy spawn timer
-pub fn randomly_spawn_enemies_over_time(
-    commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    mut last_enemy_spawn_point: ResMut<LastEnemySpawnPoint>,
-    mut number_of_enemies_spawned_this_round: ResMut<NumberOfEnemiesSpawnedThisRound>,
+/// System that rolls random enemies over time according to the enemy spawn timer in rounds that are
+/// not scripted by a wave script. Rolled enemies are handed to the spawn director
+pub fn roll_random_enemies_over_time(
+    loaded_map: Res<LoadedMap>,
+    last_enemy_spawn_point: Res<LastEnemySpawnPoint>,
+    number_of_enemies_spawned_this_round: Res<NumberOfEnemiesSpawnedThisRound>,
     max_number_of_enemies_this_round: Res<MaxNumberOfEnemiesCurrentRound>,
     number_of_enemies_unlived_current_round: Res<NumberOfEnemiesUnlivedThisRound>,
     enemy_base_speed_this_round: Res<EnemyBaseSpeedCurrentRound>,
     mut enemy_spawn_timer: ResMut<EnemySpawnTimer>,
-    asset_server: Res<AssetServer>,
-    mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
     words_handle: Res<WordsHandle>,
     words: Res<Assets<Words>>,
+    round_number: Res<RoundNumber>,
+    difficulty_indicator: Res<DifficultyIndicator>,
+    mut game_rng: ResMut<GameRng>,
+    mut spawn_director: ResMut<SpawnDirector>,
+    current_wave: Res<CurrentWave>,
 ) {
+    // Enemies can only spawn once the map is loaded
+    if loaded_map.spawn_routes.is_empty() {
+        return;
+    }
     // Spawn only as many enemies as is planned for this round
-    if number_of_enemies_spawned_this_round.number < max_number_of_enemies_this_round.number {
-        // Get thread rng once for better performance
-        let mut rng = rand::thread_rng();
-        if (enemy_spawn_timer.timer.finished() && rng.gen_bool(CHANCE_OF_SPAWNING_ENEMY))
-            || (number_of_enemies_spawned_this_round.number
-                == number_of_enemies_unlived_current_round.number)
-        {
-            if number_of_enemies_spawned_this_round.number
-                == number_of_enemies_unlived_current_round.number
-            {
-                enemy_spawn_timer
-                    .timer
-                    .set_elapsed(std::time::Duration::from_secs_f32(0.0));
-            }
-            let window = window_query.get_single().expect("Window should exist");
+    if number_of_enemies_spawned_this_round.number >= max_number_of_enemies_this_round.number {
+        return;
+    }
+    // Roll a new enemy only if none is waiting for the spawn director
+    if spawn_director.pending_spawn.is_some() {
+        return;
+    }
+    // Get the stream for spawning decisions once
+    let spawn_rng = game_rng.stream(RngStream::SpawnPoints);
+    let no_enemies_alive = number_of_enemies_spawned_this_round.number
+        == number_of_enemies_unlived_current_round.number;
+    if !((enemy_spawn_timer.timer.finished() && spawn_rng.gen_bool(CHANCE_OF_SPAWNING_ENEMY))
+        || no_enemies_alive)
+    {
+        return;
+    }
+    let Some(word) = words.get(words_handle.0.id()) else {
+        return;
+    };
+    if no_enemies_alive {
+        enemy_spawn_timer
+            .timer
+            .set_elapsed(std::time::Duration::from_secs_f32(0.0));
+    }
+    // Get a random spawn point
+    let spawn_point = last_enemy_spawn_point
+        .spawn_point
+        .next_spawn_point_excluding_self(
+            &loaded_map.spawn_routes,
+            game_rng.stream(RngStream::SpawnPoints),
+        );
+    // Get random enemy sprite according to the spawn table of the current round
+    let enemy_type = EnemyType::random_for_round(
+        round_number.number,
+        &difficulty_indicator.difficulty,
+        game_rng.stream(RngStream::EnemyTypes),
+    );
+    // Set speed of enemy randomly in range of 0.625 to 1.375 times the enemy base speed this round
+    let speed = random_speed_factor(game_rng.stream(RngStream::EnemySpeeds))
+        * enemy_base_speed_this_round.speed;
+    // Get random word of the word tier of the round from list
+    let word_for_enemy = current_wave
+        .word_tier
+        .choose(&word.vec_of_words, game_rng.stream(RngStream::Words))
+        .expect("The list of words shouldn't be empty")
+        .clone();
+    spawn_director.pending_spawn = Some(SpawnCandidate {
+        spawn_point,
+        enemy_type,
+        speed,
+        word: word_for_enemy,
+        branch_seed: game_rng.stream(RngStream::Junctions).gen(),
+        fixed_route: false,
+    });
+}
 
-            // Get a random spawn point
-            let spawn_point = last_enemy_spawn_point
-                .spawn_point
-                .next_spawn_point_excluding_self(&mut rng);
-            last_enemy_spawn_point.spawn_point = spawn_point;
-            let spawn_point_transform =
-                movement::systems::generate_spawn_point_transform_from_enum(spawn_point, window);
+/// Returns a random factor in the range of 0.625 to 1.375 that the base speed of enemies is multiplied with
+pub fn random_speed_factor(rng: &mut impl Rng) -> f32 {
+    rng.gen::<f32>() * 0.75 + 0.625
+}
 
-            // Get random enemy sprite
-            let enemy_type: EnemyType = rng.gen();
-            let (enemy_name, sprite_width, sprite_height, animation_length) =
-                generate_sprite_information_from_enemy_type(&enemy_type);
-            let texture_handle: Handle<Image> =
-                asset_server.load(format!("sprites/enemies/{}.png", enemy_name));
-            let texture_atlas = TextureAtlasLayout::from_grid(
-                Vec2::new(sprite_width, sprite_height),
-                animation_length,
-                1,
-                None,
-                None,
-            );
-            let texture_atlas_handle: Handle<TextureAtlasLayout> =
-                texture_atlases.add(texture_atlas);
+/// System that hands the rolled enemy waiting for a spawn to the spawn director, which picks its route
+/// and holds it back while too much typing would arrive at the castle at once.
+/// Approved enemies first show a warning at the start of their route
+pub fn spawn_pending_enemies(
+    mut commands: Commands,
+    loaded_map: Res<LoadedMap>,
+    mut last_enemy_spawn_point: ResMut<LastEnemySpawnPoint>,
+    mut number_of_enemies_spawned_this_round: ResMut<NumberOfEnemiesSpawnedThisRound>,
+    mut spawn_director: ResMut<SpawnDirector>,
+    spawn_warning_settings: Res<SpawnWarningSettings>,
+) {
+    let Some(candidate) = spawn_director.pending_spawn.clone() else {
+        return;
+    };
+    // Enemies spawn once the warning at the start of their route is over
+    let spawn_time = spawn_director.round_time + spawn_warning_settings.duration;
+    let Some(spawn_point) = spawn_director.choose_spawn_point(&candidate, &loaded_map, spawn_time)
+    else {
+        // Wait until enough typing time is free
+        return;
+    };
+    last_enemy_spawn_point.spawn_point = spawn_point;
+    let spawn_warning = spawn_warning::systems::spawn_warning(
+        &mut commands,
+        &loaded_map,
+        &spawn_warning_settings,
+        candidate.clone(),
+        spawn_point,
+    );
+    spawn_director.plan_arrival(
+        spawn_warning,
+        &candidate,
+        spawn_point,
+        &loaded_map,
+        spawn_time,
+    );
+    spawn_director.pending_spawn = None;
+    number_of_enemies_spawned_this_round.number += 1;
+}
 
-            // Set speed of enemy randomly in range of 0.625 to 1.375 times the enemy base speed this round
-            let speed = (rng.gen::<f32>() * 0.75 + 0.625) * enemy_base_speed_this_round.speed;
-            let walking_animation: WalkingAnimation = WalkingAnimation {
-                length_of_animation: animation_length,
-                animation_timer: Timer::from_seconds(
-                    BASE_ANIMATION_SPEED / speed,
-                    TimerMode::Repeating,
-                ),
-            };
+/// Spawns the enemy that was rolled as the given candidate at the start of the route of the spawn point.
+/// Returns the spawned enemy
+pub fn spawn_enemy_from_candidate(
+    commands: &mut Commands,
+    candidate: &SpawnCandidate,
+    spawn_point: EnemySpawnPoint,
+    loaded_map: &LoadedMap,
+    asset_server: &AssetServer,
+    texture_atlases: &mut Assets<TextureAtlasLayout>,
+) -> Entity {
+    let spawn_point_transform =
+        movement::systems::generate_spawn_point_transform(&spawn_point, loaded_map);
 
-            // Flip the sprite on the y-axis if enemy is spawned left or bottom
-            let flip_on_y_axis =
-                movement::systems::check_if_sprite_needs_to_be_flipped_from_spawnpoint(spawn_point);
+    let (enemy_name, sprite_width, sprite_height, animation_length) =
+        generate_sprite_information_from_enemy_type(&candidate.enemy_type);
+    let texture_handle: Handle<Image> =
+        asset_server.load(format!("sprites/enemies/{}.png", enemy_name));
+    let texture_atlas = TextureAtlasLayout::from_grid(
+        Vec2::new(sprite_width, sprite_height),
+        animation_length,
+        1,
+        None,
+        None,
+    );
+    let texture_atlas_handle: Handle<TextureAtlasLayout> = texture_atlases.add(texture_atlas);
 
-            // Resize the sprites for game
-            let custom_sprite_size = Some(Vec2::new(
-                ENEMY_SPRITE_SCALE_FACTOR * sprite_width,
-                ENEMY_SPRITE_SCALE_FACTOR * sprite_height,
-            ));
+    let walking_animation: WalkingAnimation = WalkingAnimation {
+        length_of_animation: animation_length,
+        animation_timer: Timer::from_seconds(
+            BASE_ANIMATION_SPEED / candidate.speed,
+            TimerMode::Repeating,
+        ),
+    };
 
-            if let Some(word) = words.get(words_handle.0.id()) {
-                // Get random word from list
-                let word_for_enemy = word
-                    .vec_of_words
-                    .choose(&mut rng)
-                    .expect("The list of words shouldn't be empty");
-                spawn_enemy(
-                    commands,
-                    spawn_point_transform,
-                    flip_on_y_axis,
-                    custom_sprite_size,
-                    texture_atlas_handle,
-                    texture_handle,
-                    spawn_point,
-                    speed,
-                    walking_animation,
-                    enemy_type,
-                    word_for_enemy,
-                );
-            }
-            number_of_enemies_spawned_this_round.number += 1;
-        }
-    }
+    // Flip the sprite on the y-axis if enemy is spawned left or bottom
+    let flip_on_y_axis = movement::systems::check_if_sprite_needs_to_be_flipped_from_spawnpoint(
+        &spawn_point,
+        loaded_map,
+    );
+
+    // Resize the sprites for game
+    let custom_sprite_size = Some(Vec2::new(
+        ENEMY_SPRITE_SCALE_FACTOR * sprite_width,
+        ENEMY_SPRITE_SCALE_FACTOR * sprite_height,
+    ));
+
+    spawn_enemy(
+        commands,
+        spawn_point_transform,
+        flip_on_y_axis,
+        custom_sprite_size,
+        texture_atlas_handle,
+        texture_handle,
+        spawn_point,
+        PathProgress {
+            branch_seed: candidate.branch_seed,
+            ..default()
+        },
+        candidate.speed,
+        walking_animation,
+        candidate.enemy_type,
+        &candidate.word,
+    )
 }
 
-/// Function that spawns enemies
+/// Function that spawns enemies. Returns the spawned enemy
 pub fn spawn_enemy(
-    mut commands: Commands,
+    commands: &mut Commands,
     spawn_point_transform: Transform,
     flip_on_y_axis: bool,
     custom_sprite_size: Option<Vec2>,
     texture_atlas_handle: Handle<TextureAtlasLayout>,
     texture_handle: Handle<Image>,
     spawn_point: EnemySpawnPoint,
+    path_progress: PathProgress,
     speed: f32,
     walking_animation: WalkingAnimation,
     enemy_type: EnemyType,
     word_for_enemy: &String,
-) {
+) -> Entity {
     commands
         .spawn((
             EnemyBundle {
@@ -185,8 +277,8 @@ pub fn spawn_enemy(
                 speed: Speed { speed: speed },
                 walking_animation,
                 enemy_type,
-                path_checkpoint_number: PathCheckpointNumber::default(),
-                text_collision: CollidingWith::default(),
+                path_progress,
+                lane_offset: LaneOffset::default(),
                 name: Name::new(word_for_enemy.clone()),
             },
             ZIndex::Local(10),
@@ -206,9 +298,11 @@ pub fn spawn_enemy(
                     transform: Transform::from_xyz(0.0, TEXT_HEIGHT, TEXT_Z_VALUE),
                     ..default()
                 },
+                LabelLayout::default(),
                 ZIndex::Local(10),
             ));
-        });
+        })
+        .id()
 }
 
 /// Returns the necessary info in order to generate a spritesheet for each enemy type
@@ -1,16 +1,17 @@
 use super::*;
 
-/// Component used to track what entity the enemy is currently colliding with. Defaults to entity placeholder from bevy
+/// Component on the text of an enemy that holds where the label is currently drawn relative to its enemy.
+/// The offset moves smoothly towards the position assigned by the label layout
 #[derive(Reflect, Component)]
 #[reflect(Component)]
-pub struct CollidingWith {
-    pub entity_colliding_with: Entity,
+pub struct LabelLayout {
+    pub offset: Vec2,
 }
 
-impl Default for CollidingWith {
-    fn default() -> CollidingWith {
-        CollidingWith {
-            entity_colliding_with: Entity::PLACEHOLDER,
+impl Default for LabelLayout {
+    fn default() -> LabelLayout {
+        LabelLayout {
+            offset: Vec2::new(0.0, TEXT_HEIGHT),
         }
     }
 }
@@ -1,30 +1,55 @@
 use bevy::input::{keyboard::KeyboardInput, ButtonState};
 
-use enemies::rounds_and_indicators::resources::StreakIndicator;
-
-use self::enemies::movement::{
-    components::{EnemySpawnPoint, PathCheckpointNumber},
-    systems::TurnInstruction,
-};
+use enemies::rounds_and_indicators::resources::{RoundSummary, RunStatistics, StreakIndicator};
 
 use super::*;
+use crate::game::effects::components::{DeathAnimation, HitFlash};
+use crate::game::enemies::spatial_index::resources::SpatialGrid;
+use crate::{WORLD_HEIGHT, WORLD_WIDTH};
+
+/// Path of the word pack enemies get their words from outside of the campaign
+pub const DEFAULT_WORD_PACK: &str = "words/thousand_most_frequent_words.words.toml";
+/// Height of a label slot, labels are stacked in steps of this height
+const LABEL_HEIGHT: f32 = TEXT_HEIGHT;
+/// Height of the hud banner at the top of the screen (10% height plus 0.5% margin) that labels must stay below
+pub const HUD_BANNER_HEIGHT: f32 = 0.105 * WORLD_HEIGHT;
+/// Size of the cells of the grid used to look up labels that might overlap
+const LABEL_GRID_CELL_SIZE: f32 = 200.0;
+/// How fast labels move towards their assigned position - higher = snappier
+const LABEL_SMOOTHING_SPEED: f32 = 12.0;
 
-/// Event that used whenever an enemy was typed
+/// Event that used whenever an enemy was typed. Holds the typed enemy, its translation and the number of letters of its word
 #[derive(Event)]
-pub struct EnemyTypedEvent();
+pub struct EnemyTypedEvent {
+    pub entity: Entity,
+    pub translation: Vec3,
+    pub letters: usize,
+}
+
+/// Event that is sent once for every keystroke that typed a correct letter
+#[derive(Event)]
+pub struct LetterTypedEvent;
 
 /// System that updates which enemies are being typed
 pub fn update_text_from_enemies_on_button_press(
     mut commands: Commands,
     mut enemies_being_typed: ResMut<EnemiesBeingTyped>,
     mut streak_indicator: ResMut<StreakIndicator>,
+    mut run_statistics: ResMut<RunStatistics>,
+    mut round_summary: ResMut<RoundSummary>,
     mut keyboard_input_events: EventReader<KeyboardInput>,
     mut q_parent_with_enemy: Query<
-        (Entity, Option<&mut CurrentlyBeingTyped>, &Children),
+        (
+            Entity,
+            Option<&mut CurrentlyBeingTyped>,
+            &Children,
+            &Transform,
+        ),
         With<Enemy>,
     >,
     mut q_child_with_text: Query<&mut Text>,
     mut enemy_typed_event: EventWriter<EnemyTypedEvent>,
+    mut letter_typed_event: EventWriter<LetterTypedEvent>,
 ) {
     for key_event in keyboard_input_events.read() {
         if key_event.state != ButtonState::Pressed {
@@ -34,7 +59,7 @@ pub fn update_text_from_enemies_on_button_press(
             let pressed_key = key_event.key_code;
             // Check if esc or backspace was just pressed and reset all enemies if so
             if pressed_key == KeyCode::Backspace {
-                for (entity_id, currently_being_typed, child) in q_parent_with_enemy.iter_mut() {
+                for (entity_id, currently_being_typed, child, _) in q_parent_with_enemy.iter_mut() {
                     if let Some(_) = currently_being_typed {
                         let mut iter = q_child_with_text.iter_many_mut(child);
                         while let Some(mut text) = iter.fetch_next() {
@@ -52,8 +77,14 @@ pub fn update_text_from_enemies_on_button_press(
             // Check if the key is a key and not a function/logical key otherwise can ignore
             if let Some(pressed_letter) = key_to_letter(pressed_key) {
                 let mut made_a_mistake_global = false;
+                // Track if the letter was correct for any enemy in order to count the keystroke once
+                let mut typed_a_correct_letter = false;
+                // Track the texts of enemies that were mistyped in order to flash them if no enemy is left
+                let mut mistyped_texts: Vec<Entity> = Vec::new();
                 // Iterate over all enemies with children and get typing index if necessary
-                for (entity_id, currently_being_typed, child) in q_parent_with_enemy.iter_mut() {
+                for (entity_id, currently_being_typed, child, transform) in
+                    q_parent_with_enemy.iter_mut()
+                {
                     if !enemies_being_typed.indicator && !made_a_mistake_global {
                         // If nothing is currently being typed
                         let mut iter = q_child_with_text.iter_many_mut(child);
@@ -62,12 +93,17 @@ pub fn update_text_from_enemies_on_button_press(
                             if let Some(text_section) = text.sections.get_mut(0) {
                                 if text_section.value == pressed_letter {
                                     streak_indicator.number += 1;
+                                    typed_a_correct_letter = true;
                                     if number_of_letter_in_word == 1 {
                                         // You got "typed"
                                         // Enemy only consists of one letter - You got "typed"
-                                        // Despawn entity and remove entity from list of enemies that are currently being typed
-                                        commands.entity(entity_id).despawn_recursive();
-                                        enemy_typed_event.send(EnemyTypedEvent {});
+                                        // Let entity die and remove entity from list of enemies that are currently being typed
+                                        unlive_typed_enemy(&mut commands, entity_id);
+                                        enemy_typed_event.send(EnemyTypedEvent {
+                                            entity: entity_id,
+                                            translation: transform.translation,
+                                            letters: number_of_letter_in_word,
+                                        });
                                     } else {
                                         // Player is starting to type this enemy
                                         text_section.style.color = TYPING_COLOR;
@@ -96,10 +132,11 @@ pub fn update_text_from_enemies_on_button_press(
                                         currently_being_typed.index =
                                             currently_being_typed.index + 1;
                                         streak_indicator.number += 1;
+                                        typed_a_correct_letter = true;
                                         if currently_being_typed.index == text.sections.len() - 1 {
                                             // You got "typed"
-                                            // Despawn entity and remove entity from list of enemies that are currently being typed
-                                            commands.entity(entity_id).despawn_recursive();
+                                            // Let entity die and remove entity from list of enemies that are currently being typed
+                                            unlive_typed_enemy(&mut commands, entity_id);
                                             enemies_being_typed
                                                 .vec_of_enemies
                                                 .retain(|&x| x != entity_id);
@@ -107,7 +144,11 @@ pub fn update_text_from_enemies_on_button_press(
                                                 // Check if there are no more enemies being typed
                                                 enemies_being_typed.indicator = false;
                                             }
-                                            enemy_typed_event.send(EnemyTypedEvent {});
+                                            enemy_typed_event.send(EnemyTypedEvent {
+                                                entity: entity_id,
+                                                translation: transform.translation,
+                                                letters: text.sections.len(),
+                                            });
                                         }
                                     } else {
                                         // Player is typing another enemy or has made a mistake
@@ -124,6 +165,7 @@ pub fn update_text_from_enemies_on_button_press(
                                     enemies_being_typed
                                         .vec_of_enemies
                                         .retain(|&x| x != entity_id);
+                                    mistyped_texts.extend(child.iter());
                                 }
                                 // If there were mistakes and there is no enemy left that is being typed
                                 if enemies_being_typed.vec_of_enemies.len() == 0 {
@@ -136,6 +178,18 @@ pub fn update_text_from_enemies_on_button_press(
                         }
                     }
                 }
+                // Case where a wrong key was pressed and no enemy is being typed anymore
+                if made_a_mistake_global && enemies_being_typed.vec_of_enemies.is_empty() {
+                    run_statistics.wrong_keystrokes += 1;
+                    round_summary.wrong_keystrokes += 1;
+                    for text_entity in mistyped_texts {
+                        commands.entity(text_entity).try_insert(HitFlash::default());
+                    }
+                } else if typed_a_correct_letter {
+                    run_statistics.correct_keystrokes += 1;
+                    round_summary.correct_keystrokes += 1;
+                    letter_typed_event.send(LetterTypedEvent);
+                }
                 // Case where there were no enemies being typed before but now there is one
                 // This is done outside of the for loop in order not to exclude partial matches
                 if !enemies_being_typed.indicator && enemies_being_typed.vec_of_enemies.len() > 0 {
@@ -147,6 +201,15 @@ pub fn update_text_from_enemies_on_button_press(
     }
 }
 
+/// Lets a typed enemy die by removing its text and enemy tag and fading it out with a death animation
+pub fn unlive_typed_enemy(commands: &mut Commands, entity_id: Entity) {
+    commands
+        .entity(entity_id)
+        .despawn_descendants()
+        .remove::<(Enemy, CurrentlyBeingTyped)>()
+        .try_insert(DeathAnimation::default());
+}
+
 /// Maps keys to letters and returns none if the key is not needed
 fn key_to_letter(key: KeyCode) -> Option<String> {
     match key {
@@ -183,528 +246,91 @@ fn key_to_letter(key: KeyCode) -> Option<String> {
 
 /// Sets up the asset list of words used for typing
 pub fn setup_list_of_words_asset(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let words_handle =
-        WordsHandle(asset_server.load("words/thousand_most_frequent_words.words.toml"));
+    let words_handle = WordsHandle(asset_server.load(DEFAULT_WORD_PACK));
     commands.insert_resource(words_handle);
 }
 
-/// System that handles the movement of text when enemies collide.
-/// Moves the text of the approaching enemy upward
-pub fn handle_text_when_enemies_collide(
-    mut q_parent_with_enemy: Query<
-        (
-            Entity,
-            &Transform,
-            &Children,
-            &EnemySpawnPoint,
-            &PathCheckpointNumber,
-            &mut CollidingWith,
-        ),
+/// System that places the labels of all enemies so that they don't overlap and stay on screen.
+/// Labels of enemies being typed are placed first, every other label takes the closest free
+/// slot above or below its enemy. Placed labels are kept in a grid so each label only checks its neighbours
+pub fn layout_enemy_labels(
+    time: Res<Time>,
+    q_parent_with_enemy: Query<
+        (Entity, &Transform, &Children, Has<CurrentlyBeingTyped>),
         With<Enemy>,
     >,
-    mut q_child_with_text: Query<(&mut Transform, &Text), Without<Enemy>>,
-) {
-    let mut q_parent_combinations_iter = q_parent_with_enemy.iter_combinations_mut();
-    while let Some(
-        [(
-            entity_first_enemy,
-            transform_first_enemy,
-            children_first,
-            enemy_spawn_point_first_enemy,
-            path_checkpoint_number_first_enemy,
-            mut text_colliding_with_first,
-        ), (
-            entity_second_enemy,
-            transform_second_enemy,
-            children_second,
-            enemy_spawn_point_second_enemy,
-            path_checkpoint_number_second_enemy,
-            mut text_colliding_with_second,
-        )],
-    ) = q_parent_combinations_iter.fetch_next()
-    {
-        let text_collision_instructions = query_texts_and_check_if_it_collides(
-            &q_child_with_text,
-            children_first,
-            children_second,
-            transform_first_enemy,
-            transform_second_enemy,
-            enemy_spawn_point_first_enemy,
-            enemy_spawn_point_second_enemy,
-            path_checkpoint_number_first_enemy,
-            path_checkpoint_number_second_enemy,
-            false,
-        );
-
-        if !(text_colliding_with_first.entity_colliding_with == entity_second_enemy
-            || text_colliding_with_second.entity_colliding_with == entity_first_enemy)
-        {
-            // If there is a collision, then change position of text
-            if let Some((text_collision_bool, colliding_text_new_translation)) =
-                text_collision_instructions
-            {
-                match text_collision_bool {
-                    // First enemy's text will be moved up
-                    true => {
-                        // Add component to enemy indicating that currently texts are colliding
-                        text_colliding_with_first.entity_colliding_with = entity_second_enemy;
-                        change_position_of_text(
-                            &mut q_child_with_text,
-                            children_first,
-                            colliding_text_new_translation,
-                        );
-                    }
-                    // Second enemy's text will be moved up
-                    false => {
-                        // Add component to enemy indicating that currently texts are colliding
-                        text_colliding_with_second.entity_colliding_with = entity_first_enemy;
-                        change_position_of_text(
-                            &mut q_child_with_text,
-                            children_second,
-                            colliding_text_new_translation,
-                        );
-                    }
-                };
-            }
-        }
-    }
-}
-
-/// Queries the texts given the children entities of the enemies (the children are the text bundles)
-/// and checks if the texts are colliding.
-///
-/// Returns an Option bool with true if the first enemy's text has to be moved and false if the second enemy's text has to be moved
-/// with the needed adjustment in the Vec3. If there is no collision, None is returned.
-fn query_texts_and_check_if_it_collides(
-    q_child_with_text: &Query<(&mut Transform, &Text), Without<Enemy>>,
-    children_first: &Children,
-    children_second: &Children,
-    transform_first_enemy: &Transform,
-    transform_second_enemy: &Transform,
-    enemy_spawn_point_first_enemy: &EnemySpawnPoint,
-    enemy_spawn_point_second_enemy: &EnemySpawnPoint,
-    path_checkpoint_number_first_enemy: &PathCheckpointNumber,
-    path_checkpoint_number_second_enemy: &PathCheckpointNumber,
-    skip_check_if_y_transforms_of_texts_are_equal: bool,
-) -> Option<(bool, Vec3)> {
-    let vec_of_both_texts: Vec<(&Transform, &Text)> = q_child_with_text
-        .iter_many(children_first.iter().chain(children_second.iter()))
-        .collect();
-    let (transform_first_text, text_first_text) = vec_of_both_texts
-        .get(0)
-        .expect("Enemies should have text child");
-    let (transform_second_text, text_second_text) = vec_of_both_texts
-        .get(1)
-        .expect("Enemies should have text child");
-
-    check_if_text_is_colliding(
-        text_first_text,
-        text_second_text,
-        &transform_first_enemy.translation,
-        &transform_second_enemy.translation,
-        enemy_spawn_point_first_enemy,
-        enemy_spawn_point_second_enemy,
-        path_checkpoint_number_first_enemy,
-        path_checkpoint_number_second_enemy,
-        &transform_first_text.translation,
-        &transform_second_text.translation,
-        skip_check_if_y_transforms_of_texts_are_equal,
-    )
-}
-
-/// Checks if the texts of the first and second enemy are colliding.
-///
-/// Returns an Option bool with true if the first enemy's text has to be moved and false if the second enemy's text has to be moved
-/// with the needed adjustment in the Vec3. If there is no collision, None is returned.
-fn check_if_text_is_colliding(
-    text_first_text: &Text,
-    text_second_text: &Text,
-    translation_first_enemy: &Vec3,
-    translation_second_enemy: &Vec3,
-    enemy_spawn_point_first_enemy: &EnemySpawnPoint,
-    enemy_spawn_point_second_enemy: &EnemySpawnPoint,
-    path_checkpoint_number_first_enemy: &PathCheckpointNumber,
-    path_checkpoint_number_second_enemy: &PathCheckpointNumber,
-    translation_first_text: &Vec3,
-    translation_second_text: &Vec3,
-    skip_check_if_y_transforms_of_texts_are_equal: bool,
-) -> Option<(bool, Vec3)> {
-    let font_size = text_first_text
-        .sections
-        .get(0)
-        .expect("Text shouldn't be empty")
-        .style
-        .font_size;
-    // Font size * 0.5 is an estimation for the size in pixels of one letter
-    let first_text_pixel_size: f32 = text_first_text.sections.len() as f32 * 0.5 * font_size;
-    let second_text_pixel_size: f32 = text_second_text.sections.len() as f32 * 0.5 * font_size;
-
-    let turn_instruction_first_enemy = super::movement::systems::get_current_turn_instruction(
-        enemy_spawn_point_first_enemy,
-        path_checkpoint_number_first_enemy.number,
-    );
-    let turn_instruction_second_enemy = super::movement::systems::get_current_turn_instruction(
-        enemy_spawn_point_second_enemy,
-        path_checkpoint_number_second_enemy.number,
-    );
-
-    if turn_instruction_first_enemy == turn_instruction_second_enemy {
-        match turn_instruction_first_enemy {
-            TurnInstruction::Left | TurnInstruction::Right => {
-                if (translation_first_enemy.y - translation_second_enemy.y).abs() < 10.0 {
-                    if (translation_first_enemy.x - translation_second_enemy.x).abs()
-                        < 0.5 * first_text_pixel_size + 0.5 * second_text_pixel_size
-                    {
-                        if translation_first_text.y == translation_second_text.y
-                            || skip_check_if_y_transforms_of_texts_are_equal
-                        {
-                            // We have liftoff (collision)!
-                            if translation_first_enemy.x <= translation_second_enemy.x {
-                                match turn_instruction_first_enemy {
-                                    // Second text needs to be moved
-                                    TurnInstruction::Left => {
-                                        return Some((
-                                            false,
-                                            Vec3::new(
-                                                translation_first_text.x,
-                                                translation_first_text.y + enemies::TEXT_HEIGHT,
-                                                translation_first_text.z,
-                                            ),
-                                        ))
-                                    }
-                                    // First text needs to be moved
-                                    TurnInstruction::Right => {
-                                        return Some((
-                                            true,
-                                            Vec3::new(
-                                                translation_second_text.x,
-                                                translation_second_text.y + enemies::TEXT_HEIGHT,
-                                                translation_second_text.z,
-                                            ),
-                                        ))
-                                    }
-                                    // This can never happen
-                                    _ => return None,
-                                }
-                            } else {
-                                match turn_instruction_first_enemy {
-                                    // Second text needs to be moved
-                                    TurnInstruction::Right => {
-                                        return Some((
-                                            false,
-                                            Vec3::new(
-                                                translation_first_text.x,
-                                                translation_first_text.y + enemies::TEXT_HEIGHT,
-                                                translation_first_text.z,
-                                            ),
-                                        ))
-                                    }
-                                    // First text needs to be moved
-                                    TurnInstruction::Left => {
-                                        return Some((
-                                            true,
-                                            Vec3::new(
-                                                translation_second_text.x,
-                                                translation_second_text.y + enemies::TEXT_HEIGHT,
-                                                translation_second_text.z,
-                                            ),
-                                        ))
-                                    }
-                                    // This can never happen
-                                    _ => return None,
-                                }
-                            };
-                        }
-                    }
-                }
-            }
-            TurnInstruction::Up | TurnInstruction::Down => {
-                if (translation_first_enemy.x - translation_second_enemy.x).abs() < 10.0 {
-                    if (translation_first_enemy.y - translation_second_enemy.y).abs() < TEXT_HEIGHT
-                    {
-                        if translation_first_text.y == translation_second_text.y
-                            || skip_check_if_y_transforms_of_texts_are_equal
-                        {
-                            // We have liftoff (collision)!
-                            if translation_first_enemy.y <= translation_second_enemy.y {
-                                match turn_instruction_first_enemy {
-                                    // Second text needs to be moved
-                                    TurnInstruction::Up => {
-                                        return Some((
-                                            false,
-                                            Vec3::new(
-                                                translation_first_text.x,
-                                                translation_first_text.y + enemies::TEXT_HEIGHT,
-                                                translation_first_text.z,
-                                            ),
-                                        ))
-                                    }
-                                    // Second text needs to be moved
-                                    TurnInstruction::Down => {
-                                        return Some((
-                                            false,
-                                            Vec3::new(
-                                                translation_first_text.x,
-                                                translation_first_text.y + enemies::TEXT_HEIGHT,
-                                                translation_first_text.z,
-                                            ),
-                                        ))
-                                    }
-                                    // This can never happen
-                                    _ => return None,
-                                }
-                            } else {
-                                match turn_instruction_first_enemy {
-                                    // First text needs to be moved
-                                    TurnInstruction::Up => {
-                                        return Some((
-                                            true,
-                                            Vec3::new(
-                                                translation_second_text.x,
-                                                translation_second_text.y + enemies::TEXT_HEIGHT,
-                                                translation_second_text.z,
-                                            ),
-                                        ))
-                                    }
-                                    // First text needs to be moved
-                                    TurnInstruction::Down => {
-                                        return Some((
-                                            true,
-                                            Vec3::new(
-                                                translation_second_text.x,
-                                                translation_second_text.y + enemies::TEXT_HEIGHT,
-                                                translation_second_text.z,
-                                            ),
-                                        ))
-                                    }
-                                    // This can never happen
-                                    _ => return None,
-                                }
-                            };
-                        }
-                    }
-                }
-            }
-        }
-    }
-    None
-}
-
-/// Changes the position of text according to the given new translation
-fn change_position_of_text(
-    q_child_with_text: &mut Query<(&mut Transform, &Text), Without<Enemy>>,
-    children: &Children,
-    colliding_text_new_translation: Vec3,
+    mut q_child_with_text: Query<(&mut Transform, &mut LabelLayout, &Text), Without<Enemy>>,
 ) {
-    let mut text_iter = q_child_with_text.iter_many_mut(children);
-    if let Some((mut text_transform, _)) = text_iter.fetch_next() {
-        text_transform.translation = colliding_text_new_translation;
-    }
-}
+    let mut enemies: Vec<_> = q_parent_with_enemy.iter().collect();
+    enemies.sort_by_key(|(entity_id, _, _, currently_being_typed)| {
+        (!currently_being_typed, *entity_id)
+    });
 
-/// Resets the height of text
-fn reset_height_of_text(
-    q_child_with_text: &mut Query<(&mut Transform, &Text), Without<Enemy>>,
-    children: &Children,
-    colliding_text_colliding_with: &mut CollidingWith,
-) {
-    let mut text_iter = q_child_with_text.iter_many_mut(children);
-    if let Some((mut text_transform, _)) = text_iter.fetch_next() {
-        text_transform.translation = Vec3::new(0.0, TEXT_HEIGHT, TEXT_Z_VALUE);
-        colliding_text_colliding_with.entity_colliding_with = Entity::PLACEHOLDER;
-    }
-}
+    let mut label_grid: SpatialGrid<()> = SpatialGrid::new(LABEL_GRID_CELL_SIZE);
+    let smoothing = 1.0 - (-LABEL_SMOOTHING_SPEED * time.delta_seconds()).exp();
 
-/// Resets text height when enemies passed each other
-pub fn reset_text_height_when_enemies_passed_each_other(
-    mut q_parent_with_enemy: Query<
-        (
-            Entity,
-            &Transform,
-            &Children,
-            &EnemySpawnPoint,
-            &PathCheckpointNumber,
-            &mut CollidingWith,
-        ),
-        With<Enemy>,
-    >,
-    mut q_child_with_text: Query<(&mut Transform, &Text), Without<Enemy>>,
-) {
-    let mut q_parent_combinations_iter = q_parent_with_enemy.iter_combinations_mut();
-    while let Some(
-        [(
-            entity_first_enemy,
-            transform_first_enemy,
-            children_first,
-            enemy_spawn_point_first_enemy,
-            path_checkpoint_number_first_enemy,
-            mut text_colliding_with_first_enemy,
-        ), (
-            entity_second_enemy,
-            transform_second_enemy,
-            children_second,
-            enemy_spawn_point_second_enemy,
-            path_checkpoint_number_second_enemy,
-            mut text_colliding_with_second_enemy,
-        )],
-    ) = q_parent_combinations_iter.fetch_next()
-    {
-        if text_colliding_with_first_enemy.entity_colliding_with == entity_second_enemy {
-            handle_checking_collision_and_resetting_text(
-                children_first,
-                transform_first_enemy,
-                children_second,
-                transform_second_enemy,
-                enemy_spawn_point_first_enemy,
-                enemy_spawn_point_second_enemy,
-                path_checkpoint_number_first_enemy,
-                path_checkpoint_number_second_enemy,
-                &mut q_child_with_text,
-                &mut text_colliding_with_first_enemy,
+    for (_, enemy_transform, children, _) in enemies {
+        for &child in children.iter() {
+            let Ok((mut text_transform, mut label_layout, text)) = q_child_with_text.get_mut(child)
+            else {
+                continue;
+            };
+            let label_size = estimate_label_size(text);
+            let enemy_position = enemy_transform.translation.truncate();
+            let label_rect = find_free_label_rect(
+                &label_grid,
+                enemy_position + Vec2::new(0.0, TEXT_HEIGHT),
+                label_size,
             );
-        } else if text_colliding_with_second_enemy.entity_colliding_with == entity_first_enemy {
-            handle_checking_collision_and_resetting_text(
-                children_second,
-                transform_second_enemy,
-                children_first,
-                transform_first_enemy,
-                enemy_spawn_point_first_enemy,
-                enemy_spawn_point_second_enemy,
-                path_checkpoint_number_first_enemy,
-                path_checkpoint_number_second_enemy,
-                &mut q_child_with_text,
-                &mut text_colliding_with_second_enemy,
-            )
-        };
-    }
-}
+            label_grid.insert((), label_rect);
 
-/// Handles checking collisions and resetting text
-fn handle_checking_collision_and_resetting_text(
-    colliding_enemy_child: &Children,
-    colliding_enemy_transform: &Transform,
-    non_colliding_enemy_child: &Children,
-    non_colliding_enemy_transform: &Transform,
-    enemy_spawn_point_first_enemy: &EnemySpawnPoint,
-    enemy_spawn_point_second_enemy: &EnemySpawnPoint,
-    path_checkpoint_number_first_enemy: &PathCheckpointNumber,
-    path_checkpoint_number_second_enemy: &PathCheckpointNumber,
-    mut q_child_with_text: &mut Query<(&mut Transform, &Text), Without<Enemy>>,
-    colliding_text_colliding_with: &mut CollidingWith,
-) {
-    if let None = query_texts_and_check_if_it_collides(
-        &q_child_with_text,
-        colliding_enemy_child,
-        non_colliding_enemy_child,
-        colliding_enemy_transform,
-        non_colliding_enemy_transform,
-        enemy_spawn_point_first_enemy,
-        enemy_spawn_point_second_enemy,
-        path_checkpoint_number_first_enemy,
-        path_checkpoint_number_second_enemy,
-        true,
-    ) {
-        reset_height_of_text(
-            &mut q_child_with_text,
-            colliding_enemy_child,
-            colliding_text_colliding_with,
-        )
-    };
-}
-
-/// Lowers text stepwise when colliding enemy is removed instead of resetting text height
-pub fn lower_text_stepwise_when_colliding_enemy_is_removed(
-    mut commands: Commands,
-    mut enemies_with_colliding_text_query: Query<
-        (Entity, &Transform, &Children, &mut CollidingWith),
-        With<Enemy>,
-    >,
-    mut q_child_with_text: Query<(&mut Transform, &Text), Without<Enemy>>,
-) {
-    for (_, _, enemy_children, mut enemy_text_colliding_with) in
-        enemies_with_colliding_text_query.iter_mut()
-    {
-        if let None = commands.get_entity(enemy_text_colliding_with.entity_colliding_with) {
-            lower_text_stepwise(
-                &mut q_child_with_text,
-                enemy_children,
-                &mut enemy_text_colliding_with,
-            );
+            let target_offset = label_rect.center() - enemy_position;
+            label_layout.offset = label_layout.offset.lerp(target_offset, smoothing);
+            text_transform.translation.x = label_layout.offset.x;
+            text_transform.translation.y = label_layout.offset.y;
         }
     }
 }
 
-/// Lower text stepwise
-fn lower_text_stepwise(
-    q_child_with_text: &mut Query<(&mut Transform, &Text), Without<Enemy>>,
-    children: &Children,
-    colliding_text_colliding_with: &mut CollidingWith,
-) {
-    let mut text_iter = q_child_with_text.iter_many_mut(children);
-    if let Some((mut text_transform, _)) = text_iter.fetch_next() {
-        if text_transform.translation.y > TEXT_HEIGHT {
-            text_transform.translation = Vec3::new(
-                0.0,
-                text_transform.translation.y - TEXT_HEIGHT,
-                TEXT_Z_VALUE,
-            );
-            if text_transform.translation.y == TEXT_HEIGHT {
-                colliding_text_colliding_with.entity_colliding_with = Entity::PLACEHOLDER;
-            }
-        }
-    }
+/// Estimates the size of a label in world units
+fn estimate_label_size(text: &Text) -> Vec2 {
+    let font_size = text
+        .sections
+        .first()
+        .map_or(ENEMY_TEXT_FONT_SIZE, |section| section.style.font_size);
+    // Font size * 0.5 is an estimation for the size in pixels of one letter
+    Vec2::new(text.sections.len() as f32 * 0.5 * font_size, LABEL_HEIGHT)
 }
 
-/// Check if the text of the colliding enemy has moved
-pub fn check_if_colliding_text_has_moved(
-    q_parent_with_enemy: Query<(Entity, &Children, &CollidingWith), With<Enemy>>,
-    mut q_child_with_text: Query<(&mut Transform, &Text), Without<Enemy>>,
-) {
-    let mut q_parent_combinations_iter = q_parent_with_enemy.iter_combinations();
-    while let Some(
-        [(entity_first_enemy, children_first, text_colliding_with_first_enemy), (entity_second_enemy, children_second, text_colliding_with_second_enemy)],
-    ) = q_parent_combinations_iter.fetch_next()
-    {
-        if text_colliding_with_first_enemy.entity_colliding_with == entity_second_enemy {
-            check_if_colliding_text_is_right_above_and_change_position_of_text_if_necessary(
-                &mut q_child_with_text,
-                children_first,
-                children_second,
-            );
-        } else if text_colliding_with_second_enemy.entity_colliding_with == entity_first_enemy {
-            check_if_colliding_text_is_right_above_and_change_position_of_text_if_necessary(
-                &mut q_child_with_text,
-                children_second,
-                children_first,
-            );
-        };
-    }
+/// Returns the area on screen (below the hud banner) in which the center of a label of the given size may lie
+fn label_bounds(label_size: Vec2) -> Rect {
+    let half_size = 0.5 * label_size;
+    let min = Vec2::new(-0.5 * WORLD_WIDTH, -0.5 * WORLD_HEIGHT) + half_size;
+    let max = Vec2::new(0.5 * WORLD_WIDTH, 0.5 * WORLD_HEIGHT - HUD_BANNER_HEIGHT) - half_size;
+    Rect::from_corners(min, max.max(min))
 }
 
-/// Checks if the text of the colliding enemy has moved and adjusts position accordingly if necessary
-fn check_if_colliding_text_is_right_above_and_change_position_of_text_if_necessary(
-    q_child_with_text: &mut Query<(&mut Transform, &Text), Without<Enemy>>,
-    child_text_that_is_moved_above: &Children,
-    child_text_of_enemy_that_is_collided_with: &Children,
-) {
-    let mut text_iter_collided_with =
-        q_child_with_text.iter_many(child_text_of_enemy_that_is_collided_with);
-    let mut y_translation_of_text_that_is_collided_with_option: Option<f32> = None;
-    if let Some((text_transform, _)) = text_iter_collided_with.fetch_next() {
-        y_translation_of_text_that_is_collided_with_option = Some(text_transform.translation.y);
-    }
+/// Finds the slot closest to the desired position that doesn't overlap any placed label.
+/// Tries the slots above the desired position first and then the ones below.
+/// If every slot is taken the label is placed at the desired position
+fn find_free_label_rect(
+    label_grid: &SpatialGrid<()>,
+    desired_center: Vec2,
+    label_size: Vec2,
+) -> Rect {
+    let bounds = label_bounds(label_size);
+    let desired_center = desired_center.clamp(bounds.min, bounds.max);
 
-    let mut text_iter = q_child_with_text.iter_many_mut(child_text_that_is_moved_above);
-    if let Some((mut text_transform, _)) = text_iter.fetch_next() {
-        if let Some(y_translation_of_text_that_is_collided_with) =
-            y_translation_of_text_that_is_collided_with_option
-        {
-            if text_transform.translation.y
-                != y_translation_of_text_that_is_collided_with + TEXT_HEIGHT
-            {
-                text_transform.translation.y =
-                    y_translation_of_text_that_is_collided_with + TEXT_HEIGHT;
-            }
-        }
-    }
+    let slots_above = ((bounds.max.y - desired_center.y) / LABEL_HEIGHT) as i32;
+    let slots_below = ((desired_center.y - bounds.min.y) / LABEL_HEIGHT) as i32;
+    (0..=slots_above)
+        .chain((1..=slots_below).map(|slot| -slot))
+        .map(|slot| {
+            Rect::from_center_size(
+                desired_center + Vec2::new(0.0, slot as f32 * LABEL_HEIGHT),
+                label_size,
+            )
+        })
+        .find(|candidate| !label_grid.overlaps_any(*candidate))
+        .unwrap_or_else(|| Rect::from_center_size(desired_center, label_size))
 }
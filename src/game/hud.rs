@@ -4,6 +4,11 @@ use systems::*;
 pub mod components;
 use components::*;
 
+use super::adaptive_difficulty::resources::AdaptiveDifficulty;
+use super::castle::resources::NumberOfLivesLeft;
+use super::power_ups::resources::ActivePowerUps;
+use super::rounds_and_indicators::resources::GameModeIndicator;
+use super::shop::resources::{Coins, PurchasedUpgrades};
 use crate::menu::{GameStartedState, MenuState};
 
 use super::*;
@@ -18,14 +23,19 @@ impl Plugin for HUDPlugin {
             .register_type::<InGameHudParent>()
             .register_type::<ScoreText>()
             .register_type::<WpmText>()
+            .register_type::<LivesText>()
+            .register_type::<CoinsText>()
+            .register_type::<PowerUpText>()
             // Add startup systems
             .add_systems(Startup, spawn_hud)
             .add_systems(
                 OnEnter(RoundState::InBetweenRounds),
-                spawn_in_between_rounds_text.run_if(
-                    in_state(GameStartedState::GameHasStarted)
-                        .and_then(in_state(MenuState::NotInTheMenu)),
-                ),
+                spawn_round_summary
+                    .after(super::adaptive_difficulty::systems::record_round_performance)
+                    .run_if(
+                        in_state(GameStartedState::GameHasStarted)
+                            .and_then(in_state(MenuState::NotInTheMenu)),
+                    ),
             )
             .add_systems(
                 OnExit(RoundState::InBetweenRounds),
@@ -38,6 +48,14 @@ impl Plugin for HUDPlugin {
                 Update,
                 update_score_hud_element.after(super::rounds_and_indicators::systems::update_score),
             )
+            .add_systems(OnEnter(AppState::InGame), update_score_hud_label)
+            .add_systems(
+                Update,
+                update_score_hud_label.run_if(
+                    resource_changed::<AdaptiveDifficulty>
+                        .or_else(resource_changed::<GameModeIndicator>),
+                ),
+            )
             .add_systems(
                 Update,
                 update_streak_hud_element.in_set(super::InputHandlingSystemSet::AfterInputHandling),
@@ -50,6 +68,20 @@ impl Plugin for HUDPlugin {
                 Update,
                 update_round_number_hud_element
                     .after(super::rounds_and_indicators::systems::increase_round_counter),
+            )
+            .add_systems(
+                Update,
+                update_lives_hud_element.run_if(resource_changed::<NumberOfLivesLeft>),
+            )
+            .add_systems(
+                Update,
+                update_coins_hud_element.run_if(
+                    resource_changed::<Coins>.or_else(resource_changed::<PurchasedUpgrades>),
+                ),
+            )
+            .add_systems(
+                Update,
+                update_power_up_hud_element.run_if(resource_changed::<ActivePowerUps>),
             );
     }
 }
@@ -30,6 +30,21 @@ pub struct ScoreText;
 #[reflect(Component)]
 pub struct StreakText;
 
+/// Tag component used to tag the text that displays the lives of the castle
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct LivesText;
+
+/// Tag component used to tag the text that displays the coins and the bought upgrades
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct CoinsText;
+
+/// Tag component used to tag the text that displays the active power-ups
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct PowerUpText;
+
 /// Tag component used to tag the parent entity of all the nodes in the hud banner at the top of the screen
 #[derive(Reflect, Component, Default)]
 #[reflect(Component)]
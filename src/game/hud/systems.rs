@@ -1,12 +1,27 @@
-use self::rounds_and_indicators::resources::{RoundNumber, StreakIndicator};
+use self::rounds_and_indicators::resources::{DifficultyIndicator, RoundNumber, StreakIndicator};
 
+use super::castle::resources::{NumberOfLivesLeft, NUMBER_OF_LIVES_AT_START};
+use super::enemies::components::EnemyType;
+use super::power_ups::resources::ActivePowerUps;
+use super::shop::resources::{Coins, PurchasedUpgrades, Upgrade};
+
+use super::adaptive_difficulty::resources::AdaptiveDifficulty;
 use super::enemies::systems::ENEMY_TEXT_FONT_SIZE;
-use super::rounds_and_indicators::resources::{ScoreIndicator, WordPerMinuteTypedIndicator};
+use super::rounds_and_indicators::resources::{
+    GameMode, GameModeIndicator, NumberOfEnemiesTypedThisRound, RoundSummary, ScoreIndicator,
+    WordPerMinuteTypedIndicator,
+};
 use super::*;
 
 pub const UI_TEXT_FONT_SIZE: f32 = ENEMY_TEXT_FONT_SIZE * 0.50;
 pub const UI_TEXT_COLOR: Color = Color::DARK_GRAY;
 pub const UI_NUMBER_TEXT_COLOR: Color = Color::WHITE;
+/// Color of the number of lives once the castle can only take one more hit
+pub const UI_LOW_LIVES_TEXT_COLOR: Color = Color::ORANGE_RED;
+/// Background of the summary panel shown in between rounds
+const ROUND_SUMMARY_BACKGROUND_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.65);
+/// Number of words that reached the castle listed in the round summary, further ones are only counted
+const MAX_WORDS_AT_CASTLE_SHOWN: usize = 8;
 
 /// Updates the wpm text in the hud
 pub fn update_wpm_hud_element(
@@ -65,7 +80,7 @@ pub fn update_round_number_hud_element(
     }
 }
 
-/// Updates the score text in the hud
+/// Updates the score text in the hud and marks the score of adaptive runs
 pub fn update_score_hud_element(
     score: Res<ScoreIndicator>,
     mut score_hud_text_query: Query<&mut Text, With<ScoreText>>,
@@ -84,6 +99,83 @@ pub fn update_score_hud_element(
     }
 }
 
+/// Labels the score in the hud as adaptive when the game starts in the adaptive mode and whenever
+/// the mode is switched. Scores of adaptive runs are not comparable to the ones of the fixed difficulties
+pub fn update_score_hud_label(
+    mut score_hud_text_query: Query<&mut Text, With<ScoreText>>,
+    adaptive_difficulty: Res<AdaptiveDifficulty>,
+    game_mode: Res<GameModeIndicator>,
+) {
+    let score_label = if adaptive_difficulty.enabled && game_mode.mode == GameMode::Endless {
+        "Score (adaptive): "
+    } else {
+        "Score: "
+    };
+    for mut text in &mut score_hud_text_query {
+        text.sections[0].value = score_label.to_string();
+    }
+}
+
+/// Updates the lives text in the hud. The number turns red when only one life is left
+pub fn update_lives_hud_element(
+    number_of_lives_left: Res<NumberOfLivesLeft>,
+    mut lives_hud_text_query: Query<&mut Text, With<LivesText>>,
+) {
+    for mut text in &mut lives_hud_text_query {
+        text.sections[1].value = format!(
+            "{}/{}",
+            number_of_lives_left.number, number_of_lives_left.maximum
+        );
+        text.sections[1].style.color = if number_of_lives_left.number <= 1 {
+            UI_LOW_LIVES_TEXT_COLOR
+        } else {
+            UI_NUMBER_TEXT_COLOR
+        };
+    }
+}
+
+/// Updates the coins text in the hud and lists the upgrades bought so far
+pub fn update_coins_hud_element(
+    coins: Res<Coins>,
+    purchased_upgrades: Res<PurchasedUpgrades>,
+    mut coins_hud_text_query: Query<&mut Text, With<CoinsText>>,
+) {
+    let bought_upgrades: Vec<String> = Upgrade::ALL
+        .iter()
+        .filter(|upgrade| **upgrade != Upgrade::CastleRepair)
+        .filter(|upgrade| purchased_upgrades.level(**upgrade) > 0)
+        .map(|upgrade| format!("{} {}", upgrade, purchased_upgrades.level(*upgrade)))
+        .collect();
+    for mut text in &mut coins_hud_text_query {
+        text.sections[1].value = coins.amount.to_string();
+        text.sections[2].value = if bought_upgrades.is_empty() {
+            String::new()
+        } else {
+            format!("\n{}", bought_upgrades.join("\n"))
+        };
+    }
+}
+
+/// Updates the text in the hud listing the active power-ups with their remaining time
+pub fn update_power_up_hud_element(
+    active_power_ups: Res<ActivePowerUps>,
+    mut power_up_hud_text_query: Query<&mut Text, With<PowerUpText>>,
+) {
+    let active: Vec<String> = active_power_ups
+        .active()
+        .iter()
+        .map(|(effect, seconds_left)| format!("{} {:.1}s", effect, seconds_left))
+        .collect();
+    let color = active_power_ups
+        .active()
+        .first()
+        .map_or(UI_NUMBER_TEXT_COLOR, |(effect, _)| effect.color());
+    for mut text in &mut power_up_hud_text_query {
+        text.sections[0].value = active.join("\n");
+        text.sections[0].style.color = color;
+    }
+}
+
 /// Spawns the hud
 pub fn spawn_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands
@@ -103,6 +195,61 @@ pub fn spawn_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
             Name::new("Hud Banner parent"),
         ))
         .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_sections([
+                    TextSection::new(
+                        "Coins: ",
+                        TextStyle {
+                            color: UI_TEXT_COLOR,
+                            font_size: UI_TEXT_FONT_SIZE,
+                            ..default()
+                        },
+                    ),
+                    TextSection::new(
+                        "0",
+                        TextStyle {
+                            font_size: UI_TEXT_FONT_SIZE,
+                            color: UI_NUMBER_TEXT_COLOR,
+                            ..default()
+                        },
+                    ),
+                    TextSection::new(
+                        "",
+                        TextStyle {
+                            font_size: UI_TEXT_FONT_SIZE * 0.6,
+                            color: UI_TEXT_COLOR,
+                            ..default()
+                        },
+                    ),
+                ])
+                .with_style(Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(1.0),
+                    top: Val::Percent(1.0),
+                    ..default()
+                }),
+                CoinsText,
+                InGameHudUiElement,
+            ));
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: UI_TEXT_FONT_SIZE,
+                        color: UI_NUMBER_TEXT_COLOR,
+                        ..default()
+                    },
+                )
+                .with_text_justify(JustifyText::Right)
+                .with_style(Style {
+                    position_type: PositionType::Absolute,
+                    right: Val::Percent(1.0),
+                    top: Val::Percent(1.0),
+                    ..default()
+                }),
+                PowerUpText,
+                InGameHudUiElement,
+            ));
             parent
                 .spawn((
                     NodeBundle {
@@ -146,7 +293,7 @@ pub fn spawn_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
                             ),
                         ])
                         .with_style(Style {
-                            width: Val::Percent(20.0),
+                            width: Val::Percent(16.0),
                             margin: UiRect::new(
                                 Val::Percent(5.0),
                                 Val::Percent(0.0),
@@ -179,7 +326,7 @@ pub fn spawn_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
                             ),
                         ])
                         .with_style(Style {
-                            width: Val::Percent(20.0),
+                            width: Val::Percent(16.0),
                             margin: UiRect::bottom(Val::Percent(3.8)),
                             ..default()
                         }),
@@ -207,7 +354,7 @@ pub fn spawn_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
                             ),
                         ])
                         .with_style(Style {
-                            width: Val::Percent(20.0),
+                            width: Val::Percent(16.0),
                             margin: UiRect::new(
                                 Val::Percent(0.0),
                                 Val::Percent(0.0),
@@ -241,7 +388,35 @@ pub fn spawn_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
                             ),
                         ])
                         .with_style(Style {
-                            width: Val::Percent(20.0),
+                            width: Val::Percent(16.0),
+                            margin: UiRect::bottom(Val::Percent(3.8)),
+                            ..default()
+                        }),
+                        RoundNumberText,
+                        InGameHudUiElement,
+                    ));
+                    parent.spawn((
+                        // Create a TextBundle that has a Text with a list of sections.
+                        TextBundle::from_sections([
+                            TextSection::new(
+                                "Lives: ",
+                                TextStyle {
+                                    color: UI_TEXT_COLOR,
+                                    font_size: UI_TEXT_FONT_SIZE,
+                                    ..default()
+                                },
+                            ),
+                            TextSection::new(
+                                format!("{0}/{0}", NUMBER_OF_LIVES_AT_START),
+                                TextStyle {
+                                    font_size: UI_TEXT_FONT_SIZE,
+                                    color: UI_NUMBER_TEXT_COLOR,
+                                    ..default()
+                                },
+                            ),
+                        ])
+                        .with_style(Style {
+                            width: Val::Percent(16.0),
                             margin: UiRect::new(
                                 Val::Percent(0.0),
                                 Val::Percent(5.0),
@@ -250,33 +425,131 @@ pub fn spawn_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
                             ),
                             ..default()
                         }),
-                        RoundNumberText,
+                        LivesText,
                         InGameHudUiElement,
                     ));
                 });
         });
 }
 
-/// Spawns the text in between rounds indicating that it's in between rounds and how to continue
-pub fn spawn_in_between_rounds_text(mut commands: Commands) {
-    commands.spawn((
-        // Create a TextBundle that has a Text with a list of sections.
-        TextBundle::from_section(
-            "You are currently in between rounds.\nPress 'Space' to start the next round",
-            TextStyle {
-                font_size: 60.0,
-                color: Color::BLACK,
+/// Spawns the summary of the round that just finished in between rounds together with how to continue.
+/// Also shows the adjustment of the adaptive difficulty and announces the enemy types that appear for
+/// the first time in the next round
+pub fn spawn_round_summary(
+    mut commands: Commands,
+    round_number: Res<RoundNumber>,
+    difficulty_indicator: Res<DifficultyIndicator>,
+    round_summary: Res<RoundSummary>,
+    number_of_enemies_typed_this_round: Res<NumberOfEnemiesTypedThisRound>,
+    wpm: Res<WordPerMinuteTypedIndicator>,
+    adaptive_difficulty: Res<AdaptiveDifficulty>,
+    game_mode: Res<GameModeIndicator>,
+) {
+    let score = &round_summary.score;
+    let mut summary_text = format!(
+        "Words typed: {}   Missed: {}   Lives lost: {}\n\
+         WPM: {:.0}   Accuracy: {:.1}%   Best streak: {}\n\
+         Score gained: {}\n\
+         WPM {:.0} + streak {:.0} + round {:.0} + difficulty {:.0}",
+        number_of_enemies_typed_this_round.number,
+        round_summary.words_missed,
+        round_summary.lives_lost,
+        wpm.wpm,
+        round_summary.accuracy(),
+        round_summary.best_streak,
+        score.total,
+        score.wpm,
+        score.streak,
+        score.round,
+        score.difficulty,
+    );
+    if score.upgrades > 0.0 {
+        summary_text.push_str(&format!(" + upgrades {:.0}", score.upgrades));
+    }
+    if score.power_ups > 0.0 {
+        summary_text.push_str(&format!(" + power-ups {:.0}", score.power_ups));
+    }
+    if !round_summary.words_at_castle.is_empty() {
+        let mut words_at_castle = round_summary
+            .words_at_castle
+            .iter()
+            .take(MAX_WORDS_AT_CASTLE_SHOWN)
+            .cloned()
+            .collect::<Vec<String>>()
+            .join(", ");
+        if round_summary.words_at_castle.len() > MAX_WORDS_AT_CASTLE_SHOWN {
+            words_at_castle.push_str(&format!(
+                " and {} more",
+                round_summary.words_at_castle.len() - MAX_WORDS_AT_CASTLE_SHOWN
+            ));
+        }
+        summary_text.push_str(&format!("\nReached the castle: {words_at_castle}"));
+    }
+    if adaptive_difficulty.enabled && game_mode.mode == GameMode::Endless {
+        summary_text.push_str(&format!("\n{}", *adaptive_difficulty));
+    }
+    let mut new_enemy_names: Vec<String> =
+        EnemyType::introduced_in_round(round_number.number + 1, &difficulty_indicator.difficulty)
+            .iter()
+            .map(|enemy_type| enemy_type.to_string())
+            .collect();
+    // The rocks are all called rock
+    new_enemy_names.dedup();
+    if !new_enemy_names.is_empty() {
+        summary_text.push_str(&format!(
+            "\nNew enemies approaching: {}",
+            new_enemy_names.join(", ")
+        ));
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    align_self: AlignSelf::Start,
+                    justify_self: JustifySelf::Center,
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(8.0),
+                    max_width: Val::Percent(70.0),
+                    padding: UiRect::all(Val::Px(16.0)),
+                    margin: UiRect::top(Val::Percent(7.0)),
+                    ..default()
+                },
+                background_color: ROUND_SUMMARY_BACKGROUND_COLOR.into(),
                 ..default()
             },
-        )
-        .with_style(Style {
-            position_type: PositionType::Absolute,
-            align_self: AlignSelf::Center,
-            justify_self: JustifySelf::Center,
-            margin: UiRect::bottom(Val::Percent(32.5)),
-            ..default()
-        })
-        .with_text_justify(JustifyText::Center),
-        InBetweenRoundsHudUiElement,
-    ));
+            InBetweenRoundsHudUiElement,
+            Name::new("Round summary"),
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                format!("Round {} complete", round_number.number),
+                TextStyle {
+                    font_size: 50.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+            parent.spawn(
+                TextBundle::from_section(
+                    summary_text,
+                    TextStyle {
+                        font_size: 26.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                )
+                .with_text_justify(JustifyText::Center),
+            );
+            parent.spawn(TextBundle::from_section(
+                "Press 'Space' to start the next round",
+                TextStyle {
+                    font_size: 34.0,
+                    color: UI_NUMBER_TEXT_COLOR,
+                    ..default()
+                },
+            ));
+        });
 }
@@ -0,0 +1,40 @@
+pub mod components;
+use components::*;
+
+pub mod resources;
+use resources::*;
+
+pub mod systems;
+use systems::*;
+
+use super::*;
+
+use bevy_common_assets::toml::TomlAssetPlugin;
+
+pub struct MapPlugin;
+
+impl Plugin for MapPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Register types for debug
+            .register_type::<MapBackground>()
+            .register_type::<SelectedMap>()
+            // Initialize Resources
+            .init_resource::<SelectedMap>()
+            .init_resource::<LoadedMap>()
+            // Setup maps as assets
+            .add_plugins(TomlAssetPlugin::<Map>::new(&["map.toml"]))
+            .add_systems(Startup, setup_map_assets)
+            // Add update systems. The map has to be loaded before entering the game (e.g. for the castle)
+            .add_systems(
+                Update,
+                (
+                    update_loaded_map
+                        .after(crate::menu::systems::menu_action)
+                        .after(daily_challenge::systems::start_daily_challenge),
+                    spawn_map_background.run_if(resource_changed::<LoadedMap>),
+                )
+                    .chain(),
+            );
+    }
+}
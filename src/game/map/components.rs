@@ -0,0 +1,6 @@
+use super::*;
+
+/// Component used to tag the background of the map
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct MapBackground {}
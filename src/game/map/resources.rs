@@ -0,0 +1,500 @@
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use super::*;
+use crate::game::enemies::movement::components::EnemySpawnPoint;
+
+/// Paths of all maps that can be selected in the main menu. The first one is the default map
+pub const MAP_PATHS: [&str; 3] = [
+    "maps/meadow.map.toml",
+    "maps/meadow_sides.map.toml",
+    "maps/meadow_junctions.map.toml",
+];
+/// Number of points a smoothed route is sampled with between two checkpoints
+const ROUTE_SMOOTHING_SUBDIVISIONS: usize = 8;
+
+/// Asset that describes a map. All coordinates are in pixels of the background image with the
+/// origin at its center
+#[derive(serde::Deserialize, serde::Serialize, Asset, TypePath, Clone)]
+pub struct Map {
+    pub name: String,
+    pub background: String,
+    pub castle_position: (f32, f32),
+    pub tree_positions: Vec<(f32, f32)>,
+    /// Name of the route the boss walks along
+    pub boss_route: String,
+    pub routes: Vec<RouteDescription>,
+}
+
+/// A named route of a map given by the checkpoints enemies walk along. The first checkpoint is the
+/// spawn point.
+///
+/// At the end of the route enemies continue on one of the branches, which should start at the last
+/// checkpoint. Routes can branch into the same route, such that shared parts only need to be defined once
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct RouteDescription {
+    pub name: String,
+    pub checkpoints: Vec<(f32, f32)>,
+    #[serde(default)]
+    pub smoothing: RouteSmoothing,
+    /// Whether enemies spawn at the start of this route or only reach it through branches
+    #[serde(default = "default_spawn")]
+    pub spawn: bool,
+    #[serde(default)]
+    pub branches: Vec<BranchDescription>,
+}
+
+/// A route enemies can continue on at the end of another route. The branch is chosen randomly
+/// according to the weights of all branches
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct BranchDescription {
+    pub route: String,
+    #[serde(default = "default_weight")]
+    pub weight: f32,
+}
+
+fn default_spawn() -> bool {
+    true
+}
+
+fn default_weight() -> f32 {
+    1.0
+}
+
+/// How the checkpoints of a route are connected
+#[derive(serde::Deserialize, serde::Serialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteSmoothing {
+    /// Straight lines between the checkpoints
+    #[default]
+    None,
+    /// A Catmull-Rom spline through the checkpoints
+    CatmullRom,
+}
+
+/// Resource holding the handles of all maps that can be selected
+#[derive(Resource, Default)]
+pub struct MapHandles {
+    pub handles: Vec<Handle<Map>>,
+}
+
+/// Resource for tracking which of the maps in MAP_PATHS is selected
+#[derive(Reflect, Resource, Default)]
+#[reflect(Resource)]
+pub struct SelectedMap {
+    pub index: usize,
+}
+
+/// Resource holding the selected map once it is loaded.
+/// Stays empty until the first map is loaded
+#[derive(Resource, Default)]
+pub struct LoadedMap {
+    pub name: String,
+    pub background: String,
+    pub castle_position: Vec2,
+    pub tree_positions: Vec<Vec2>,
+    pub boss_route: EnemySpawnPoint,
+    pub routes: Vec<Route>,
+    /// Routes enemies spawn on
+    pub spawn_routes: Vec<usize>,
+}
+
+/// A route of the loaded map as a polyline that enemies walk along by arc length
+pub struct Route {
+    pub name: String,
+    /// Points of the polyline. The first point is the spawn point
+    pub points: Vec<Vec2>,
+    /// Arc length from the start of the route to each of the points
+    pub distances: Vec<f32>,
+    /// Arc length from the start of the route to each of the checkpoints it was created from
+    pub checkpoint_distances: Vec<f32>,
+    /// Routes enemies can continue on at the end of this route with their weights
+    pub branches: Vec<(usize, f32)>,
+}
+
+impl LoadedMap {
+    /// Creates the loaded map from a map asset. Routes with fewer than two checkpoints are left out
+    /// and branches, the boss route and the spawn routes refer to the remaining routes
+    pub fn from_map(map: &Map) -> LoadedMap {
+        let descriptions: Vec<&RouteDescription> = map
+            .routes
+            .iter()
+            .filter(|description| {
+                let is_valid = description.checkpoints.len() >= 2;
+                if !is_valid {
+                    warn!(
+                        "Route {} of map {} needs at least two checkpoints and is left out",
+                        description.name, map.name
+                    );
+                }
+                is_valid
+            })
+            .collect();
+        let route_index = |name: &str| {
+            descriptions
+                .iter()
+                .position(|description| description.name == name)
+        };
+        let mut routes: Vec<Route> = descriptions
+            .iter()
+            .map(|description| Route::from_description(description))
+            .collect();
+        // Resolve the names of the branches once all routes exist
+        for (route, description) in routes.iter_mut().zip(&descriptions) {
+            for branch in &description.branches {
+                match route_index(&branch.route) {
+                    Some(branch_route) => route.branches.push((branch_route, branch.weight)),
+                    None => warn!(
+                        "Branch {} of route {} does not exist on map {}",
+                        branch.route, description.name, map.name
+                    ),
+                }
+            }
+        }
+        let boss_route = match route_index(&map.boss_route) {
+            Some(route) => route,
+            None => {
+                warn!(
+                    "Boss route {} does not exist on map {}. Using first route",
+                    map.boss_route, map.name
+                );
+                0
+            }
+        };
+        LoadedMap {
+            name: map.name.clone(),
+            background: map.background.clone(),
+            castle_position: Vec2::from(map.castle_position),
+            tree_positions: map.tree_positions.iter().map(|&p| Vec2::from(p)).collect(),
+            boss_route: EnemySpawnPoint { route: boss_route },
+            spawn_routes: descriptions
+                .iter()
+                .enumerate()
+                .filter(|(_, description)| description.spawn)
+                .map(|(route, _)| route)
+                .collect(),
+            routes,
+        }
+    }
+
+    /// Returns whether enemies can spawn on the map, i.e. it has at least one valid spawn route.
+    /// The boss route is always valid then
+    pub fn is_playable(&self) -> bool {
+        !self.spawn_routes.is_empty()
+    }
+
+    /// Returns the route an enemy walks along given its spawn point
+    pub fn route(&self, spawn_point: &EnemySpawnPoint) -> &Route {
+        self.routes
+            .get(spawn_point.route)
+            .expect("Route should exist")
+    }
+
+    /// Returns the route with the given name if it exists
+    pub fn route_named(&self, name: &str) -> Option<&Route> {
+        self.routes.iter().find(|route| route.name == name)
+    }
+
+    /// Returns the expected distance an enemy walks from the start of the given route to the end of its
+    /// journey, averaging over the branches by their weights. Loops are followed a limited number of times
+    pub fn expected_length_from(&self, route: usize) -> f32 {
+        self.expected_length_with_depth(route, self.routes.len())
+    }
+
+    fn expected_length_with_depth(&self, route: usize, depth: usize) -> f32 {
+        let Some(current_route) = self.routes.get(route) else {
+            return 0.0;
+        };
+        let total_weight: f32 = current_route
+            .branches
+            .iter()
+            .map(|(_, weight)| weight)
+            .sum();
+        if depth == 0 || total_weight <= 0.0 {
+            return current_route.length();
+        }
+        current_route.length()
+            + current_route
+                .branches
+                .iter()
+                .map(|&(branch, weight)| {
+                    weight / total_weight * self.expected_length_with_depth(branch, depth - 1)
+                })
+                .sum::<f32>()
+    }
+}
+
+impl Route {
+    /// Creates a route from its description, smoothing it if requested
+    fn from_description(description: &RouteDescription) -> Route {
+        let checkpoints: Vec<Vec2> = description
+            .checkpoints
+            .iter()
+            .map(|&checkpoint| Vec2::from(checkpoint))
+            .collect();
+        let number_of_checkpoints = checkpoints.len();
+        let points: Vec<Vec2> = match description.smoothing {
+            RouteSmoothing::CatmullRom if checkpoints.len() > 2 => {
+                // Repeat the first and last checkpoint such that the spline passes through all checkpoints
+                let control_points: Vec<Vec2> = std::iter::once(checkpoints[0])
+                    .chain(checkpoints.iter().copied())
+                    .chain(std::iter::once(checkpoints[checkpoints.len() - 1]))
+                    .collect();
+                CubicCardinalSpline::new_catmull_rom(control_points)
+                    .to_curve()
+                    .iter_positions((checkpoints.len() - 1) * ROUTE_SMOOTHING_SUBDIVISIONS)
+                    .collect()
+            }
+            _ => checkpoints,
+        };
+        let mut distances: Vec<f32> = Vec::with_capacity(points.len());
+        let mut distance = 0.0;
+        for (index, point) in points.iter().enumerate() {
+            if index > 0 {
+                distance += point.distance(points[index - 1]);
+            }
+            distances.push(distance);
+        }
+        // Smoothed routes pass through the checkpoints every few points
+        let checkpoint_distances: Vec<f32> = if points.len() == number_of_checkpoints {
+            distances.clone()
+        } else {
+            distances
+                .iter()
+                .copied()
+                .step_by(ROUTE_SMOOTHING_SUBDIVISIONS)
+                .collect()
+        };
+        Route {
+            name: description.name.clone(),
+            points,
+            distances,
+            checkpoint_distances,
+            branches: Vec::new(),
+        }
+    }
+
+    /// Returns the length of the route
+    pub fn length(&self) -> f32 {
+        self.distances.last().copied().unwrap_or(0.0)
+    }
+
+    /// Chooses the branch an enemy continues on at the end of the route. Returns None if the route
+    /// does not branch.
+    ///
+    /// The choice only depends on the branch seed and the number of junctions passed, such that
+    /// enemies with the same seed (e.g. the boss and its ghosts) take the same branches
+    pub fn choose_branch(&self, branch_seed: u64, junctions_passed: u32) -> Option<usize> {
+        let mut rng = StdRng::seed_from_u64(
+            branch_seed ^ (u64::from(junctions_passed) + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15),
+        );
+        self.branches
+            .choose_weighted(&mut rng, |(_, weight)| *weight)
+            .ok()
+            .map(|(route, _)| *route)
+    }
+
+    /// Returns the index of the segment the given distance along the route lies on.
+    /// Distances beyond the end of the route lie on the last segment
+    fn segment_at(&self, distance: f32) -> usize {
+        self.distances
+            .partition_point(|&point_distance| point_distance <= distance)
+            .saturating_sub(1)
+            .min(self.points.len().saturating_sub(2))
+    }
+
+    /// Returns the position at the given distance along the route.
+    /// Beyond the end, the route continues in the direction of its last segment
+    pub fn position_at(&self, distance: f32) -> Vec2 {
+        let segment = self.segment_at(distance);
+        let start = *self.points.get(segment).expect("Route should not be empty");
+        start + self.direction_at(distance) * (distance - self.distances[segment])
+    }
+
+    /// Returns the normalized direction of travel at the given distance along the route
+    pub fn direction_at(&self, distance: f32) -> Vec2 {
+        let segment = self.segment_at(distance);
+        match (self.points.get(segment), self.points.get(segment + 1)) {
+            (Some(start), Some(end)) => (*end - *start).normalize_or_zero(),
+            _ => Vec2::ZERO,
+        }
+    }
+
+    /// Returns the distance of the last checkpoint at least the given distance before the given
+    /// distance along the route, or the start of the route if there is none
+    pub fn checkpoint_before(&self, distance: f32, minimum_distance_back: f32) -> f32 {
+        self.checkpoint_distances
+            .iter()
+            .rev()
+            .copied()
+            .find(|&checkpoint_distance| checkpoint_distance <= distance - minimum_distance_back)
+            .unwrap_or(0.0)
+    }
+
+    /// Returns the shortest distance from the given position to the route
+    pub fn distance_to(&self, position: Vec2) -> f32 {
+        self.points
+            .windows(2)
+            .map(|segment| {
+                let along = segment[1] - segment[0];
+                let t = ((position - segment[0]).dot(along)
+                    / along.length_squared().max(f32::EPSILON))
+                .clamp(0.0, 1.0);
+                position.distance(segment[0] + along * t)
+            })
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    /// Returns whether the route starts on the left half of the map
+    pub fn starts_on_the_left(&self) -> bool {
+        self.points
+            .first()
+            .is_some_and(|spawn_point| spawn_point.x < 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route_description(name: &str, checkpoints: Vec<(f32, f32)>) -> RouteDescription {
+        RouteDescription {
+            name: name.to_string(),
+            checkpoints,
+            smoothing: RouteSmoothing::None,
+            spawn: true,
+            branches: Vec::new(),
+        }
+    }
+
+    fn map(routes: Vec<RouteDescription>, boss_route: &str) -> Map {
+        Map {
+            name: "Test".to_string(),
+            background: String::new(),
+            castle_position: (0.0, 0.0),
+            tree_positions: Vec::new(),
+            boss_route: boss_route.to_string(),
+            routes,
+        }
+    }
+
+    #[test]
+    fn from_map_leaves_out_routes_with_fewer_than_two_checkpoints() {
+        let mut short_route = route_description("short", vec![(0.0, 0.0)]);
+        short_route.branches.push(BranchDescription {
+            route: "long".to_string(),
+            weight: 1.0,
+        });
+        let mut long_route = route_description("long", vec![(0.0, 0.0), (10.0, 0.0)]);
+        long_route.branches.push(BranchDescription {
+            route: "short".to_string(),
+            weight: 1.0,
+        });
+        let loaded_map = LoadedMap::from_map(&map(vec![short_route, long_route], "long"));
+
+        assert_eq!(loaded_map.routes.len(), 1);
+        assert_eq!(loaded_map.routes[0].name, "long");
+        assert!(loaded_map.routes[0].branches.is_empty());
+        assert_eq!(loaded_map.spawn_routes, vec![0]);
+        assert_eq!(loaded_map.boss_route.route, 0);
+    }
+
+    #[test]
+    fn from_map_resolves_branches_by_name() {
+        let mut first_route = route_description("first", vec![(0.0, 0.0), (10.0, 0.0)]);
+        first_route.branches.push(BranchDescription {
+            route: "second".to_string(),
+            weight: 2.0,
+        });
+        let mut second_route = route_description("second", vec![(10.0, 0.0), (20.0, 0.0)]);
+        second_route.spawn = false;
+        let loaded_map = LoadedMap::from_map(&map(vec![first_route, second_route], "second"));
+
+        assert_eq!(loaded_map.routes[0].branches, vec![(1, 2.0)]);
+        assert_eq!(loaded_map.spawn_routes, vec![0]);
+        assert_eq!(loaded_map.boss_route.route, 1);
+        assert_eq!(loaded_map.expected_length_from(0), 20.0);
+    }
+
+    #[test]
+    fn checkpoint_before_returns_the_last_checkpoint_far_enough_back() {
+        let route = Route::from_description(&route_description(
+            "route",
+            vec![(0.0, 0.0), (100.0, 0.0), (200.0, 0.0), (300.0, 0.0)],
+        ));
+
+        assert_eq!(route.checkpoint_before(250.0, 0.0), 200.0);
+        assert_eq!(route.checkpoint_before(250.0, 100.0), 100.0);
+        assert_eq!(route.checkpoint_before(250.0, 200.0), 0.0);
+        assert_eq!(route.checkpoint_before(50.0, 100.0), 0.0);
+    }
+
+    #[test]
+    fn checkpoint_before_uses_the_checkpoints_of_smoothed_routes() {
+        let mut description =
+            route_description("route", vec![(0.0, 0.0), (100.0, 0.0), (200.0, 0.0)]);
+        description.smoothing = RouteSmoothing::CatmullRom;
+        let route = Route::from_description(&description);
+
+        assert_eq!(route.checkpoint_distances.len(), 3);
+        assert!(route.points.len() > 3);
+        assert_eq!(
+            route.checkpoint_before(route.length(), 1.0),
+            route.checkpoint_distances[1]
+        );
+    }
+
+    #[test]
+    fn distance_to_returns_the_distance_to_the_closest_segment() {
+        let route = Route::from_description(&route_description(
+            "route",
+            vec![(0.0, 0.0), (100.0, 0.0), (100.0, 100.0)],
+        ));
+
+        assert_eq!(route.distance_to(Vec2::new(50.0, 20.0)), 20.0);
+        assert_eq!(route.distance_to(Vec2::new(130.0, 50.0)), 30.0);
+        assert_eq!(route.distance_to(Vec2::new(-30.0, -40.0)), 50.0);
+    }
+
+    #[test]
+    fn map_without_valid_spawn_routes_is_not_playable() {
+        let mut branch_only_route = route_description("branch", vec![(0.0, 0.0), (10.0, 0.0)]);
+        branch_only_route.spawn = false;
+        let short_route = route_description("short", vec![(0.0, 0.0)]);
+
+        assert!(!LoadedMap::from_map(&map(vec![short_route.clone()], "short")).is_playable());
+        assert!(
+            !LoadedMap::from_map(&map(vec![short_route, branch_only_route], "branch"))
+                .is_playable()
+        );
+        assert!(LoadedMap::from_map(&map(
+            vec![route_description("long", vec![(0.0, 0.0), (10.0, 0.0)])],
+            "long"
+        ))
+        .is_playable());
+    }
+
+    #[test]
+    fn map_survives_a_round_trip_through_toml() {
+        let mut route = route_description("a \"quoted\" route", vec![(0.0, 0.0), (10.5, -3.0)]);
+        route.smoothing = RouteSmoothing::CatmullRom;
+        route.spawn = false;
+        route.branches.push(BranchDescription {
+            route: "other".to_string(),
+            weight: 2.5,
+        });
+        let mut original = map(vec![route], "a \"quoted\" route");
+        original.tree_positions.push((1.0, 2.0));
+
+        let toml = toml::to_string_pretty(&original).expect("Map should be serializable");
+        let parsed: Map = toml::from_str(&toml).expect("Serialized map should be parsable");
+
+        assert_eq!(parsed.boss_route, original.boss_route);
+        assert_eq!(parsed.tree_positions, original.tree_positions);
+        assert_eq!(parsed.routes[0].name, original.routes[0].name);
+        assert_eq!(parsed.routes[0].checkpoints, original.routes[0].checkpoints);
+        assert!(parsed.routes[0].smoothing == RouteSmoothing::CatmullRom);
+        assert!(!parsed.routes[0].spawn);
+        assert_eq!(parsed.routes[0].branches[0].route, "other");
+        assert_eq!(parsed.routes[0].branches[0].weight, 2.5);
+    }
+}
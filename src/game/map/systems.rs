@@ -0,0 +1,69 @@
+use super::*;
+
+/// Z value of the background (in order to be behind everything else)
+const BACKGROUND_Z_VALUE: f32 = -10.0;
+
+/// Starts loading all maps that can be selected
+pub fn setup_map_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(MapHandles {
+        handles: MAP_PATHS
+            .iter()
+            .map(|path| asset_server.load(*path))
+            .collect(),
+    });
+}
+
+/// Updates the loaded map if another map is selected or the selected map finished loading
+pub fn update_loaded_map(
+    mut map_asset_events: EventReader<AssetEvent<Map>>,
+    selected_map: Res<SelectedMap>,
+    map_handles: Res<MapHandles>,
+    maps: Res<Assets<Map>>,
+    mut loaded_map: ResMut<LoadedMap>,
+) {
+    let selected_map_handle = map_handles
+        .handles
+        .get(selected_map.index)
+        .expect("Selected map should exist");
+    let selected_map_was_loaded = map_asset_events.read().any(|event| {
+        event.is_loaded_with_dependencies(selected_map_handle)
+            || event.is_modified(selected_map_handle)
+    });
+    if selected_map.is_changed() || selected_map_was_loaded {
+        if let Some(map) = maps.get(selected_map_handle) {
+            let new_loaded_map = LoadedMap::from_map(map);
+            if new_loaded_map.is_playable() {
+                *loaded_map = new_loaded_map;
+            } else {
+                warn!(
+                    "Map {} has no route enemies can spawn on. Keeping the previous map",
+                    map.name
+                );
+            }
+        }
+    }
+}
+
+/// Spawns the background of the loaded map and despawns the previous one
+pub fn spawn_map_background(
+    mut commands: Commands,
+    background_query: Query<Entity, With<MapBackground>>,
+    loaded_map: Res<LoadedMap>,
+    asset_server: Res<AssetServer>,
+) {
+    for background_entity in background_query.iter() {
+        commands.entity(background_entity).despawn_recursive();
+    }
+    if loaded_map.background.is_empty() {
+        return;
+    }
+    commands.spawn((
+        SpriteBundle {
+            transform: Transform::from_xyz(0.0, 0.0, BACKGROUND_Z_VALUE),
+            texture: asset_server.load(&loaded_map.background),
+            ..default()
+        },
+        MapBackground {},
+        Name::new("Map Background"),
+    ));
+}
@@ -0,0 +1,53 @@
+pub mod components;
+use components::*;
+
+pub mod resources;
+use resources::*;
+
+mod systems;
+use systems::*;
+
+use super::*;
+
+use bevy_common_assets::toml::TomlAssetPlugin;
+
+pub struct PowerUpsPlugin;
+
+impl Plugin for PowerUpsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Register types for debug
+            .register_type::<PowerUp>()
+            .register_type::<PowerUpSettings>()
+            .register_type::<ActivePowerUps>()
+            // Initialize Resources
+            .init_resource::<PowerUpSettings>()
+            .init_resource::<ActivePowerUps>()
+            // Setup the power-up settings as asset
+            .add_plugins(TomlAssetPlugin::<PowerUpSettings>::new(&["power_ups.toml"]))
+            .add_systems(Startup, setup_power_up_settings)
+            // Power-ups end with the round
+            .add_systems(OnEnter(RoundState::InBetweenRounds), reset_active_power_ups)
+            // Add update systems
+            .add_systems(
+                Update,
+                (
+                    turn_new_enemies_into_power_ups,
+                    trigger_typed_power_ups,
+                    freeze_enemies,
+                    tick_active_power_ups,
+                    highlight_power_up_words,
+                )
+                    .chain()
+                    .in_set(InputHandlingSystemSet::AfterInputHandling),
+            )
+            .add_systems(
+                Update,
+                (
+                    apply_power_up_settings,
+                    draw_power_up_auras.run_if(in_state(AppState::InGame)),
+                    reset_active_power_ups_on_restart,
+                ),
+            );
+    }
+}
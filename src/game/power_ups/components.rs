@@ -0,0 +1,8 @@
+use super::*;
+
+/// Component of enemies carrying a power-up that is triggered when their word is typed
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct PowerUp {
+    pub effect: PowerUpEffect,
+}
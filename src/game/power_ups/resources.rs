@@ -0,0 +1,146 @@
+use std::fmt;
+
+use super::*;
+
+/// Path of the asset the power-up settings are loaded from
+pub const POWER_UP_SETTINGS_PATH: &str = "power_ups/default.power_ups.toml";
+/// Chance that a spawned enemy carries a power-up
+const POWER_UP_CHANCE: f32 = 0.08;
+/// Seconds enemies stay frozen after a freeze power-up was typed
+const FREEZE_DURATION: f32 = 4.0;
+/// Seconds the score is doubled after a double score power-up was typed
+const DOUBLE_SCORE_DURATION: f32 = 10.0;
+/// Seconds the name of a power-up without duration is shown in the hud after it was typed
+pub const ANNOUNCEMENT_DURATION: f32 = 2.0;
+
+/// The effects that are triggered by typing the word of an enemy carrying a power-up
+#[derive(Reflect, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerUpEffect {
+    /// All enemies on the road stop walking for a while
+    #[default]
+    Freeze,
+    /// The enemy with the fewest letters left is cleared
+    ClearEnemy,
+    /// The castle gets back one of its lives
+    RestoreLife,
+    /// Typed words give twice the score for a while
+    DoubleScore,
+}
+
+impl PowerUpEffect {
+    /// All power-up effects
+    pub const ALL: [PowerUpEffect; 4] = [
+        PowerUpEffect::Freeze,
+        PowerUpEffect::ClearEnemy,
+        PowerUpEffect::RestoreLife,
+        PowerUpEffect::DoubleScore,
+    ];
+
+    /// Returns the color the words and sprites of enemies carrying the power-up are highlighted with
+    pub fn color(&self) -> Color {
+        match self {
+            PowerUpEffect::Freeze => Color::CYAN,
+            PowerUpEffect::ClearEnemy => Color::FUCHSIA,
+            PowerUpEffect::RestoreLife => Color::LIME_GREEN,
+            PowerUpEffect::DoubleScore => Color::GOLD,
+        }
+    }
+}
+
+impl fmt::Display for PowerUpEffect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                PowerUpEffect::Freeze => "Freeze",
+                PowerUpEffect::ClearEnemy => "Clear",
+                PowerUpEffect::RestoreLife => "Repair",
+                PowerUpEffect::DoubleScore => "Double score",
+            }
+        )
+    }
+}
+
+/// Resource for how often power-ups spawn. Each spawned enemy carries a power-up with the given
+/// chance, which one is chosen according to the weights.
+/// Is loaded from the asset at POWER_UP_SETTINGS_PATH, missing fields keep their default
+#[derive(serde::Deserialize, Asset, Reflect, Resource, Clone)]
+#[serde(default)]
+#[reflect(Resource)]
+pub struct PowerUpSettings {
+    pub chance: f32,
+    pub freeze_weight: u32,
+    pub clear_enemy_weight: u32,
+    pub restore_life_weight: u32,
+    pub double_score_weight: u32,
+    pub freeze_duration: f32,
+    pub double_score_duration: f32,
+}
+
+impl Default for PowerUpSettings {
+    fn default() -> PowerUpSettings {
+        PowerUpSettings {
+            chance: POWER_UP_CHANCE,
+            freeze_weight: 3,
+            clear_enemy_weight: 3,
+            restore_life_weight: 1,
+            double_score_weight: 2,
+            freeze_duration: FREEZE_DURATION,
+            double_score_duration: DOUBLE_SCORE_DURATION,
+        }
+    }
+}
+
+impl PowerUpSettings {
+    /// Returns the weight with which the given effect is chosen
+    pub fn weight(&self, effect: PowerUpEffect) -> u32 {
+        match effect {
+            PowerUpEffect::Freeze => self.freeze_weight,
+            PowerUpEffect::ClearEnemy => self.clear_enemy_weight,
+            PowerUpEffect::RestoreLife => self.restore_life_weight,
+            PowerUpEffect::DoubleScore => self.double_score_weight,
+        }
+    }
+}
+
+/// Resource holding the handle of the power-up settings asset
+#[derive(Resource)]
+pub struct PowerUpSettingsHandle(pub Handle<PowerUpSettings>);
+
+/// Resource for the seconds left of the power-ups that last a while and the last power-up without
+/// duration, which is announced in the hud for a moment
+#[derive(Reflect, Resource, Default)]
+#[reflect(Resource)]
+pub struct ActivePowerUps {
+    pub freeze_seconds_left: f32,
+    pub double_score_seconds_left: f32,
+    pub announcement: Option<PowerUpEffect>,
+    pub announcement_seconds_left: f32,
+}
+
+impl ActivePowerUps {
+    /// Returns the factor the score of typed words is multiplied with
+    pub fn score_multiplier(&self) -> f64 {
+        if self.double_score_seconds_left > 0.0 {
+            2.0
+        } else {
+            1.0
+        }
+    }
+
+    /// Returns the active power-ups with the seconds they last for
+    pub fn active(&self) -> Vec<(PowerUpEffect, f32)> {
+        [
+            (PowerUpEffect::Freeze, self.freeze_seconds_left),
+            (PowerUpEffect::DoubleScore, self.double_score_seconds_left),
+        ]
+        .into_iter()
+        .chain(
+            self.announcement
+                .map(|effect| (effect, self.announcement_seconds_left)),
+        )
+        .filter(|(_, seconds_left)| *seconds_left > 0.0)
+        .collect()
+    }
+}
@@ -0,0 +1,244 @@
+use bevy::ecs::event::ManualEventReader;
+use rand::{seq::SliceRandom, Rng};
+
+use super::boss::components::Boss;
+use super::castle::resources::NumberOfLivesLeft;
+use super::enemies::components::{CurrentlyBeingTyped, Enemy, EnemyType};
+use super::enemies::movement::components::Frozen;
+use super::enemies::resources::EnemiesBeingTyped;
+use super::enemies::systems::STANDARD_TEXT_COLOR;
+use super::enemies::text::systems::{unlive_typed_enemy, EnemyTypedEvent};
+use super::randomness::resources::{GameRng, RngStream};
+use crate::menu::systems::Restart;
+
+use super::*;
+
+/// Radius of the ring pulsing around enemies carrying a power-up
+const POWER_UP_AURA_RADIUS: f32 = 40.0;
+/// Pulses of the ring around enemies carrying a power-up per second
+const POWER_UP_AURA_PULSE_FREQUENCY: f32 = 1.5;
+
+/// Starts loading the power-up settings
+pub fn setup_power_up_settings(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(PowerUpSettingsHandle(
+        asset_server.load(POWER_UP_SETTINGS_PATH),
+    ));
+}
+
+/// Applies the power-up settings once they are loaded
+pub fn apply_power_up_settings(
+    mut asset_events: EventReader<AssetEvent<PowerUpSettings>>,
+    power_up_settings_handle: Res<PowerUpSettingsHandle>,
+    power_up_settings_assets: Res<Assets<PowerUpSettings>>,
+    mut power_up_settings: ResMut<PowerUpSettings>,
+) {
+    let settings_were_loaded = asset_events.read().any(|event| {
+        event.is_loaded_with_dependencies(&power_up_settings_handle.0)
+            || event.is_modified(&power_up_settings_handle.0)
+    });
+    if !settings_were_loaded {
+        return;
+    }
+    if let Some(settings) = power_up_settings_assets.get(&power_up_settings_handle.0) {
+        *power_up_settings = settings.clone();
+    }
+}
+
+/// Lets newly spawned enemies carry a power-up according to the power-up settings and tints them in its color
+pub fn turn_new_enemies_into_power_ups(
+    mut commands: Commands,
+    mut new_enemy_query: Query<(Entity, &mut Sprite), Added<EnemyType>>,
+    power_up_settings: Res<PowerUpSettings>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let rng = game_rng.stream(RngStream::PowerUps);
+    for (entity, mut sprite) in new_enemy_query.iter_mut() {
+        if !rng.gen_bool(power_up_settings.chance.clamp(0.0, 1.0) as f64) {
+            continue;
+        }
+        let Ok(&effect) = PowerUpEffect::ALL
+            .choose_weighted(&mut *rng, |effect| power_up_settings.weight(*effect))
+        else {
+            continue;
+        };
+        sprite.color = effect.color();
+        commands.entity(entity).insert(PowerUp { effect });
+    }
+}
+
+/// Triggers the effect of the power-ups whose enemies were typed.
+/// Enemies cleared by a power-up count as typed, so they earn score and coins like typed enemies
+pub fn trigger_typed_power_ups(
+    mut commands: Commands,
+    mut enemy_typed_events: ResMut<Events<EnemyTypedEvent>>,
+    mut enemy_typed_event_reader: Local<ManualEventReader<EnemyTypedEvent>>,
+    power_up_query: Query<&PowerUp>,
+    power_up_settings: Res<PowerUpSettings>,
+    mut active_power_ups: ResMut<ActivePowerUps>,
+    mut number_of_lives_left: ResMut<NumberOfLivesLeft>,
+    enemy_query: Query<(Entity, &Transform, &Children, Option<&CurrentlyBeingTyped>), With<Enemy>>,
+    text_query: Query<&Text>,
+    mut enemies_being_typed: ResMut<EnemiesBeingTyped>,
+) {
+    let typed_enemies: Vec<Entity> = enemy_typed_event_reader
+        .read(&enemy_typed_events)
+        .map(|enemy_typed| enemy_typed.entity)
+        .collect();
+    // Enemies cleared by an earlier power-up in the same frame are not despawned yet
+    let mut cleared_enemies: Vec<EnemyTypedEvent> = Vec::new();
+    for typed_enemy in typed_enemies {
+        let Ok(power_up) = power_up_query.get(typed_enemy) else {
+            continue;
+        };
+        match power_up.effect {
+            PowerUpEffect::Freeze => {
+                active_power_ups.freeze_seconds_left = active_power_ups
+                    .freeze_seconds_left
+                    .max(power_up_settings.freeze_duration);
+            }
+            PowerUpEffect::DoubleScore => {
+                active_power_ups.double_score_seconds_left = active_power_ups
+                    .double_score_seconds_left
+                    .max(power_up_settings.double_score_duration);
+            }
+            PowerUpEffect::ClearEnemy => {
+                // The entity breaks ties, so the same enemy is cleared in every run
+                let enemy_with_fewest_letters_left = enemy_query
+                    .iter()
+                    .filter(|(entity, _, _, _)| {
+                        !cleared_enemies
+                            .iter()
+                            .any(|cleared_enemy| cleared_enemy.entity == *entity)
+                    })
+                    .map(|(entity, transform, children, currently_being_typed)| {
+                        let typed_letters = currently_being_typed
+                            .map_or(0, |currently_being_typed| currently_being_typed.index + 1);
+                        let letters: usize = text_query
+                            .iter_many(children)
+                            .map(|text| text.sections.len())
+                            .sum();
+                        (
+                            letters.saturating_sub(typed_letters),
+                            entity,
+                            transform.translation,
+                            letters,
+                        )
+                    })
+                    .min_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+                if let Some((_, entity, translation, letters)) = enemy_with_fewest_letters_left {
+                    unlive_typed_enemy(&mut commands, entity);
+                    cleared_enemies.push(EnemyTypedEvent {
+                        entity,
+                        translation,
+                        letters,
+                    });
+                    enemies_being_typed.vec_of_enemies.retain(|&x| x != entity);
+                    if enemies_being_typed.vec_of_enemies.is_empty() {
+                        enemies_being_typed.indicator = false;
+                    }
+                }
+                active_power_ups.announcement = Some(power_up.effect);
+                active_power_ups.announcement_seconds_left = ANNOUNCEMENT_DURATION;
+            }
+            PowerUpEffect::RestoreLife => {
+                if number_of_lives_left.number < number_of_lives_left.maximum {
+                    number_of_lives_left.number += 1;
+                }
+                active_power_ups.announcement = Some(power_up.effect);
+                active_power_ups.announcement_seconds_left = ANNOUNCEMENT_DURATION;
+            }
+        }
+    }
+    enemy_typed_events.send_batch(cleared_enemies);
+}
+
+/// Keeps all enemies and bosses on the road frozen while a freeze power-up is active
+pub fn freeze_enemies(
+    mut commands: Commands,
+    active_power_ups: Res<ActivePowerUps>,
+    enemy_query: Query<(Entity, Option<&Frozen>), Or<(With<EnemyType>, With<Boss>)>>,
+) {
+    if active_power_ups.freeze_seconds_left <= 0.0 {
+        return;
+    }
+    for (entity, frozen) in enemy_query.iter() {
+        let frozen_long_enough = frozen.is_some_and(|frozen| {
+            frozen.timer.remaining_secs() >= active_power_ups.freeze_seconds_left
+        });
+        if !frozen_long_enough {
+            commands.entity(entity).insert(Frozen {
+                timer: Timer::from_seconds(active_power_ups.freeze_seconds_left, TimerMode::Once),
+            });
+        }
+    }
+}
+
+/// Counts down the active power-ups
+pub fn tick_active_power_ups(mut active_power_ups: ResMut<ActivePowerUps>, time: Res<Time>) {
+    let delta_seconds = time.delta_seconds();
+    active_power_ups.freeze_seconds_left =
+        (active_power_ups.freeze_seconds_left - delta_seconds).max(0.0);
+    active_power_ups.double_score_seconds_left =
+        (active_power_ups.double_score_seconds_left - delta_seconds).max(0.0);
+    active_power_ups.announcement_seconds_left =
+        (active_power_ups.announcement_seconds_left - delta_seconds).max(0.0);
+}
+
+/// Colors the letters of power-up words that are not being typed in the color of their power-up
+pub fn highlight_power_up_words(
+    power_up_query: Query<(&PowerUp, &Children), With<Enemy>>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (power_up, children) in power_up_query.iter() {
+        let mut text_iter = text_query.iter_many_mut(children);
+        while let Some(mut text) = text_iter.fetch_next() {
+            // Only touch the text if needed in order to not lay it out again every frame
+            if !text
+                .sections
+                .iter()
+                .any(|section| section.style.color == STANDARD_TEXT_COLOR)
+            {
+                continue;
+            }
+            for section in text.sections.iter_mut() {
+                if section.style.color == STANDARD_TEXT_COLOR {
+                    section.style.color = power_up.effect.color();
+                }
+            }
+        }
+    }
+}
+
+/// Draws a pulsing ring around enemies carrying a power-up
+pub fn draw_power_up_auras(
+    mut gizmos: Gizmos,
+    power_up_query: Query<(&PowerUp, &Transform), With<Enemy>>,
+    time: Res<Time>,
+) {
+    let pulse = (time.elapsed_seconds() * POWER_UP_AURA_PULSE_FREQUENCY * std::f32::consts::TAU)
+        .sin()
+        * 0.5
+        + 0.5;
+    for (power_up, transform) in power_up_query.iter() {
+        gizmos.circle_2d(
+            transform.translation.truncate(),
+            POWER_UP_AURA_RADIUS * (0.8 + 0.2 * pulse),
+            power_up.effect.color(),
+        );
+    }
+}
+
+/// Ends all active power-ups at the end of a round
+pub fn reset_active_power_ups(mut active_power_ups: ResMut<ActivePowerUps>) {
+    *active_power_ups = ActivePowerUps::default();
+}
+
+/// Ends all active power-ups on restart
+pub fn reset_active_power_ups_on_restart(
+    mut restart_event_reader: EventReader<Restart>,
+    mut active_power_ups: ResMut<ActivePowerUps>,
+) {
+    for _ in restart_event_reader.read() {
+        *active_power_ups = ActivePowerUps::default();
+    }
+}
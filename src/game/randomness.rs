@@ -0,0 +1,19 @@
+pub mod resources;
+use resources::*;
+
+mod systems;
+use systems::*;
+
+use super::*;
+
+pub struct RandomnessPlugin;
+
+impl Plugin for RandomnessPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Initialize Resources. The rng is needed at startup already (e.g. for the trees)
+            .insert_resource(GameRng::new(parse_seed_from_command_line()))
+            // Add update systems
+            .add_systems(Update, reseed_game_rng_on_restart);
+    }
+}
@@ -0,0 +1,80 @@
+use rand::{rngs::StdRng, SeedableRng};
+
+use super::*;
+
+/// The independent streams of random numbers. Each subsystem draws from its own stream such that
+/// adding a random call in one place does not perturb the random decisions in another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RngStream {
+    SpawnPoints,
+    EnemyTypes,
+    EnemySpeeds,
+    Words,
+    Boss,
+    Castle,
+    Decorations,
+    Effects,
+    Menu,
+    Junctions,
+    PowerUps,
+}
+
+impl RngStream {
+    /// All streams in the order they are seeded in
+    const ALL: [RngStream; 11] = [
+        RngStream::SpawnPoints,
+        RngStream::EnemyTypes,
+        RngStream::EnemySpeeds,
+        RngStream::Words,
+        RngStream::Boss,
+        RngStream::Castle,
+        RngStream::Decorations,
+        RngStream::Effects,
+        RngStream::Menu,
+        RngStream::Junctions,
+        RngStream::PowerUps,
+    ];
+}
+
+/// Resource through which every random decision of the game flows.
+///
+/// All streams are derived from one seed, such that two runs with the same seed are the same.
+/// The seed is fixed if it was given on the command line, otherwise a new one is chosen on restart.
+#[derive(Resource)]
+pub struct GameRng {
+    pub seed: u64,
+    pub fixed_seed: Option<u64>,
+    streams: Vec<StdRng>,
+}
+
+impl GameRng {
+    /// Creates the rng from the given seed or from a random seed if none is given
+    pub fn new(seed: Option<u64>) -> GameRng {
+        let mut game_rng = GameRng {
+            seed: 0,
+            fixed_seed: seed,
+            streams: Vec::new(),
+        };
+        game_rng.reseed(seed.unwrap_or_else(rand::random));
+        game_rng
+    }
+
+    /// Resets all streams to the beginning of the sequence of the given seed
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.streams = RngStream::ALL
+            .iter()
+            .map(|&stream| {
+                // Spread the streams apart with the golden ratio constant used by splitmix64
+                StdRng::seed_from_u64(
+                    seed ^ (stream as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15),
+                )
+            })
+            .collect();
+    }
+
+    /// Returns the given stream of random numbers
+    pub fn stream(&mut self, stream: RngStream) -> &mut StdRng {
+        &mut self.streams[stream as usize]
+    }
+}
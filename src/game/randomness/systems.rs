@@ -0,0 +1,30 @@
+use crate::menu::systems::Restart;
+
+use super::*;
+
+/// Command line argument for setting the seed, e.g. `--seed 42`
+const SEED_COMMAND_LINE_ARGUMENT: &str = "--seed";
+
+/// Returns the seed given on the command line if there is one
+pub fn parse_seed_from_command_line() -> Option<u64> {
+    let mut args = std::env::args().skip_while(|arg| arg != SEED_COMMAND_LINE_ARGUMENT);
+    args.next()?;
+    match args.next().map(|seed| seed.parse::<u64>()) {
+        Some(Ok(seed)) => Some(seed),
+        _ => {
+            warn!("Expected a positive number after {SEED_COMMAND_LINE_ARGUMENT}, using a random seed");
+            None
+        }
+    }
+}
+
+/// Resets the rng on restart. Runs with a fixed seed start over with the same seed, other runs get a new random seed
+pub fn reseed_game_rng_on_restart(
+    mut game_rng: ResMut<GameRng>,
+    mut restart_event_reader: EventReader<Restart>,
+) {
+    for _ in restart_event_reader.read() {
+        let seed = game_rng.fixed_seed.unwrap_or_else(rand::random);
+        game_rng.reseed(seed);
+    }
+}
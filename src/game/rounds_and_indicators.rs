@@ -25,6 +25,12 @@ impl Plugin for RoundsAndIndicatorsPlugin {
             .register_type::<ScoreIndicator>()
             .register_type::<StreakIndicator>()
             .register_type::<DifficultyIndicator>()
+            .register_type::<RunStatistics>()
+            .register_type::<RoundSummary>()
+            .register_type::<GameModeIndicator>()
+            .register_type::<PlayerSettings>()
+            // Add events
+            .add_event::<ScoreGainedEvent>()
             // Initialize Resources
             .init_resource::<MaxNumberOfEnemiesCurrentRound>()
             .init_resource::<NumberOfEnemiesSpawnedThisRound>()
@@ -37,25 +43,34 @@ impl Plugin for RoundsAndIndicatorsPlugin {
             .init_resource::<ScoreIndicator>()
             .init_resource::<StreakIndicator>()
             .init_resource::<DifficultyIndicator>()
+            .init_resource::<RunStatistics>()
+            .init_resource::<RoundSummary>()
+            .init_resource::<GameModeIndicator>()
+            .init_resource::<PlayerSettings>()
             // Add systems that run on entry of round
             .add_systems(
                 OnEnter(RoundState::InRound),
                 (
-                    reset_indicators,
-                    increase_round_difficulty,
+                    // The composition of the round is known once the wave is prepared
+                    reset_indicators.after(super::waves::systems::prepare_wave),
+                    increase_round_difficulty.after(super::waves::systems::prepare_wave),
                     increase_round_counter,
                     reset_round_stopwatch,
                     reset_wpm,
+                    reset_round_summary,
                 ),
             )
+            .add_systems(OnEnter(AppState::InGame), reset_run_statistics)
             // Add update systems
             .add_systems(
                 Update,
-                tick_round_stopwatch.in_set(super::InputHandlingSystemSet::BeforeInputHandling),
+                (tick_round_stopwatch, tick_run_statistics)
+                    .in_set(super::InputHandlingSystemSet::BeforeInputHandling),
             )
             .add_systems(
                 Update,
-                check_if_round_is_over.in_set(super::InputHandlingSystemSet::AfterInputHandling),
+                (check_if_round_is_over, track_best_streak)
+                    .in_set(super::InputHandlingSystemSet::AfterInputHandling),
             )
             .add_systems(
                 Update,
@@ -85,7 +100,11 @@ impl Plugin for RoundsAndIndicatorsPlugin {
             )
             .add_systems(
                 Update,
-                (set_states_on_restart, reset_score_and_indicators_on_restart),
+                (
+                    set_states_on_restart,
+                    reset_score_and_indicators_on_restart,
+                    restore_player_settings_on_restart,
+                ),
             );
     }
 }
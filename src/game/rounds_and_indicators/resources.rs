@@ -145,6 +145,102 @@ impl Default for StreakIndicator {
     }
 }
 
+/// Resource for tracking statistics over the whole run, e.g. for the results of a daily challenge.
+///
+/// Is reset whenever a game is started.
+#[derive(Reflect, Resource, Default)]
+#[reflect(Resource)]
+pub struct RunStatistics {
+    pub words_typed: u32,
+    pub seconds_in_rounds: f64,
+    pub correct_keystrokes: u32,
+    pub wrong_keystrokes: u32,
+}
+
+impl RunStatistics {
+    /// Returns the words per minute over all rounds of the run
+    pub fn wpm(&self) -> f64 {
+        if self.seconds_in_rounds > 0.0 {
+            self.words_typed as f64 / (self.seconds_in_rounds / 60.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the share of correct keystrokes in percent
+    pub fn accuracy(&self) -> f64 {
+        let keystrokes = self.correct_keystrokes + self.wrong_keystrokes;
+        if keystrokes > 0 {
+            self.correct_keystrokes as f64 / keystrokes as f64 * 100.0
+        } else {
+            100.0
+        }
+    }
+}
+
+/// Resource for tracking statistics of the current round, shown in the summary in between rounds.
+///
+/// Is reset at the beginning of each round.
+#[derive(Reflect, Resource, Default)]
+#[reflect(Resource)]
+pub struct RoundSummary {
+    /// Words that ran into the castle or out of the screen
+    pub words_missed: u32,
+    pub lives_lost: u32,
+    pub correct_keystrokes: u32,
+    pub wrong_keystrokes: u32,
+    pub best_streak: u64,
+    pub score: ScoreBreakdown,
+    pub words_at_castle: Vec<String>,
+}
+
+impl RoundSummary {
+    /// Returns the share of correct keystrokes this round in percent
+    pub fn accuracy(&self) -> f64 {
+        let keystrokes = self.correct_keystrokes + self.wrong_keystrokes;
+        if keystrokes > 0 {
+            self.correct_keystrokes as f64 / keystrokes as f64 * 100.0
+        } else {
+            100.0
+        }
+    }
+}
+
+/// The score gained split into the points of the wpm and the bonus points each multiplier added on
+/// top, applied in the order of the score formula
+#[derive(Reflect, Default, Debug, Clone)]
+pub struct ScoreBreakdown {
+    pub total: u64,
+    pub wpm: f64,
+    pub streak: f64,
+    pub round: f64,
+    pub difficulty: f64,
+    pub upgrades: f64,
+    pub power_ups: f64,
+}
+
+impl ScoreBreakdown {
+    /// Adds the points of a typed word given its multipliers
+    pub fn add(&mut self, points: u64, wpm: f64, multipliers: [f64; 5]) {
+        self.total += points;
+        self.wpm += wpm;
+        let mut points_so_far = wpm;
+        for (bonus, multiplier) in [
+            &mut self.streak,
+            &mut self.round,
+            &mut self.difficulty,
+            &mut self.upgrades,
+            &mut self.power_ups,
+        ]
+        .into_iter()
+        .zip(multipliers)
+        {
+            *bonus += points_so_far * (multiplier - 1.0);
+            points_so_far *= multiplier;
+        }
+    }
+}
+
 /// Resource for tracking streaks (typing without mistakes and no enemy hitting the castle)
 #[derive(Reflect, Resource)]
 #[reflect(Resource)]
@@ -162,7 +258,8 @@ impl Default for DifficultyIndicator {
 
 /// Resource for tracking the current difficulty setting which influences the number of enemies
 /// spawned per round / speed / spawn interval and score calculations
-#[derive(Default, Reflect, PartialEq, Eq)]
+#[derive(Default, Reflect, PartialEq, Eq, Debug, Clone, Copy, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Difficulty {
     Easy,
     #[default]
@@ -183,3 +280,85 @@ impl fmt::Display for Difficulty {
         )
     }
 }
+
+/// Resource remembering the difficulty and map the player chose while a game mode with fixed
+/// settings (e.g. the daily challenge) overrides them, such that they are given back on restart
+#[derive(Reflect, Resource, Default)]
+#[reflect(Resource)]
+pub struct PlayerSettings {
+    /// Difficulty and index of the selected map in MAP_PATHS before they were overridden
+    pub saved: Option<(Difficulty, usize)>,
+}
+
+impl PlayerSettings {
+    /// Remembers the given settings unless settings are remembered already
+    pub fn save(&mut self, difficulty: Difficulty, map_index: usize) {
+        self.saved.get_or_insert((difficulty, map_index));
+    }
+}
+
+/// Resource for tracking the game mode that is currently played
+#[derive(Reflect, Resource, Default)]
+#[reflect(Resource)]
+pub struct GameModeIndicator {
+    pub mode: GameMode,
+}
+
+/// The different game modes. In the daily challenge everybody gets the same enemies and words
+/// on the same calendar day. In the campaign handcrafted levels are played
+#[derive(Default, Reflect, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum GameMode {
+    #[default]
+    Endless,
+    Daily,
+    Campaign,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_breakdown_splits_points_by_multiplier_in_order() {
+        let mut breakdown = ScoreBreakdown::default();
+        breakdown.add(30, 10.0, [1.5, 2.0, 1.0, 1.0, 1.0]);
+
+        assert_eq!(breakdown.total, 30);
+        assert_eq!(breakdown.wpm, 10.0);
+        assert_eq!(breakdown.streak, 5.0);
+        assert_eq!(breakdown.round, 15.0);
+        assert_eq!(breakdown.difficulty, 0.0);
+        assert_eq!(breakdown.upgrades, 0.0);
+        assert_eq!(breakdown.power_ups, 0.0);
+    }
+
+    #[test]
+    fn score_breakdown_parts_add_up_to_the_points() {
+        let mut breakdown = ScoreBreakdown::default();
+        breakdown.add(120, 20.0, [1.0, 1.5, 2.0, 1.0, 2.0]);
+        breakdown.add(40, 40.0, [1.0, 1.0, 1.0, 1.0, 1.0]);
+
+        assert_eq!(breakdown.total, 160);
+        assert_eq!(breakdown.wpm, 60.0);
+        assert_eq!(breakdown.round, 10.0);
+        assert_eq!(breakdown.difficulty, 30.0);
+        assert_eq!(breakdown.power_ups, 60.0);
+        let parts = breakdown.wpm
+            + breakdown.streak
+            + breakdown.round
+            + breakdown.difficulty
+            + breakdown.upgrades
+            + breakdown.power_ups;
+        assert_eq!(parts, breakdown.total as f64);
+    }
+
+    #[test]
+    fn round_summary_accuracy_is_perfect_without_keystrokes() {
+        let mut round_summary = RoundSummary::default();
+        assert_eq!(round_summary.accuracy(), 100.0);
+
+        round_summary.correct_keystrokes = 3;
+        round_summary.wrong_keystrokes = 1;
+        assert_eq!(round_summary.accuracy(), 75.0);
+    }
+}
@@ -25,31 +25,33 @@ const ENEMY_SPAWN_INTERVAL_DECREMENT_HARD_DIFFICULTY: f32 = 0.15;
 // Initial interval for spawning enemies
 use enemies::resources::INITIAL_ENEMY_SPAWN_INTERVAL;
 
-use super::boss::systems::BOSS_WORD_COUNT_MULTIPLIER;
+use super::map::resources::SelectedMap;
+use super::power_ups::resources::ActivePowerUps;
+use super::shop::resources::PurchasedUpgrades;
+use super::waves::resources::CurrentWave;
 
-/// Resets the number of enemies spawned, unlived and typed current round
+/// Resets the number of enemies spawned, unlived and typed current round.
+/// The words of the boss count as spawned
 pub fn reset_indicators(
     mut number_of_enemies_spawned_this_round: ResMut<NumberOfEnemiesSpawnedThisRound>,
     mut number_of_enemies_unlived_current_round: ResMut<NumberOfEnemiesUnlivedThisRound>,
     mut number_of_enemies_typed_current_round: ResMut<NumberOfEnemiesTypedThisRound>,
-    round_number: Res<RoundNumber>,
+    current_wave: Res<CurrentWave>,
 ) {
-    number_of_enemies_spawned_this_round.number = if round_number.number % 10 != 0 {
-        0
-    } else {
-        BOSS_WORD_COUNT_MULTIPLIER * round_number.number
-    };
+    number_of_enemies_spawned_this_round.number = current_wave.boss_words;
     number_of_enemies_unlived_current_round.number = 0;
     number_of_enemies_typed_current_round.number = 0;
 }
 
 /// Increases the maximum number of enemies spawned this round and base speed according to constants defined in this file.
+/// Scripted waves set the number of enemies themselves and may set the base speed
 pub fn increase_round_difficulty(
     mut max_number_of_enemies_this_round: ResMut<MaxNumberOfEnemiesCurrentRound>,
     mut enemy_base_speed_this_round: ResMut<EnemyBaseSpeedCurrentRound>,
     mut enemy_spawn_timer: ResMut<EnemySpawnTimer>,
     round_number: Res<RoundNumber>,
     difficulty_indicator: Res<DifficultyIndicator>,
+    current_wave: Res<CurrentWave>,
 ) {
     enemy_spawn_timer.timer = Timer::from_seconds(
         (INITIAL_ENEMY_SPAWN_INTERVAL
@@ -62,8 +64,12 @@ pub fn increase_round_difficulty(
         .max(0.5),
         TimerMode::Repeating,
     );
-    // Every 10 rounds is boss round and no enemies should be spawned
-    max_number_of_enemies_this_round.number = if round_number.number % 10 != 0 {
+    // In boss rounds of the endless mode no other enemies are spawned
+    max_number_of_enemies_this_round.number = if current_wave.scripted {
+        current_wave.spawns.len() as u32 + current_wave.boss_words
+    } else if current_wave.boss {
+        current_wave.boss_words
+    } else {
         INITIAL_MAX_NUMBER_OF_ENEMIES
             + round_number.number
                 * match difficulty_indicator.difficulty {
@@ -71,10 +77,8 @@ pub fn increase_round_difficulty(
                     Difficulty::Medium => NUMBER_OF_ENEMIES_PER_ROUND_INCREMENT_MEDIUM_DIFFICULTY,
                     Difficulty::Hard => NUMBER_OF_ENEMIES_PER_ROUND_INCREMENT_HARD_DIFFICULTY,
                 }
-    } else {
-        BOSS_WORD_COUNT_MULTIPLIER * round_number.number
     };
-    enemy_base_speed_this_round.speed = if round_number.number % 10 != 0 {
+    enemy_base_speed_this_round.speed = current_wave.speed.unwrap_or(if !current_wave.boss {
         INITIAL_ENEMY_SPEED
             + round_number.number as f32
                 * match difficulty_indicator.difficulty {
@@ -84,7 +88,7 @@ pub fn increase_round_difficulty(
                 }
     } else {
         INITIAL_ENEMY_SPEED * 0.5
-    };
+    });
 }
 
 /// Increases the round_counter by one at the start of each round.
@@ -123,6 +127,29 @@ pub fn tick_round_stopwatch(mut round_stopwatch: ResMut<RoundStopwatch>, time: R
     round_stopwatch.stopwatch.tick(time.delta());
 }
 
+/// Adds the time passed in the current round to the statistics of the run
+pub fn tick_run_statistics(mut run_statistics: ResMut<RunStatistics>, time: Res<Time>) {
+    run_statistics.seconds_in_rounds += time.delta_seconds_f64();
+}
+
+/// Resets the summary of the round at the beginning of each round
+pub fn reset_round_summary(mut round_summary: ResMut<RoundSummary>) {
+    *round_summary = RoundSummary::default();
+}
+
+/// Keeps track of the best streak of the round
+pub fn track_best_streak(
+    streak_indicator: Res<StreakIndicator>,
+    mut round_summary: ResMut<RoundSummary>,
+) {
+    round_summary.best_streak = round_summary.best_streak.max(streak_indicator.number);
+}
+
+/// Resets the statistics of the run when a game is started
+pub fn reset_run_statistics(mut run_statistics: ResMut<RunStatistics>) {
+    *run_statistics = RunStatistics::default();
+}
+
 /// Resets the stopwatch for each round tracking the duration of the current round
 pub fn reset_round_stopwatch(mut round_stopwatch: ResMut<RoundStopwatch>) {
     round_stopwatch.stopwatch.reset();
@@ -143,29 +170,52 @@ pub fn reset_wpm(mut wpm: ResMut<WordPerMinuteTypedIndicator>) {
     wpm.wpm = 0.0;
 }
 
+/// Event that is sent whenever the score is increased by typing an enemy. Holds the points gained
+/// and the translation of the typed enemy
+#[derive(Event)]
+pub struct ScoreGainedEvent {
+    pub points: u64,
+    pub translation: Vec3,
+}
+
 /// When an enemy is typed, the score is increased by:
 ///
 /// current wpm * (streak counter / 50 + 1) * (round number / 10 + 1) * difficulty multiplier
 ///
 /// Where the difficulty multiplier is 1 for easy, 2 for medium and 3 for hard and operations are
-/// done as f64 and converted to u64 at the end.
+/// done as f64 and converted to u64 at the end. The score multiplier upgrade and an active double
+/// score power-up multiply the result.
 pub fn update_score(
     mut score: ResMut<ScoreIndicator>,
     mut enemy_typed_event: EventReader<EnemyTypedEvent>,
+    mut score_gained_event: EventWriter<ScoreGainedEvent>,
     wpm: Res<WordPerMinuteTypedIndicator>,
     streak_counter: Res<StreakIndicator>,
     round_number: Res<RoundNumber>,
     difficulty: Res<DifficultyIndicator>,
+    purchased_upgrades: Res<PurchasedUpgrades>,
+    active_power_ups: Res<ActivePowerUps>,
+    mut round_summary: ResMut<RoundSummary>,
 ) {
-    for _ in enemy_typed_event.read() {
-        score.score += (match difficulty.difficulty {
-            Difficulty::Easy => 1,
-            Difficulty::Medium => 2,
-            Difficulty::Hard => 3,
-        } as f64
-            * wpm.wpm
-            * (streak_counter.number as f64 / 50.0 + 1.0)
-            * (round_number.number as f64 / 10.0 + 1.0)) as u64;
+    for enemy_typed in enemy_typed_event.read() {
+        let multipliers = [
+            streak_counter.number as f64 / 50.0 + 1.0,
+            round_number.number as f64 / 10.0 + 1.0,
+            match difficulty.difficulty {
+                Difficulty::Easy => 1.0,
+                Difficulty::Medium => 2.0,
+                Difficulty::Hard => 3.0,
+            },
+            purchased_upgrades.score_multiplier(),
+            active_power_ups.score_multiplier(),
+        ];
+        let points = (wpm.wpm * multipliers.iter().product::<f64>()) as u64;
+        score.score += points;
+        round_summary.score.add(points, wpm.wpm, multipliers);
+        score_gained_event.send(ScoreGainedEvent {
+            points,
+            translation: enemy_typed.translation,
+        });
     }
 }
 
@@ -174,10 +224,12 @@ pub fn update_number_of_enemies_typed(
     mut enemy_typed_event: EventReader<EnemyTypedEvent>,
     mut number_of_enemies_unlived_current_round: ResMut<NumberOfEnemiesUnlivedThisRound>,
     mut number_of_enemies_typed_current_round: ResMut<NumberOfEnemiesTypedThisRound>,
+    mut run_statistics: ResMut<RunStatistics>,
 ) {
     for _ in enemy_typed_event.read() {
         number_of_enemies_unlived_current_round.number += 1;
         number_of_enemies_typed_current_round.number += 1;
+        run_statistics.words_typed += 1;
     }
 }
 
@@ -199,6 +251,21 @@ pub fn set_states_on_restart(
     }
 }
 
+/// Gives back the difficulty and map the player chose once a game mode with fixed settings is left
+pub fn restore_player_settings_on_restart(
+    mut restart_event_reader: EventReader<Restart>,
+    mut player_settings: ResMut<PlayerSettings>,
+    mut difficulty: ResMut<DifficultyIndicator>,
+    mut selected_map: ResMut<SelectedMap>,
+) {
+    for _ in restart_event_reader.read() {
+        if let Some((saved_difficulty, saved_map_index)) = player_settings.saved.take() {
+            difficulty.difficulty = saved_difficulty;
+            selected_map.index = saved_map_index;
+        }
+    }
+}
+
 /// Sets the score and indicators of the game accordingly on a restart event
 pub fn reset_score_and_indicators_on_restart(
     mut restart_event_reader: EventReader<Restart>,
@@ -207,8 +274,10 @@ pub fn reset_score_and_indicators_on_restart(
     mut round_number: ResMut<RoundNumber>,
     mut score: ResMut<ScoreIndicator>,
     mut streak: ResMut<StreakIndicator>,
+    mut game_mode: ResMut<GameModeIndicator>,
 ) {
     for _ in restart_event_reader.read() {
+        game_mode.mode = GameMode::Endless;
         round_number.number = 0;
         score.score = 0;
         streak.number = 0;
@@ -0,0 +1,64 @@
+pub mod components;
+use components::*;
+
+pub mod resources;
+use resources::*;
+
+mod systems;
+use systems::*;
+
+use crate::menu::{systems::despawn_entities_with_specific_component, GameStartedState, MenuState};
+
+use super::*;
+
+pub struct ShopPlugin;
+
+impl Plugin for ShopPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Register types for debug
+            .register_type::<Coins>()
+            .register_type::<PurchasedUpgrades>()
+            .register_type::<ShopUiElement>()
+            .register_type::<ShopButton>()
+            .register_type::<ShopCoinsText>()
+            // Initialize Resources
+            .init_resource::<Coins>()
+            .init_resource::<PurchasedUpgrades>()
+            // Add systems for entering and leaving the time in between rounds
+            .add_systems(
+                OnEnter(RoundState::InBetweenRounds),
+                spawn_shop.run_if(
+                    in_state(GameStartedState::GameHasStarted)
+                        .and_then(in_state(MenuState::NotInTheMenu)),
+                ),
+            )
+            .add_systems(
+                OnExit(RoundState::InBetweenRounds),
+                despawn_entities_with_specific_component::<ShopUiElement>,
+            )
+            // Apply the upgrades once the round difficulty is set and before the boss is spawned with it
+            .add_systems(
+                OnEnter(RoundState::InRound),
+                apply_upgrades_to_round
+                    .after(rounds_and_indicators::systems::increase_round_difficulty)
+                    .after(enemies::spawn_warning::systems::update_spawn_warning_settings)
+                    .before(boss::systems::spawn_boss),
+            )
+            // Add update systems
+            .add_systems(
+                Update,
+                award_coins_for_typed_words
+                    .after(InputHandlingSystemSet::AfterInputHandling)
+                    .run_if(in_state(AppState::InGame).and_then(in_state(MenuState::NotInTheMenu))),
+            )
+            .add_systems(
+                Update,
+                (buy_upgrades, update_shop_texts).chain().run_if(
+                    in_state(RoundState::InBetweenRounds)
+                        .and_then(in_state(MenuState::NotInTheMenu)),
+                ),
+            )
+            .add_systems(Update, (shop_button_animations, reset_shop_on_restart));
+    }
+}
@@ -0,0 +1,18 @@
+use super::*;
+
+/// Component used to tag entities of the shop shown in between rounds
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct ShopUiElement;
+
+/// Component of the buttons in the shop holding the upgrade they buy
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct ShopButton {
+    pub upgrade: Upgrade,
+}
+
+/// Component used to tag the text in the shop that displays the coins
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct ShopCoinsText;
@@ -0,0 +1,153 @@
+use std::fmt;
+
+use super::*;
+
+/// Slowdown of enemies per level of the slower enemies upgrade
+const ENEMY_SLOWDOWN_PER_LEVEL: f32 = 0.06;
+/// Additional seconds of spawn warnings per level of the longer warnings upgrade
+const WARNING_BONUS_PER_LEVEL: f32 = 0.5;
+/// Additional score multiplier per level of the score multiplier upgrade
+const SCORE_BONUS_PER_LEVEL: f64 = 0.25;
+
+/// Resource for the coins the player earned by typing words in the current run
+#[derive(Reflect, Resource, Default)]
+#[reflect(Resource)]
+pub struct Coins {
+    pub amount: u32,
+}
+
+/// The upgrades that can be bought in the shop in between rounds
+#[derive(Reflect, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Upgrade {
+    #[default]
+    CastleRepair,
+    ExtraLife,
+    SlowerEnemies,
+    LongerWarnings,
+    ScoreMultiplier,
+}
+
+impl Upgrade {
+    /// All upgrades in the order they are listed in the shop
+    pub const ALL: [Upgrade; 5] = [
+        Upgrade::CastleRepair,
+        Upgrade::ExtraLife,
+        Upgrade::SlowerEnemies,
+        Upgrade::LongerWarnings,
+        Upgrade::ScoreMultiplier,
+    ];
+
+    /// Returns a short description of what the upgrade does
+    pub fn description(&self) -> &'static str {
+        match self {
+            Upgrade::CastleRepair => "restores one life",
+            Upgrade::ExtraLife => "one more life for the castle",
+            Upgrade::SlowerEnemies => "enemies walk 6% slower",
+            Upgrade::LongerWarnings => "spawns are announced 0.5s earlier",
+            Upgrade::ScoreMultiplier => "+25% score per word",
+        }
+    }
+
+    /// Returns how often the upgrade can be bought. Repairs can be bought as long as lives are missing
+    pub fn max_level(&self) -> Option<u32> {
+        match self {
+            Upgrade::CastleRepair => None,
+            Upgrade::ExtraLife => Some(3),
+            Upgrade::SlowerEnemies => Some(5),
+            Upgrade::LongerWarnings => Some(3),
+            Upgrade::ScoreMultiplier => Some(4),
+        }
+    }
+
+    /// Returns the cost of the first level of the upgrade. Every further level costs this much more
+    fn base_cost(&self) -> u32 {
+        match self {
+            Upgrade::CastleRepair => 40,
+            Upgrade::ExtraLife => 120,
+            Upgrade::SlowerEnemies => 80,
+            Upgrade::LongerWarnings => 60,
+            Upgrade::ScoreMultiplier => 100,
+        }
+    }
+}
+
+impl fmt::Display for Upgrade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Upgrade::CastleRepair => "Castle repair",
+                Upgrade::ExtraLife => "Extra life",
+                Upgrade::SlowerEnemies => "Slower enemies",
+                Upgrade::LongerWarnings => "Longer warnings",
+                Upgrade::ScoreMultiplier => "Score multiplier",
+            }
+        )
+    }
+}
+
+/// Resource for the upgrades bought in the current run. Is reset on restart
+#[derive(Reflect, Resource, Default)]
+#[reflect(Resource)]
+pub struct PurchasedUpgrades {
+    pub castle_repairs: u32,
+    pub extra_lives: u32,
+    pub slower_enemies: u32,
+    pub longer_warnings: u32,
+    pub score_multiplier: u32,
+}
+
+impl PurchasedUpgrades {
+    /// Returns how often the upgrade was bought
+    pub fn level(&self, upgrade: Upgrade) -> u32 {
+        match upgrade {
+            Upgrade::CastleRepair => self.castle_repairs,
+            Upgrade::ExtraLife => self.extra_lives,
+            Upgrade::SlowerEnemies => self.slower_enemies,
+            Upgrade::LongerWarnings => self.longer_warnings,
+            Upgrade::ScoreMultiplier => self.score_multiplier,
+        }
+    }
+
+    /// Increases the level of the upgrade by one
+    pub fn increase_level(&mut self, upgrade: Upgrade) {
+        match upgrade {
+            Upgrade::CastleRepair => self.castle_repairs += 1,
+            Upgrade::ExtraLife => self.extra_lives += 1,
+            Upgrade::SlowerEnemies => self.slower_enemies += 1,
+            Upgrade::LongerWarnings => self.longer_warnings += 1,
+            Upgrade::ScoreMultiplier => self.score_multiplier += 1,
+        }
+    }
+
+    /// Returns whether the upgrade was bought as often as possible
+    pub fn is_maxed(&self, upgrade: Upgrade) -> bool {
+        upgrade
+            .max_level()
+            .is_some_and(|max_level| self.level(upgrade) >= max_level)
+    }
+
+    /// Returns the cost of the next level of the upgrade. Repairs always cost the same
+    pub fn cost(&self, upgrade: Upgrade) -> u32 {
+        match upgrade {
+            Upgrade::CastleRepair => upgrade.base_cost(),
+            _ => upgrade.base_cost() * (self.level(upgrade) + 1),
+        }
+    }
+
+    /// Returns the factor the speed of enemies is multiplied with
+    pub fn enemy_speed_factor(&self) -> f32 {
+        1.0 - ENEMY_SLOWDOWN_PER_LEVEL * self.slower_enemies as f32
+    }
+
+    /// Returns the seconds added to the duration of spawn warnings
+    pub fn warning_bonus(&self) -> f32 {
+        WARNING_BONUS_PER_LEVEL * self.longer_warnings as f32
+    }
+
+    /// Returns the factor the score of each word is multiplied with
+    pub fn score_multiplier(&self) -> f64 {
+        1.0 + SCORE_BONUS_PER_LEVEL * self.score_multiplier as f64
+    }
+}
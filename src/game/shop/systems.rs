@@ -0,0 +1,245 @@
+use super::castle::resources::NumberOfLivesLeft;
+use super::enemies::spawn_warning::resources::SpawnWarningSettings;
+use super::enemies::text::systems::EnemyTypedEvent;
+use super::rounds_and_indicators::resources::EnemyBaseSpeedCurrentRound;
+use crate::menu::systems::Restart;
+
+use super::*;
+
+/// Coins awarded per letter of a typed word
+const COINS_PER_LETTER: u32 = 1;
+/// Font size of the texts in the shop
+const SHOP_FONT_SIZE: f32 = 28.0;
+/// Background color of the shop buttons
+const SHOP_BUTTON_COLOR: Color = Color::rgba(0.1, 0.1, 0.1, 0.75);
+/// Background color of the shop buttons while hovered
+const SHOP_BUTTON_HOVERED_COLOR: Color = Color::rgba(0.3, 0.3, 0.3, 0.85);
+/// Text color of upgrades that can be bought
+const AFFORDABLE_UPGRADE_COLOR: Color = Color::WHITE;
+/// Text color of upgrades that are too expensive or can't be bought anymore
+const UNAVAILABLE_UPGRADE_COLOR: Color = Color::GRAY;
+/// Keys for buying the upgrades, in the order of [`Upgrade::ALL`]
+const UPGRADE_KEYS: [KeyCode; 5] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+];
+
+/// Awards coins for every typed word depending on its length
+pub fn award_coins_for_typed_words(
+    mut enemy_typed_event: EventReader<EnemyTypedEvent>,
+    mut coins: ResMut<Coins>,
+) {
+    for enemy_typed in enemy_typed_event.read() {
+        coins.amount += enemy_typed.letters as u32 * COINS_PER_LETTER;
+    }
+}
+
+/// Spawns the shop at the bottom of the screen with one button per upgrade
+pub fn spawn_shop(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    align_self: AlignSelf::FlexEnd,
+                    justify_self: JustifySelf::Center,
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Stretch,
+                    row_gap: Val::Px(6.0),
+                    margin: UiRect::bottom(Val::Percent(4.0)),
+                    ..default()
+                },
+                ..default()
+            },
+            ShopUiElement,
+            Name::new("Shop"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: SHOP_FONT_SIZE * 1.25,
+                        color: Color::BLACK,
+                        ..default()
+                    },
+                )
+                .with_text_justify(JustifyText::Center),
+                ShopCoinsText,
+            ));
+            for upgrade in Upgrade::ALL {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                padding: UiRect::axes(Val::Px(16.0), Val::Px(6.0)),
+                                ..default()
+                            },
+                            background_color: SHOP_BUTTON_COLOR.into(),
+                            ..default()
+                        },
+                        ShopButton { upgrade },
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font_size: SHOP_FONT_SIZE,
+                                color: AFFORDABLE_UPGRADE_COLOR,
+                                ..default()
+                            },
+                        ));
+                    });
+            }
+        });
+}
+
+/// Buys upgrades when their button is clicked or their number key is pressed
+pub fn buy_upgrades(
+    interaction_query: Query<(&Interaction, &ShopButton), Changed<Interaction>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut coins: ResMut<Coins>,
+    mut purchased_upgrades: ResMut<PurchasedUpgrades>,
+    mut number_of_lives_left: ResMut<NumberOfLivesLeft>,
+) {
+    let clicked_upgrades = interaction_query
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+        .map(|(_, shop_button)| shop_button.upgrade);
+    let pressed_upgrades = Upgrade::ALL
+        .into_iter()
+        .zip(UPGRADE_KEYS)
+        .filter(|(_, key)| keyboard_input.just_pressed(*key))
+        .map(|(upgrade, _)| upgrade);
+
+    for upgrade in clicked_upgrades.chain(pressed_upgrades) {
+        if !can_buy(upgrade, &coins, &purchased_upgrades, &number_of_lives_left) {
+            continue;
+        }
+        coins.amount -= purchased_upgrades.cost(upgrade);
+        purchased_upgrades.increase_level(upgrade);
+        // Lives change right away, the other upgrades are applied at the start of each round
+        match upgrade {
+            Upgrade::CastleRepair => number_of_lives_left.number += 1,
+            Upgrade::ExtraLife => {
+                number_of_lives_left.maximum += 1;
+                number_of_lives_left.number += 1;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns whether the upgrade can be bought right now
+fn can_buy(
+    upgrade: Upgrade,
+    coins: &Coins,
+    purchased_upgrades: &PurchasedUpgrades,
+    number_of_lives_left: &NumberOfLivesLeft,
+) -> bool {
+    let repair_needed = number_of_lives_left.number < number_of_lives_left.maximum;
+    coins.amount >= purchased_upgrades.cost(upgrade)
+        && !purchased_upgrades.is_maxed(upgrade)
+        && (upgrade != Upgrade::CastleRepair || repair_needed)
+}
+
+/// Updates the texts of the shop whenever coins, upgrades or lives change
+pub fn update_shop_texts(
+    coins: Res<Coins>,
+    purchased_upgrades: Res<PurchasedUpgrades>,
+    number_of_lives_left: Res<NumberOfLivesLeft>,
+    mut coins_text_query: Query<&mut Text, With<ShopCoinsText>>,
+    shop_button_query: Query<(&ShopButton, &Children)>,
+    added_shop_button_query: Query<(), Added<ShopButton>>,
+    mut text_query: Query<&mut Text, Without<ShopCoinsText>>,
+) {
+    if !(coins.is_changed()
+        || purchased_upgrades.is_changed()
+        || number_of_lives_left.is_changed()
+        || !added_shop_button_query.is_empty())
+    {
+        return;
+    }
+    for mut text in coins_text_query.iter_mut() {
+        text.sections[0].value = format!("Shop - {} coins", coins.amount);
+    }
+    for (shop_button, children) in shop_button_query.iter() {
+        let upgrade = shop_button.upgrade;
+        let key_number = Upgrade::ALL
+            .iter()
+            .position(|other| *other == upgrade)
+            .unwrap_or_default()
+            + 1;
+        let level = match upgrade.max_level() {
+            Some(max_level) => format!(" ({}/{})", purchased_upgrades.level(upgrade), max_level),
+            None => String::new(),
+        };
+        let price = if purchased_upgrades.is_maxed(upgrade) {
+            "sold out".to_string()
+        } else {
+            format!("{} coins", purchased_upgrades.cost(upgrade))
+        };
+        let color = if can_buy(upgrade, &coins, &purchased_upgrades, &number_of_lives_left) {
+            AFFORDABLE_UPGRADE_COLOR
+        } else {
+            UNAVAILABLE_UPGRADE_COLOR
+        };
+        let mut text_iter = text_query.iter_many_mut(children);
+        while let Some(mut text) = text_iter.fetch_next() {
+            text.sections[0].value = format!(
+                "[{}] {}{}: {} - {}",
+                key_number,
+                upgrade,
+                level,
+                upgrade.description(),
+                price
+            );
+            text.sections[0].style.color = color;
+        }
+    }
+}
+
+/// Handles the background color of the shop buttons based on mouse interaction
+pub fn shop_button_animations(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<ShopButton>),
+    >,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        *color = match *interaction {
+            Interaction::None => SHOP_BUTTON_COLOR.into(),
+            _ => SHOP_BUTTON_HOVERED_COLOR.into(),
+        };
+    }
+}
+
+/// Applies the bought upgrades to the enemies and spawn warnings of the round that just started
+pub fn apply_upgrades_to_round(
+    purchased_upgrades: Res<PurchasedUpgrades>,
+    mut enemy_base_speed_this_round: ResMut<EnemyBaseSpeedCurrentRound>,
+    mut spawn_warning_settings: ResMut<SpawnWarningSettings>,
+) {
+    enemy_base_speed_this_round.speed *= purchased_upgrades.enemy_speed_factor();
+    spawn_warning_settings.duration += purchased_upgrades.warning_bonus();
+}
+
+/// Resets coins and upgrades and removes the shop on restart
+pub fn reset_shop_on_restart(
+    mut commands: Commands,
+    mut restart_event_reader: EventReader<Restart>,
+    mut coins: ResMut<Coins>,
+    mut purchased_upgrades: ResMut<PurchasedUpgrades>,
+    shop_query: Query<Entity, With<ShopUiElement>>,
+) {
+    for _ in restart_event_reader.read() {
+        *coins = Coins::default();
+        *purchased_upgrades = PurchasedUpgrades::default();
+        for entity in shop_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
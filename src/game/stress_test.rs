@@ -0,0 +1,34 @@
+pub mod systems;
+use systems::*;
+
+use bevy::diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin};
+
+use super::*;
+
+pub struct StressTestPlugin;
+
+impl Plugin for StressTestPlugin {
+    fn build(&self, app: &mut App) {
+        // The stress test only runs if it was requested on the command line
+        if !stress_test_requested_on_command_line() {
+            return;
+        }
+        app
+            // Log the frame time every second
+            .add_plugins((FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin::default()))
+            // Add systems for entering a round. The stress test enemies count into the prepared round
+            .add_systems(
+                OnEnter(RoundState::InRound),
+                spawn_stress_test_enemies
+                    .after(rounds_and_indicators::systems::reset_indicators)
+                    .after(adaptive_difficulty::systems::apply_adaptive_difficulty),
+            )
+            // Add update systems
+            .add_systems(
+                Update,
+                compare_spatial_index_with_brute_force
+                    .after(InputHandlingSystemSet::InputHandling)
+                    .run_if(in_state(RoundState::InRound)),
+            );
+    }
+}
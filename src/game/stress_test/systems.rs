@@ -0,0 +1,156 @@
+use bevy::utils::Instant;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::enemies::components::{Enemy, EnemyType, WalkingAnimation};
+use super::enemies::movement::components::{EnemySpawnPoint, PathProgress};
+use super::enemies::resources::WordsHandle;
+use super::enemies::spatial_index::resources::EnemySpatialIndex;
+use super::enemies::systems::{
+    generate_sprite_information_from_enemy_type, spawn_enemy, Words, BASE_ANIMATION_SPEED,
+    ENEMY_SPRITE_SCALE_FACTOR,
+};
+use super::map::resources::LoadedMap;
+use super::rounds_and_indicators::resources::{
+    MaxNumberOfEnemiesCurrentRound, NumberOfEnemiesSpawnedThisRound,
+};
+use super::*;
+
+/// Command line argument that starts the stress test
+const STRESS_TEST_COMMAND_LINE_ARGUMENT: &str = "--stress-test";
+/// Number of enemies spawned at the start of every round during the stress test
+const STRESS_TEST_ENEMY_COUNT: usize = 500;
+/// Speed of the enemies spawned by the stress test. Slow so they stay on screen for a while
+const STRESS_TEST_ENEMY_SPEED: f32 = 15.0;
+/// Enemies are spread over this fraction of the length of their route
+const STRESS_TEST_ROUTE_FRACTION: f32 = 0.6;
+/// Seed for placing the stress test enemies, independent of the game's seeded streams
+const STRESS_TEST_SEED: u64 = 0x5745_5353;
+/// Size of the area around each enemy that is searched for neighbours, roughly the size of a label
+const STRESS_TEST_NEIGHBOURHOOD: Vec2 = Vec2::new(240.0, 50.0);
+/// Seconds between two comparisons of the spatial index with a brute force search
+const STRESS_TEST_REPORT_INTERVAL: f32 = 2.0;
+
+/// Returns whether the stress test was requested on the command line
+pub fn stress_test_requested_on_command_line() -> bool {
+    std::env::args().any(|arg| arg == STRESS_TEST_COMMAND_LINE_ARGUMENT)
+}
+
+/// Spawns a crowd of slow enemies spread along all spawn routes of the map. They count into the
+/// enemies of the round, so the round still ends once all of them are gone
+pub fn spawn_stress_test_enemies(
+    mut commands: Commands,
+    mut max_number_of_enemies_this_round: ResMut<MaxNumberOfEnemiesCurrentRound>,
+    mut number_of_enemies_spawned_this_round: ResMut<NumberOfEnemiesSpawnedThisRound>,
+    loaded_map: Res<LoadedMap>,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
+    words_handle: Res<WordsHandle>,
+    words: Res<Assets<Words>>,
+) {
+    let Some(words) = words.get(words_handle.0.id()) else {
+        return;
+    };
+    if loaded_map.spawn_routes.is_empty() {
+        return;
+    }
+    let mut rng = StdRng::seed_from_u64(STRESS_TEST_SEED);
+
+    let enemy_type = EnemyType::default();
+    let (enemy_name, sprite_width, sprite_height, animation_length) =
+        generate_sprite_information_from_enemy_type(&enemy_type);
+    let texture_handle: Handle<Image> =
+        asset_server.load(format!("sprites/enemies/{}.png", enemy_name));
+    let texture_atlas_handle = texture_atlases.add(TextureAtlasLayout::from_grid(
+        Vec2::new(sprite_width, sprite_height),
+        animation_length,
+        1,
+        None,
+        None,
+    ));
+
+    for index in 0..STRESS_TEST_ENEMY_COUNT {
+        let spawn_point = EnemySpawnPoint {
+            route: loaded_map.spawn_routes[index % loaded_map.spawn_routes.len()],
+        };
+        let route = loaded_map.route(&spawn_point);
+        let distance = rng.gen::<f32>() * STRESS_TEST_ROUTE_FRACTION * route.length();
+        let word_for_enemy = &words.vec_of_words[rng.gen_range(0..words.vec_of_words.len())];
+        spawn_enemy(
+            &mut commands,
+            Transform::from_translation(route.position_at(distance).extend(0.0)),
+            route.starts_on_the_left(),
+            Some(ENEMY_SPRITE_SCALE_FACTOR * Vec2::new(sprite_width, sprite_height)),
+            texture_atlas_handle.clone(),
+            texture_handle.clone(),
+            spawn_point,
+            PathProgress {
+                distance,
+                branch_seed: rng.gen(),
+                ..default()
+            },
+            STRESS_TEST_ENEMY_SPEED,
+            WalkingAnimation {
+                length_of_animation: animation_length,
+                animation_timer: Timer::from_seconds(
+                    BASE_ANIMATION_SPEED / STRESS_TEST_ENEMY_SPEED,
+                    TimerMode::Repeating,
+                ),
+            },
+            enemy_type,
+            word_for_enemy,
+        );
+    }
+    max_number_of_enemies_this_round.number += STRESS_TEST_ENEMY_COUNT as u32;
+    number_of_enemies_spawned_this_round.number += STRESS_TEST_ENEMY_COUNT as u32;
+    info!("Stress test: spawned {} enemies", STRESS_TEST_ENEMY_COUNT);
+}
+
+/// Periodically looks up the neighbours of every enemy once by comparing all pairs and once
+/// with the spatial index and logs how long both took
+pub fn compare_spatial_index_with_brute_force(
+    time: Res<Time>,
+    mut time_since_last_report: Local<f32>,
+    enemy_query: Query<&Transform, With<Enemy>>,
+    enemy_spatial_index: Res<EnemySpatialIndex>,
+) {
+    *time_since_last_report += time.delta_seconds();
+    if *time_since_last_report < STRESS_TEST_REPORT_INTERVAL {
+        return;
+    }
+    *time_since_last_report = 0.0;
+
+    let neighbourhoods: Vec<Rect> = enemy_query
+        .iter()
+        .map(|transform| {
+            Rect::from_center_size(transform.translation.truncate(), STRESS_TEST_NEIGHBOURHOOD)
+        })
+        .collect();
+
+    let brute_force_start = Instant::now();
+    let brute_force_neighbours: usize = neighbourhoods
+        .iter()
+        .map(|neighbourhood| {
+            enemy_query
+                .iter()
+                .filter(|transform| neighbourhood.contains(transform.translation.truncate()))
+                .count()
+        })
+        .sum();
+    let brute_force_duration = brute_force_start.elapsed();
+
+    let spatial_index_start = Instant::now();
+    let spatial_index_neighbours: usize = neighbourhoods
+        .iter()
+        .map(|neighbourhood| enemy_spatial_index.grid.query(*neighbourhood).count())
+        .sum();
+    let spatial_index_duration = spatial_index_start.elapsed();
+
+    info!(
+        "Stress test: {} enemies, neighbour lookup brute force {:?} ({} hits), spatial index {:?} ({} hits)",
+        neighbourhoods.len(),
+        brute_force_duration,
+        brute_force_neighbours,
+        spatial_index_duration,
+        spatial_index_neighbours,
+    );
+}
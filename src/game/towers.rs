@@ -0,0 +1,76 @@
+pub mod components;
+use components::*;
+
+pub mod resources;
+use resources::*;
+
+mod systems;
+use systems::*;
+
+use crate::menu::{systems::despawn_entities_with_specific_component, GameStartedState, MenuState};
+
+use super::*;
+
+pub struct TowersPlugin;
+
+impl Plugin for TowersPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Register types for debug
+            .register_type::<BuildSpots>()
+            .register_type::<Tower>()
+            .register_type::<TowerShot>()
+            .register_type::<TowerUiElement>()
+            .register_type::<TowerButton>()
+            .register_type::<TowerPanelText>()
+            // Initialize Resources
+            .init_resource::<BuildSpots>()
+            // Determine the build spots whenever another map is loaded
+            .add_systems(
+                Update,
+                update_build_spots
+                    .after(map::systems::update_loaded_map)
+                    .run_if(resource_changed::<map::resources::LoadedMap>),
+            )
+            // Add systems for entering and leaving the time in between rounds
+            .add_systems(
+                OnEnter(RoundState::InBetweenRounds),
+                spawn_tower_panel.run_if(
+                    in_state(GameStartedState::GameHasStarted)
+                        .and_then(in_state(MenuState::NotInTheMenu)),
+                ),
+            )
+            .add_systems(
+                OnExit(RoundState::InBetweenRounds),
+                despawn_entities_with_specific_component::<TowerUiElement>,
+            )
+            // Add update systems
+            .add_systems(
+                Update,
+                (charge_towers, fire_charged_towers)
+                    .chain()
+                    .in_set(InputHandlingSystemSet::AfterInputHandling),
+            )
+            .add_systems(
+                Update,
+                (
+                    select_build_spot_on_click,
+                    build_towers,
+                    update_tower_panel_texts,
+                    draw_build_spots,
+                )
+                    .chain()
+                    .run_if(
+                        in_state(RoundState::InBetweenRounds)
+                            .and_then(in_state(GameStartedState::GameHasStarted))
+                            .and_then(in_state(MenuState::NotInTheMenu)),
+                    ),
+            )
+            .add_systems(
+                Update,
+                (hide_trees_under_towers, fade_tower_shots, draw_towers)
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(Update, (tower_button_animations, despawn_towers_on_restart));
+    }
+}
@@ -0,0 +1,38 @@
+use super::*;
+
+/// Component used to track a tower, the build spot it stands on and how far it is charged
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct Tower {
+    pub kind: TowerKind,
+    pub build_spot: usize,
+    /// Number of correctly typed letters since the tower fired last
+    pub charge: u32,
+}
+
+/// Component of the shot drawn from a tower to the enemy it fired at. Fades out with its timer
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct TowerShot {
+    pub from: Vec2,
+    pub to: Vec2,
+    pub kind: TowerKind,
+    pub timer: Timer,
+}
+
+/// Component used to tag entities of the tower panel shown in between rounds
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct TowerUiElement;
+
+/// Component of the buttons in the tower panel holding the tower they build
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct TowerButton {
+    pub kind: TowerKind,
+}
+
+/// Component used to tag the headline of the tower panel
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct TowerPanelText;
@@ -0,0 +1,85 @@
+use std::fmt;
+
+use super::*;
+
+/// The towers that can be built on build spots in between rounds
+#[derive(Reflect, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TowerKind {
+    #[default]
+    Frost,
+    Scribe,
+    Gust,
+}
+
+impl TowerKind {
+    /// All towers in the order they are listed in the tower panel
+    pub const ALL: [TowerKind; 3] = [TowerKind::Frost, TowerKind::Scribe, TowerKind::Gust];
+
+    /// Returns a short description of what the tower does when it fires
+    pub fn description(&self) -> &'static str {
+        match self {
+            TowerKind::Frost => "halves the speed of an enemy for 3s",
+            TowerKind::Scribe => "types a letter of a word for you",
+            TowerKind::Gust => "knocks an enemy back a checkpoint",
+        }
+    }
+
+    /// Returns the cost of building the tower
+    pub fn cost(&self) -> u32 {
+        match self {
+            TowerKind::Frost => 100,
+            TowerKind::Scribe => 120,
+            TowerKind::Gust => 150,
+        }
+    }
+
+    /// Returns the number of correctly typed letters needed to fully charge the tower
+    pub fn letters_to_charge(&self) -> u32 {
+        match self {
+            TowerKind::Frost => 12,
+            TowerKind::Scribe => 8,
+            TowerKind::Gust => 20,
+        }
+    }
+
+    /// Returns the distance up to which the tower fires at enemies
+    pub fn range(&self) -> f32 {
+        match self {
+            TowerKind::Frost => 300.0,
+            TowerKind::Scribe => 300.0,
+            TowerKind::Gust => 250.0,
+        }
+    }
+
+    /// Returns the color the tower and its shots are tinted with
+    pub fn color(&self) -> Color {
+        match self {
+            TowerKind::Frost => Color::CYAN,
+            TowerKind::Scribe => Color::GOLD,
+            TowerKind::Gust => Color::LIME_GREEN,
+        }
+    }
+}
+
+impl fmt::Display for TowerKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                TowerKind::Frost => "Frost tower",
+                TowerKind::Scribe => "Scribe tower",
+                TowerKind::Gust => "Gust tower",
+            }
+        )
+    }
+}
+
+/// Resource for the positions towers can be built on and the one that is selected for building.
+/// Build spots are the trees of the loaded map that are close to a road
+#[derive(Reflect, Resource, Default)]
+#[reflect(Resource)]
+pub struct BuildSpots {
+    pub positions: Vec<Vec2>,
+    pub selected: Option<usize>,
+}
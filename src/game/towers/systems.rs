@@ -0,0 +1,576 @@
+use bevy::window::PrimaryWindow;
+
+use super::boss::components::BossWord;
+use super::decorations::components::Tree;
+use super::enemies::components::{CurrentlyBeingTyped, Enemy};
+use super::enemies::movement::components::{EnemySpawnPoint, PathProgress, Slowed};
+use super::enemies::spatial_index::resources::EnemySpatialIndex;
+use super::enemies::systems::TYPING_COLOR;
+use super::enemies::text::systems::LetterTypedEvent;
+use super::map::resources::LoadedMap;
+use super::shop::resources::Coins;
+use crate::editor::systems::cursor_world_position;
+use crate::menu::systems::Restart;
+
+use super::*;
+
+/// Trees further away from every road than this are no build spots
+const BUILD_SPOT_MAX_ROAD_DISTANCE: f32 = 100.0;
+/// Distance to a build spot up to which a click selects it
+const BUILD_SPOT_PICK_RADIUS: f32 = 60.0;
+/// Radius of the circles marking the build spots in between rounds
+const BUILD_SPOT_MARKER_RADIUS: f32 = 40.0;
+/// Color of the circles marking free build spots
+const BUILD_SPOT_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.6);
+/// Color of the circle marking the selected build spot
+const SELECTED_BUILD_SPOT_COLOR: Color = Color::YELLOW;
+/// Texture of the towers. The castle sprite is reused and tinted in the color of the tower kind
+const TOWER_TEXTURE_PATH: &str = "sprites/castle/castle0.png";
+/// Size of the tower sprites
+const TOWER_SIZE: Vec2 = Vec2::new(96.0, 77.0);
+/// Z value of the towers (the same as the trees they replace)
+const TOWER_Z_VALUE: f32 = 0.5;
+/// Width of the charge bar below the towers
+const CHARGE_BAR_WIDTH: f32 = 80.0;
+/// Distance of the charge bar below the center of the towers
+const CHARGE_BAR_OFFSET: f32 = 50.0;
+/// Color of the empty part of the charge bar
+const CHARGE_BAR_BACKGROUND_COLOR: Color = Color::rgba(0.1, 0.1, 0.1, 0.75);
+/// Time it takes for the shot of a tower to fade out
+const TOWER_SHOT_DURATION: f32 = 0.3;
+/// Factor the speed of enemies hit by frost towers is multiplied with
+const FROST_SLOW_FACTOR: f32 = 0.5;
+/// Time enemies hit by frost towers are slowed down for
+const FROST_SLOW_DURATION: f32 = 3.0;
+/// Radius of the circle drawn around slowed enemies
+const SLOWED_MARKER_RADIUS: f32 = 30.0;
+/// Minimum distance enemies hit by gust towers are knocked back
+const GUST_MINIMUM_KNOCKBACK: f32 = 100.0;
+/// Font size of the texts in the tower panel
+const TOWER_PANEL_FONT_SIZE: f32 = 24.0;
+/// Background color of the tower buttons
+const TOWER_BUTTON_COLOR: Color = Color::rgba(0.1, 0.1, 0.1, 0.75);
+/// Background color of the tower buttons while hovered
+const TOWER_BUTTON_HOVERED_COLOR: Color = Color::rgba(0.3, 0.3, 0.3, 0.85);
+/// Text color of towers that can be built
+const AFFORDABLE_TOWER_COLOR: Color = Color::WHITE;
+/// Text color of towers that are too expensive or can't be built on the selected spot
+const UNAVAILABLE_TOWER_COLOR: Color = Color::GRAY;
+/// Keys for building the towers, in the order of [`TowerKind::ALL`] and after the keys of the shop
+const TOWER_KEYS: [KeyCode; 3] = [KeyCode::Digit6, KeyCode::Digit7, KeyCode::Digit8];
+
+/// Determines the build spots of the loaded map and removes the towers of the previous one
+pub fn update_build_spots(
+    mut commands: Commands,
+    loaded_map: Res<LoadedMap>,
+    mut build_spots: ResMut<BuildSpots>,
+    tower_query: Query<Entity, With<Tower>>,
+) {
+    for tower_entity in tower_query.iter() {
+        commands.entity(tower_entity).despawn_recursive();
+    }
+    build_spots.positions = loaded_map
+        .tree_positions
+        .iter()
+        .copied()
+        .filter(|&tree_position| {
+            loaded_map
+                .routes
+                .iter()
+                .any(|route| route.distance_to(tree_position) <= BUILD_SPOT_MAX_ROAD_DISTANCE)
+        })
+        .collect();
+    build_spots.selected = None;
+}
+
+/// Spawns the tower panel at the right of the screen with one button per tower
+pub fn spawn_tower_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    align_self: AlignSelf::Center,
+                    justify_self: JustifySelf::End,
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Stretch,
+                    row_gap: Val::Px(6.0),
+                    margin: UiRect::right(Val::Percent(1.0)),
+                    ..default()
+                },
+                ..default()
+            },
+            TowerUiElement,
+            Name::new("Tower panel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: TOWER_PANEL_FONT_SIZE * 1.25,
+                        color: Color::BLACK,
+                        ..default()
+                    },
+                )
+                .with_text_justify(JustifyText::Center),
+                TowerPanelText,
+            ));
+            for kind in TowerKind::ALL {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                                ..default()
+                            },
+                            background_color: TOWER_BUTTON_COLOR.into(),
+                            ..default()
+                        },
+                        TowerButton { kind },
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font_size: TOWER_PANEL_FONT_SIZE,
+                                color: AFFORDABLE_TOWER_COLOR,
+                                ..default()
+                            },
+                        ));
+                    });
+            }
+        });
+}
+
+/// Selects the build spot closest to a left click
+pub fn select_build_spot_on_click(
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    mut build_spots: ResMut<BuildSpots>,
+) {
+    if !mouse_input.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let Some(cursor_position) = cursor_world_position(&window_query, &camera_query) else {
+        return;
+    };
+    let clicked_spot = build_spots
+        .positions
+        .iter()
+        .enumerate()
+        .map(|(spot, position)| (spot, position.distance(cursor_position)))
+        .filter(|(_, distance)| *distance <= BUILD_SPOT_PICK_RADIUS)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(spot, _)| spot);
+    if clicked_spot.is_some() {
+        build_spots.selected = clicked_spot;
+    }
+}
+
+/// Builds a tower on the selected build spot when its button is clicked or its number key is pressed
+pub fn build_towers(
+    mut commands: Commands,
+    interaction_query: Query<(&Interaction, &TowerButton), Changed<Interaction>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut coins: ResMut<Coins>,
+    build_spots: Res<BuildSpots>,
+    tower_query: Query<&Tower>,
+    asset_server: Res<AssetServer>,
+) {
+    let clicked_towers = interaction_query
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+        .map(|(_, tower_button)| tower_button.kind);
+    let pressed_towers = TowerKind::ALL
+        .into_iter()
+        .zip(TOWER_KEYS)
+        .filter(|(_, key)| keyboard_input.just_pressed(*key))
+        .map(|(kind, _)| kind);
+
+    // Only one tower can be built per frame as the new tower only occupies its spot once it is spawned
+    let Some(kind) = clicked_towers.chain(pressed_towers).next() else {
+        return;
+    };
+    let Some(build_spot) = build_spots.selected else {
+        return;
+    };
+    if !can_build(kind, &coins, build_spot, &tower_query) {
+        return;
+    }
+    coins.amount -= kind.cost();
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: kind.color(),
+                custom_size: Some(TOWER_SIZE),
+                ..default()
+            },
+            transform: Transform::from_translation(
+                build_spots.positions[build_spot].extend(TOWER_Z_VALUE),
+            ),
+            texture: asset_server.load(TOWER_TEXTURE_PATH),
+            ..default()
+        },
+        Tower {
+            kind,
+            build_spot,
+            charge: 0,
+        },
+        Name::new(kind.to_string()),
+    ));
+}
+
+/// Returns whether the tower can be built on the given build spot right now
+fn can_build(
+    kind: TowerKind,
+    coins: &Coins,
+    build_spot: usize,
+    tower_query: &Query<&Tower>,
+) -> bool {
+    coins.amount >= kind.cost() && !is_occupied(build_spot, tower_query)
+}
+
+/// Returns whether a tower stands on the given build spot
+fn is_occupied(build_spot: usize, tower_query: &Query<&Tower>) -> bool {
+    tower_query
+        .iter()
+        .any(|tower| tower.build_spot == build_spot)
+}
+
+/// Updates the texts of the tower panel whenever coins, the selected spot or the towers change
+pub fn update_tower_panel_texts(
+    coins: Res<Coins>,
+    build_spots: Res<BuildSpots>,
+    tower_query: Query<&Tower>,
+    changed_tower_query: Query<(), Changed<Tower>>,
+    mut removed_towers: RemovedComponents<Tower>,
+    mut panel_text_query: Query<&mut Text, With<TowerPanelText>>,
+    tower_button_query: Query<(&TowerButton, &Children)>,
+    added_tower_button_query: Query<(), Added<TowerButton>>,
+    mut text_query: Query<&mut Text, Without<TowerPanelText>>,
+) {
+    if !(coins.is_changed()
+        || build_spots.is_changed()
+        || !changed_tower_query.is_empty()
+        || removed_towers.read().count() > 0
+        || !added_tower_button_query.is_empty())
+    {
+        return;
+    }
+    for mut text in panel_text_query.iter_mut() {
+        text.sections[0].value = match build_spots.selected {
+            None => "Towers - click a circle to select a spot".to_string(),
+            Some(build_spot) if is_occupied(build_spot, &tower_query) => {
+                "Towers - this spot is taken".to_string()
+            }
+            Some(_) => "Towers - build on the selected spot".to_string(),
+        };
+    }
+    for (tower_button, children) in tower_button_query.iter() {
+        let kind = tower_button.kind;
+        let key_number = TowerKind::ALL
+            .iter()
+            .position(|other| *other == kind)
+            .unwrap_or_default()
+            + 6;
+        let color = match build_spots.selected {
+            Some(build_spot) if can_build(kind, &coins, build_spot, &tower_query) => {
+                AFFORDABLE_TOWER_COLOR
+            }
+            _ => UNAVAILABLE_TOWER_COLOR,
+        };
+        let mut text_iter = text_query.iter_many_mut(children);
+        while let Some(mut text) = text_iter.fetch_next() {
+            text.sections[0].value = format!(
+                "[{}] {}: {} - {} coins",
+                key_number,
+                kind,
+                kind.description(),
+                kind.cost()
+            );
+            text.sections[0].style.color = color;
+        }
+    }
+}
+
+/// Handles the background color of the tower buttons based on mouse interaction
+pub fn tower_button_animations(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<TowerButton>),
+    >,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        *color = match *interaction {
+            Interaction::None => TOWER_BUTTON_COLOR.into(),
+            _ => TOWER_BUTTON_HOVERED_COLOR.into(),
+        };
+    }
+}
+
+/// Hides the trees that towers are built on
+pub fn hide_trees_under_towers(
+    mut tree_query: Query<(&Transform, &mut Visibility), With<Tree>>,
+    tower_query: Query<&Transform, With<Tower>>,
+) {
+    for (tree_transform, mut visibility) in tree_query.iter_mut() {
+        let covered = tower_query.iter().any(|tower_transform| {
+            tower_transform
+                .translation
+                .truncate()
+                .distance(tree_transform.translation.truncate())
+                < 1.0
+        });
+        visibility.set_if_neq(if covered {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        });
+    }
+}
+
+/// Charges all towers with every correctly typed letter
+pub fn charge_towers(
+    mut letter_typed_event: EventReader<LetterTypedEvent>,
+    mut tower_query: Query<&mut Tower>,
+) {
+    let letters = letter_typed_event.read().count() as u32;
+    if letters == 0 {
+        return;
+    }
+    for mut tower in tower_query.iter_mut() {
+        tower.charge = (tower.charge + letters).min(tower.kind.letters_to_charge());
+    }
+}
+
+/// Lets fully charged towers fire at the enemy in range that is closest to the castle.
+/// Frost and gust towers don't fire at the words of bosses, since they can't slow them down or knock
+/// them back. Towers without a suitable enemy in range stay charged
+pub fn fire_charged_towers(
+    mut commands: Commands,
+    mut tower_query: Query<(&mut Tower, &Transform)>,
+    enemy_query: Query<(Entity, &Transform, &Children, Has<Slowed>, Has<BossWord>), With<Enemy>>,
+    mut path_progress_query: Query<(&EnemySpawnPoint, &mut PathProgress)>,
+    mut currently_being_typed_query: Query<&mut CurrentlyBeingTyped>,
+    mut text_query: Query<&mut Text>,
+    enemy_spatial_index: Res<EnemySpatialIndex>,
+    loaded_map: Res<LoadedMap>,
+) {
+    for (mut tower, tower_transform) in tower_query.iter_mut() {
+        if tower.charge < tower.kind.letters_to_charge() {
+            continue;
+        }
+        let tower_position = tower_transform.translation.truncate();
+        let range = tower.kind.range();
+        let in_range = Rect::from_center_size(tower_position, Vec2::splat(2.0 * range));
+        let target = enemy_spatial_index
+            .grid
+            .query(in_range)
+            .filter_map(|entity| enemy_query.get(entity).ok())
+            .filter(|(_, transform, _, _, _)| {
+                transform.translation.truncate().distance(tower_position) <= range
+            })
+            .filter(
+                |(entity, _, children, slowed, boss_word)| match tower.kind {
+                    TowerKind::Frost => !slowed && !boss_word,
+                    TowerKind::Scribe => {
+                        untyped_letters(
+                            *entity,
+                            children,
+                            &currently_being_typed_query,
+                            &text_query,
+                        ) >= 2
+                    }
+                    TowerKind::Gust => !boss_word && path_progress_query.contains(*entity),
+                },
+            )
+            .map(|(entity, transform, children, _, _)| {
+                let distance_to_castle = transform
+                    .translation
+                    .truncate()
+                    .distance(loaded_map.castle_position);
+                (
+                    entity,
+                    transform.translation.truncate(),
+                    children,
+                    distance_to_castle,
+                )
+            })
+            // The entity breaks ties, so towers fire at the same enemies in every run
+            .min_by(|a, b| a.3.total_cmp(&b.3).then(a.0.cmp(&b.0)));
+        let Some((entity, target_position, children, _)) = target else {
+            continue;
+        };
+
+        let effect_applied = match tower.kind {
+            TowerKind::Frost => {
+                commands.entity(entity).insert(Slowed {
+                    factor: FROST_SLOW_FACTOR,
+                    timer: Timer::from_seconds(FROST_SLOW_DURATION, TimerMode::Once),
+                });
+                true
+            }
+            TowerKind::Scribe => {
+                type_letter_for_the_player(
+                    entity,
+                    children,
+                    &mut currently_being_typed_query,
+                    &mut text_query,
+                );
+                true
+            }
+            TowerKind::Gust => match path_progress_query.get_mut(entity) {
+                Ok((spawn_point, mut path_progress)) => {
+                    path_progress.distance = loaded_map
+                        .route(spawn_point)
+                        .checkpoint_before(path_progress.distance, GUST_MINIMUM_KNOCKBACK);
+                    true
+                }
+                Err(_) => false,
+            },
+        };
+        // Keep the charge if the shot had no effect
+        if !effect_applied {
+            continue;
+        }
+        tower.charge = 0;
+        commands.spawn((
+            TowerShot {
+                from: tower_position,
+                to: target_position,
+                kind: tower.kind,
+                timer: Timer::from_seconds(TOWER_SHOT_DURATION, TimerMode::Once),
+            },
+            Name::new("Tower shot"),
+        ));
+    }
+}
+
+/// Returns the number of letters of the word of an enemy that are not typed yet
+fn untyped_letters(
+    entity: Entity,
+    children: &Children,
+    currently_being_typed_query: &Query<&mut CurrentlyBeingTyped>,
+    text_query: &Query<&mut Text>,
+) -> usize {
+    let typed_letters = currently_being_typed_query
+        .get(entity)
+        .map_or(0, |currently_being_typed| currently_being_typed.index + 1);
+    text_query
+        .iter_many(children)
+        .map(|text| text.sections.len().saturating_sub(typed_letters))
+        .sum()
+}
+
+/// Types the next letter of the word of an enemy. If the player started typing the word, the letter
+/// is marked as typed, otherwise the first letter is removed from the word.
+/// Is only called for words with at least two letters left, so the enemy is never typed completely
+fn type_letter_for_the_player(
+    entity: Entity,
+    children: &Children,
+    currently_being_typed_query: &mut Query<&mut CurrentlyBeingTyped>,
+    text_query: &mut Query<&mut Text>,
+) {
+    let mut text_iter = text_query.iter_many_mut(children);
+    while let Some(mut text) = text_iter.fetch_next() {
+        match currently_being_typed_query.get_mut(entity) {
+            Ok(mut currently_being_typed) => {
+                currently_being_typed.index += 1;
+                if let Some(section) = text.sections.get_mut(currently_being_typed.index) {
+                    section.style.color = TYPING_COLOR;
+                }
+            }
+            Err(_) => {
+                text.sections.remove(0);
+            }
+        }
+    }
+}
+
+/// Fades out the shots of the towers and despawns them afterwards
+pub fn fade_tower_shots(
+    mut commands: Commands,
+    mut tower_shot_query: Query<(Entity, &mut TowerShot)>,
+    time: Res<Time>,
+) {
+    for (entity, mut tower_shot) in tower_shot_query.iter_mut() {
+        tower_shot.timer.tick(time.delta());
+        if tower_shot.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Draws the charge bars of the towers, their shots and a ring around slowed enemies
+pub fn draw_towers(
+    mut gizmos: Gizmos,
+    tower_query: Query<(&Tower, &Transform)>,
+    tower_shot_query: Query<&TowerShot>,
+    slowed_query: Query<&Transform, With<Slowed>>,
+) {
+    for (tower, transform) in tower_query.iter() {
+        let left =
+            transform.translation.truncate() - Vec2::new(CHARGE_BAR_WIDTH / 2.0, CHARGE_BAR_OFFSET);
+        let right = left + Vec2::X * CHARGE_BAR_WIDTH;
+        let charged = tower.charge as f32 / tower.kind.letters_to_charge() as f32;
+        gizmos.line_2d(left, right, CHARGE_BAR_BACKGROUND_COLOR);
+        gizmos.line_2d(left, left.lerp(right, charged), tower.kind.color());
+    }
+    for tower_shot in tower_shot_query.iter() {
+        let color = tower_shot
+            .kind
+            .color()
+            .with_a(1.0 - tower_shot.timer.fraction());
+        gizmos.line_2d(tower_shot.from, tower_shot.to, color);
+        gizmos.circle_2d(tower_shot.to, SLOWED_MARKER_RADIUS * 0.5, color);
+    }
+    for transform in slowed_query.iter() {
+        gizmos.circle_2d(
+            transform.translation.truncate(),
+            SLOWED_MARKER_RADIUS,
+            TowerKind::Frost.color(),
+        );
+    }
+}
+
+/// Marks the free build spots in between rounds and shows the range of the tower on the selected one
+pub fn draw_build_spots(
+    mut gizmos: Gizmos,
+    build_spots: Res<BuildSpots>,
+    tower_query: Query<&Tower>,
+) {
+    for (spot, position) in build_spots.positions.iter().enumerate() {
+        let tower = tower_query.iter().find(|tower| tower.build_spot == spot);
+        let selected = build_spots.selected == Some(spot);
+        if selected {
+            gizmos.circle_2d(
+                *position,
+                BUILD_SPOT_MARKER_RADIUS,
+                SELECTED_BUILD_SPOT_COLOR,
+            );
+        } else if tower.is_none() {
+            gizmos.circle_2d(*position, BUILD_SPOT_MARKER_RADIUS, BUILD_SPOT_COLOR);
+        }
+        if let (Some(tower), true) = (tower, selected) {
+            gizmos.circle_2d(*position, tower.kind.range(), tower.kind.color());
+        }
+    }
+}
+
+/// Removes all towers and their shots on restart
+pub fn despawn_towers_on_restart(
+    mut commands: Commands,
+    mut restart_event_reader: EventReader<Restart>,
+    mut build_spots: ResMut<BuildSpots>,
+    tower_query: Query<Entity, With<Tower>>,
+    tower_shot_query: Query<Entity, With<TowerShot>>,
+) {
+    for _ in restart_event_reader.read() {
+        build_spots.selected = None;
+        for entity in tower_query.iter().chain(tower_shot_query.iter()) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,38 @@
+pub mod resources;
+use resources::*;
+
+pub mod systems;
+use systems::*;
+
+use super::*;
+
+use bevy_common_assets::toml::TomlAssetPlugin;
+
+pub struct WavesPlugin;
+
+impl Plugin for WavesPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Register types for debug
+            .register_type::<CurrentWave>()
+            // Initialize Resources
+            .init_resource::<CurrentWave>()
+            // Setup the wave script as asset
+            .add_plugins(TomlAssetPlugin::<WaveScript>::new(&["waves.toml"]))
+            .add_systems(Startup, setup_wave_script_asset)
+            // Prepare the round once its number is known
+            .add_systems(
+                OnEnter(RoundState::InRound),
+                prepare_wave.after(rounds_and_indicators::systems::increase_round_counter),
+            )
+            // Add update systems
+            .add_systems(
+                Update,
+                roll_scripted_enemies
+                    .run_if(wave_is_scripted)
+                    .after(enemies::spawn_director::systems::update_spawn_plan)
+                    .before(enemies::systems::spawn_pending_enemies)
+                    .in_set(InputHandlingSystemSet::InputHandling),
+            );
+    }
+}
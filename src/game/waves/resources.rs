@@ -0,0 +1,388 @@
+use super::*;
+
+use crate::game::boss::components::BossType;
+use crate::game::boss::systems::BOSS_WORD_COUNT_MULTIPLIER;
+use crate::game::enemies::components::EnemyType;
+use crate::game::map::resources::LoadedMap;
+use crate::game::rounds_and_indicators::resources::Difficulty;
+use rand::{seq::SliceRandom, Rng};
+
+/// Path of the wave script that scripts the first rounds of a game
+pub const WAVE_SCRIPT_PATH: &str = "waves/default.waves.toml";
+/// Every this many rounds a boss appears in rounds generated by the endless mode
+pub const ENDLESS_BOSS_ROUND_INTERVAL: u32 = 10;
+/// Words with up to this many letters are short words
+const SHORT_WORD_MAX_LETTERS: usize = 4;
+/// Words with up to this many letters are medium words, longer ones are long words
+const MEDIUM_WORD_MAX_LETTERS: usize = 7;
+
+/// Asset that scripts the rounds of a game for each difficulty. The first wave of the difficulty
+/// is the first round and so on. Once the waves run out, the endless mode generates the rounds
+#[derive(serde::Deserialize, Asset, TypePath, Clone)]
+pub struct WaveScript {
+    pub name: String,
+    #[serde(default)]
+    pub easy: Vec<WaveDescription>,
+    #[serde(default)]
+    pub medium: Vec<WaveDescription>,
+    #[serde(default)]
+    pub hard: Vec<WaveDescription>,
+}
+
+/// A scripted round given by groups of enemies and whether a boss appears
+#[derive(serde::Deserialize, Clone, Default)]
+pub struct WaveDescription {
+    #[serde(default)]
+    pub groups: Vec<GroupDescription>,
+    #[serde(default)]
+    pub boss: bool,
+    /// Type of the boss. Defaults to the boss of the round in the rotation of the endless mode
+    pub boss_type: Option<BossType>,
+    /// Number of words of the boss. Defaults to the number of words of endless boss rounds
+    pub boss_words: Option<u32>,
+    /// Base speed of the enemies. Defaults to the base speed of the round in the endless mode
+    pub speed: Option<f32>,
+}
+
+/// A group of enemies of a scripted round that spawn one after another
+#[derive(serde::Deserialize, Clone)]
+pub struct GroupDescription {
+    /// Type of the enemies. Defaults to random types from the spawn table of the round
+    pub enemy: Option<EnemyType>,
+    #[serde(default = "default_count")]
+    pub count: u32,
+    /// Name of the route the enemies spawn on. Defaults to a route chosen by the spawn director
+    pub route: Option<String>,
+    /// Seconds after the previous group started (or the start of the round) that this group starts
+    #[serde(default)]
+    pub delay: f32,
+    /// Seconds between two enemies of the group
+    #[serde(default = "default_interval")]
+    pub interval: f32,
+    #[serde(default)]
+    pub word_tier: WordTier,
+    /// Factor the base speed is multiplied with. Defaults to a random factor like in the endless mode
+    pub speed: Option<f32>,
+}
+
+fn default_count() -> u32 {
+    1
+}
+
+fn default_interval() -> f32 {
+    2.0
+}
+
+/// Which words enemies of a group can get, by the length of the words
+#[derive(serde::Deserialize, Reflect, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WordTier {
+    #[default]
+    Any,
+    Short,
+    Medium,
+    Long,
+}
+
+impl WordTier {
+    /// Returns whether the given word belongs to the tier
+    pub fn contains(&self, word: &str) -> bool {
+        let letters = word.chars().count();
+        match self {
+            WordTier::Any => true,
+            WordTier::Short => letters <= SHORT_WORD_MAX_LETTERS,
+            WordTier::Medium => {
+                letters > SHORT_WORD_MAX_LETTERS && letters <= MEDIUM_WORD_MAX_LETTERS
+            }
+            WordTier::Long => letters > MEDIUM_WORD_MAX_LETTERS,
+        }
+    }
+
+    /// Chooses a random word of the tier from the given words. Takes any word if there is no word of the tier
+    pub fn choose<'a>(&self, words: &'a [String], rng: &mut impl Rng) -> Option<&'a String> {
+        let words_of_tier: Vec<&String> = words.iter().filter(|word| self.contains(word)).collect();
+        if words_of_tier.is_empty() {
+            words.choose(rng)
+        } else {
+            words_of_tier.choose(rng).copied()
+        }
+    }
+}
+
+impl WaveScript {
+    /// Returns the waves scripted for the given difficulty
+    pub fn waves(&self, difficulty: &Difficulty) -> &[WaveDescription] {
+        match difficulty {
+            Difficulty::Easy => &self.easy,
+            Difficulty::Medium => &self.medium,
+            Difficulty::Hard => &self.hard,
+        }
+    }
+}
+
+/// Resource holding the handle of the wave script
+#[derive(Resource, Default)]
+pub struct WaveScriptHandle(pub Handle<WaveScript>);
+
+/// An enemy of a scripted round that spawns at the given time
+#[derive(Reflect, Clone, Debug, Default)]
+pub struct ScriptedSpawn {
+    /// Seconds since the start of the round
+    pub time: f32,
+    pub enemy_type: Option<EnemyType>,
+    pub route: Option<usize>,
+    pub word_tier: WordTier,
+    pub speed_factor: Option<f32>,
+}
+
+/// Resource for the composition of the current round, either from the wave script or generated by the endless mode
+#[derive(Reflect, Resource, Default)]
+#[reflect(Resource)]
+pub struct CurrentWave {
+    pub scripted: bool,
+    pub boss: bool,
+    pub boss_type: BossType,
+    pub boss_words: u32,
+    pub speed: Option<f32>,
+    /// Words enemies of rounds generated by the endless mode get
+    pub word_tier: WordTier,
+    /// Scripted enemies sorted by their spawn time
+    pub spawns: Vec<ScriptedSpawn>,
+    /// Index of the scripted enemy that spawns next
+    pub next_spawn: usize,
+}
+
+impl CurrentWave {
+    /// Creates the wave of the given round from its description in the wave script.
+    /// Routes that don't exist on the loaded map are left to the spawn director
+    pub fn from_description(
+        description: &WaveDescription,
+        round_number: u32,
+        loaded_map: &LoadedMap,
+    ) -> CurrentWave {
+        let mut spawns: Vec<ScriptedSpawn> = Vec::new();
+        let mut group_start = 0.0;
+        for group in &description.groups {
+            group_start += group.delay;
+            let route = group.route.as_ref().and_then(|route_name| {
+                let route = loaded_map
+                    .routes
+                    .iter()
+                    .position(|route| &route.name == route_name);
+                if route.is_none() {
+                    warn!(
+                        "Route {} of the wave script does not exist on map {}",
+                        route_name, loaded_map.name
+                    );
+                }
+                route
+            });
+            for index in 0..group.count {
+                spawns.push(ScriptedSpawn {
+                    time: group_start + index as f32 * group.interval,
+                    enemy_type: group.enemy,
+                    route,
+                    word_tier: group.word_tier,
+                    speed_factor: group.speed,
+                });
+            }
+        }
+        // Groups may overlap, the sort is stable so enemies at the same time keep their order
+        spawns.sort_by(|a, b| a.time.total_cmp(&b.time));
+        CurrentWave {
+            scripted: true,
+            boss: description.boss,
+            boss_type: description
+                .boss_type
+                .unwrap_or_else(|| BossType::for_round(round_number)),
+            boss_words: if description.boss {
+                description
+                    .boss_words
+                    .unwrap_or(BOSS_WORD_COUNT_MULTIPLIER * round_number)
+            } else {
+                0
+            },
+            speed: description.speed,
+            word_tier: WordTier::Any,
+            spawns,
+            next_spawn: 0,
+        }
+    }
+
+    /// Creates the wave of the given round in the endless mode, where every tenth round is a boss round
+    pub fn endless(round_number: u32) -> CurrentWave {
+        let boss = round_number % ENDLESS_BOSS_ROUND_INTERVAL == 0;
+        CurrentWave {
+            scripted: false,
+            boss,
+            boss_type: BossType::for_round(round_number),
+            boss_words: if boss {
+                BOSS_WORD_COUNT_MULTIPLIER * round_number
+            } else {
+                0
+            },
+            ..default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::map::resources::Route;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn group(delay: f32, count: u32, interval: f32) -> GroupDescription {
+        GroupDescription {
+            enemy: None,
+            count,
+            route: None,
+            delay,
+            interval,
+            word_tier: WordTier::Any,
+            speed: None,
+        }
+    }
+
+    fn loaded_map_with_route(name: &str) -> LoadedMap {
+        LoadedMap {
+            routes: vec![Route {
+                name: name.to_string(),
+                points: vec![Vec2::ZERO, Vec2::X],
+                distances: vec![0.0, 1.0],
+                checkpoint_distances: vec![0.0, 1.0],
+                branches: Vec::new(),
+            }],
+            ..default()
+        }
+    }
+
+    #[test]
+    fn word_tiers_split_words_by_length() {
+        assert!(WordTier::Short.contains("cat"));
+        assert!(WordTier::Short.contains("tree"));
+        assert!(!WordTier::Short.contains("house"));
+        assert!(WordTier::Medium.contains("house"));
+        assert!(WordTier::Medium.contains("castles"));
+        assert!(!WordTier::Medium.contains("mountain"));
+        assert!(WordTier::Long.contains("mountain"));
+        assert!(!WordTier::Long.contains("tree"));
+        assert!(["a", "house", "mountain"]
+            .iter()
+            .all(|word| WordTier::Any.contains(word)));
+    }
+
+    #[test]
+    fn word_tiers_count_letters_instead_of_bytes() {
+        assert!(WordTier::Short.contains("über"));
+    }
+
+    #[test]
+    fn choose_takes_words_of_the_tier() {
+        let words: Vec<String> = ["cat", "house", "mountain", "dog"]
+            .iter()
+            .map(|word| word.to_string())
+            .collect();
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..20 {
+            let word = WordTier::Short.choose(&words, &mut rng).unwrap();
+            assert!(WordTier::Short.contains(word));
+        }
+        assert_eq!(
+            WordTier::Long.choose(&words, &mut rng).map(String::as_str),
+            Some("mountain")
+        );
+    }
+
+    #[test]
+    fn choose_takes_any_word_if_the_tier_has_none() {
+        let words = vec!["cat".to_string()];
+        let mut rng = StdRng::seed_from_u64(42);
+
+        assert_eq!(
+            WordTier::Long.choose(&words, &mut rng).map(String::as_str),
+            Some("cat")
+        );
+        assert_eq!(WordTier::Any.choose(&[], &mut rng), None);
+    }
+
+    #[test]
+    fn from_description_starts_groups_after_the_delays_of_the_previous_groups() {
+        let description = WaveDescription {
+            groups: vec![group(1.0, 3, 2.0), group(2.0, 2, 0.5)],
+            ..default()
+        };
+        let wave = CurrentWave::from_description(&description, 1, &LoadedMap::default());
+
+        let times: Vec<f32> = wave.spawns.iter().map(|spawn| spawn.time).collect();
+        assert_eq!(times, vec![1.0, 3.0, 3.0, 3.5, 5.0]);
+        assert!(wave.scripted);
+        assert_eq!(wave.next_spawn, 0);
+    }
+
+    #[test]
+    fn from_description_sorts_overlapping_groups_and_keeps_the_order_of_equal_times() {
+        let mut first_group = group(0.0, 2, 4.0);
+        first_group.enemy = Some(EnemyType::Pig);
+        let mut second_group = group(0.0, 2, 4.0);
+        second_group.enemy = Some(EnemyType::Bat);
+        let description = WaveDescription {
+            groups: vec![first_group, second_group],
+            ..default()
+        };
+        let wave = CurrentWave::from_description(&description, 1, &LoadedMap::default());
+
+        let spawns: Vec<(f32, Option<EnemyType>)> = wave
+            .spawns
+            .iter()
+            .map(|spawn| (spawn.time, spawn.enemy_type))
+            .collect();
+        assert_eq!(
+            spawns,
+            vec![
+                (0.0, Some(EnemyType::Pig)),
+                (0.0, Some(EnemyType::Bat)),
+                (4.0, Some(EnemyType::Pig)),
+                (4.0, Some(EnemyType::Bat)),
+            ]
+        );
+    }
+
+    #[test]
+    fn from_description_resolves_routes_by_name() {
+        let mut known_route = group(0.0, 1, 1.0);
+        known_route.route = Some("left".to_string());
+        let mut unknown_route = group(1.0, 1, 1.0);
+        unknown_route.route = Some("right".to_string());
+        let description = WaveDescription {
+            groups: vec![known_route, unknown_route],
+            ..default()
+        };
+        let wave = CurrentWave::from_description(&description, 1, &loaded_map_with_route("left"));
+
+        assert_eq!(wave.spawns[0].route, Some(0));
+        assert_eq!(wave.spawns[1].route, None);
+    }
+
+    #[test]
+    fn from_description_gives_bosses_words_by_round_unless_scripted() {
+        let description = WaveDescription {
+            boss: true,
+            ..default()
+        };
+        let wave = CurrentWave::from_description(&description, 4, &LoadedMap::default());
+        assert_eq!(wave.boss_words, BOSS_WORD_COUNT_MULTIPLIER * 4);
+
+        let description = WaveDescription {
+            boss: true,
+            boss_words: Some(7),
+            ..default()
+        };
+        let wave = CurrentWave::from_description(&description, 4, &LoadedMap::default());
+        assert_eq!(wave.boss_words, 7);
+
+        let wave =
+            CurrentWave::from_description(&WaveDescription::default(), 4, &LoadedMap::default());
+        assert_eq!(wave.boss_words, 0);
+    }
+}
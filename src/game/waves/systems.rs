@@ -0,0 +1,112 @@
+use rand::Rng;
+
+use crate::game::enemies::components::EnemyType;
+use crate::game::enemies::movement::components::EnemySpawnPoint;
+use crate::game::enemies::resources::{LastEnemySpawnPoint, WordsHandle};
+use crate::game::enemies::spawn_director::resources::{SpawnCandidate, SpawnDirector};
+use crate::game::enemies::systems::{random_speed_factor, Words};
+use crate::game::map::resources::LoadedMap;
+use crate::game::randomness::resources::{GameRng, RngStream};
+use crate::game::rounds_and_indicators::resources::{
+    DifficultyIndicator, EnemyBaseSpeedCurrentRound, RoundNumber,
+};
+
+use super::*;
+
+/// Sets up the wave script as asset
+pub fn setup_wave_script_asset(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(WaveScriptHandle(asset_server.load(WAVE_SCRIPT_PATH)));
+}
+
+/// Prepares the composition of the round that just started. Rounds beyond the waves of the wave
+/// script (or all rounds if it is not loaded) are generated by the endless mode
+pub fn prepare_wave(
+    mut current_wave: ResMut<CurrentWave>,
+    wave_script_handle: Res<WaveScriptHandle>,
+    wave_scripts: Res<Assets<WaveScript>>,
+    round_number: Res<RoundNumber>,
+    difficulty_indicator: Res<DifficultyIndicator>,
+    loaded_map: Res<LoadedMap>,
+) {
+    let scripted_wave = wave_scripts
+        .get(wave_script_handle.0.id())
+        .and_then(|wave_script| {
+            wave_script
+                .waves(&difficulty_indicator.difficulty)
+                .get(round_number.number.saturating_sub(1) as usize)
+        });
+    *current_wave = match scripted_wave {
+        Some(description) => {
+            CurrentWave::from_description(description, round_number.number, &loaded_map)
+        }
+        None => CurrentWave::endless(round_number.number),
+    };
+}
+
+/// Run condition for rounds that are scripted by the wave script
+pub fn wave_is_scripted(current_wave: Res<CurrentWave>) -> bool {
+    current_wave.scripted
+}
+
+/// System that rolls the enemies of a scripted round once their time has come and hands them to the spawn director
+pub fn roll_scripted_enemies(
+    mut current_wave: ResMut<CurrentWave>,
+    mut spawn_director: ResMut<SpawnDirector>,
+    loaded_map: Res<LoadedMap>,
+    last_enemy_spawn_point: Res<LastEnemySpawnPoint>,
+    enemy_base_speed_this_round: Res<EnemyBaseSpeedCurrentRound>,
+    words_handle: Res<WordsHandle>,
+    words: Res<Assets<Words>>,
+    round_number: Res<RoundNumber>,
+    difficulty_indicator: Res<DifficultyIndicator>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    // Enemies can only spawn once the map is loaded and the previous enemy is spawned
+    if loaded_map.spawn_routes.is_empty() || spawn_director.pending_spawn.is_some() {
+        return;
+    }
+    let Some(scripted_spawn) = current_wave.spawns.get(current_wave.next_spawn).cloned() else {
+        return;
+    };
+    if scripted_spawn.time > spawn_director.round_time {
+        return;
+    }
+    let Some(word) = words.get(words_handle.0.id()) else {
+        return;
+    };
+
+    let spawn_point = match scripted_spawn.route {
+        Some(route) => EnemySpawnPoint { route },
+        None => last_enemy_spawn_point
+            .spawn_point
+            .next_spawn_point_excluding_self(
+                &loaded_map.spawn_routes,
+                game_rng.stream(RngStream::SpawnPoints),
+            ),
+    };
+    let enemy_type = scripted_spawn.enemy_type.unwrap_or_else(|| {
+        EnemyType::random_for_round(
+            round_number.number,
+            &difficulty_indicator.difficulty,
+            game_rng.stream(RngStream::EnemyTypes),
+        )
+    });
+    let speed_factor = scripted_spawn
+        .speed_factor
+        .unwrap_or_else(|| random_speed_factor(game_rng.stream(RngStream::EnemySpeeds)));
+    let word_for_enemy = scripted_spawn
+        .word_tier
+        .choose(&word.vec_of_words, game_rng.stream(RngStream::Words))
+        .expect("The list of words shouldn't be empty")
+        .clone();
+
+    spawn_director.pending_spawn = Some(SpawnCandidate {
+        spawn_point,
+        enemy_type,
+        speed: speed_factor * enemy_base_speed_this_round.speed,
+        word: word_for_enemy,
+        branch_seed: game_rng.stream(RngStream::Junctions).gen(),
+        fixed_route: scripted_spawn.route.is_some(),
+    });
+    current_wave.next_spawn += 1;
+}
@@ -7,6 +7,11 @@ use game::GamePlugin;
 mod menu;
 use menu::MenuPlugin;
 
+mod editor;
+use editor::EditorPlugin;
+
+mod storage;
+
 use bevy::{
     prelude::*,
     window::{PresentMode, WindowTheme},
@@ -15,6 +20,10 @@ use bevy_inspector_egui::quick::WorldInspectorPlugin;
 
 pub const WINDOW_WIDTH: f32 = 1920.0;
 pub const WINDOW_HEIGHT: f32 = 1080.0;
+/// Width of the game world in logical units. The camera scales the world to fit the window
+pub const WORLD_WIDTH: f32 = 1920.0;
+/// Height of the game world in logical units. The camera scales the world to fit the window
+pub const WORLD_HEIGHT: f32 = 1080.0;
 
 fn main() {
     let mut app = App::new();
@@ -45,12 +54,17 @@ fn main() {
         )
         // Initialize AppState
         .init_state::<AppState>()
-        // Spawn camera and add background
-        .add_systems(Startup, spawn_background)
+        // Spawn camera
         .add_systems(Startup, spawn_camera)
-        .add_systems(Update, toggle_borderless_fullscreen)
-        // Add game and menu plugins
-        .add_plugins((GamePlugin, MenuPlugin));
+        .add_systems(
+            Update,
+            (
+                toggle_borderless_fullscreen,
+                update_ui_scale_on_window_resize,
+            ),
+        )
+        // Add game, menu and editor plugins
+        .add_plugins((GamePlugin, MenuPlugin, EditorPlugin));
 
     if cfg!(debug_assertions) {
         // Add Debugging info in game
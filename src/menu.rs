@@ -20,6 +20,9 @@ impl Plugin for MenuPlugin {
             .register_type::<MainMenuScreenUiElement>()
             .register_type::<SettingsMenuClosed>()
             .register_type::<SettingsMenuOpened>()
+            .register_type::<MapSelectorText>()
+            .register_type::<LevelSelectScreenUiElement>()
+            .register_type::<AdaptiveDifficultyToggleText>()
             // Add events
             .add_event::<DifficultyChangedEvent>()
             .add_event::<Restart>()
@@ -37,6 +40,11 @@ impl Plugin for MenuPlugin {
             )
             .add_systems(OnEnter(MenuState::InGameMainMenu), spawn_in_game_menu)
             .add_systems(OnEnter(LoosingState::Lost), spawn_lost_menu)
+            .add_systems(OnEnter(MenuState::LevelSelect), spawn_level_select_screen)
+            .add_systems(
+                OnEnter(MenuState::LevelComplete),
+                spawn_level_complete_screen,
+            )
             .add_systems(
                 OnExit(MenuState::Main),
                 despawn_entities_with_specific_component::<MainMenuScreenUiElement>,
@@ -45,6 +53,14 @@ impl Plugin for MenuPlugin {
                 OnExit(MenuState::InGameMainMenu),
                 despawn_entities_with_specific_component::<MainMenuScreenUiElement>,
             )
+            .add_systems(
+                OnExit(MenuState::LevelSelect),
+                despawn_entities_with_specific_component::<LevelSelectScreenUiElement>,
+            )
+            .add_systems(
+                OnExit(MenuState::LevelComplete),
+                despawn_entities_with_specific_component::<MainMenuScreenUiElement>,
+            )
             .add_systems(
                 OnExit(MenuState::HowToPlay),
                 despawn_entities_with_specific_component::<HowToPlayScreenUiElement>,
@@ -79,6 +95,19 @@ impl Plugin for MenuPlugin {
                     change_difficulty,
                 ),
             )
+            .add_systems(
+                Update,
+                update_map_selector_text
+                    .run_if(resource_changed::<crate::game::map::resources::LoadedMap>),
+            )
+            .add_systems(
+                Update,
+                update_adaptive_difficulty_toggle_text.run_if(
+                    resource_changed::<
+                        crate::game::adaptive_difficulty::resources::AdaptiveDifficulty,
+                    >,
+                ),
+            )
             .add_systems(
                 Update,
                 check_if_in_game_menu_is_opened.run_if(in_state(GameStartedState::GameHasStarted)),
@@ -109,6 +138,8 @@ pub enum MenuState {
     HowToPlayTransition,
     InGameMainMenu,
     LostMenu,
+    LevelSelect,
+    LevelComplete,
     #[default]
     NotInTheMenu,
 }
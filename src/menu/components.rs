@@ -10,6 +10,11 @@ pub struct MainMenuScreenUiElement;
 #[reflect(Component)]
 pub struct GitHubButton;
 
+// Component used to tag entities added on the level select screen
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct LevelSelectScreenUiElement;
+
 // Component used to tag entities added on the how to play screen
 #[derive(Reflect, Component, Default)]
 #[reflect(Component)]
@@ -21,6 +26,11 @@ pub struct HowToPlayScreenUiElement;
 pub enum MenuButtonAction {
     #[default]
     Play,
+    PlayDaily,
+    Campaign,
+    PlayLevel(usize),
+    NextMap,
+    ToggleAdaptiveDifficulty,
     HowToPlay,
     Resume,
     Main,
@@ -51,6 +61,16 @@ pub enum SettingsButton {
 #[reflect(Component)]
 pub struct DifficultySettingsText;
 
+/// Component used to tag the text of the map selector on the main menu screen
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct MapSelectorText;
+
+/// Component used to tag the text of the button on the main menu screen that turns the adaptive difficulty on or off
+#[derive(Reflect, Component, Default)]
+#[reflect(Component)]
+pub struct AdaptiveDifficultyToggleText;
+
 /// Component used to tag the closed settings ui elements
 #[derive(Reflect, Component, Default)]
 #[reflect(Component)]
@@ -2,6 +2,12 @@ use rand::seq::SliceRandom;
 
 use super::*;
 use crate::game::{
+    adaptive_difficulty::resources::AdaptiveDifficulty,
+    campaign::{
+        resources::{Campaign, CampaignHandle, CampaignProgress, CurrentLevel, MAX_STARS},
+        systems::StartLevelEvent,
+    },
+    daily_challenge::{resources::DailyChallenge, systems::StartDailyChallengeEvent},
     enemies::{
         components::{Enemy, EnemyType, Speed, WalkingAnimation},
         resources::WordsHandle,
@@ -11,7 +17,11 @@ use crate::game::{
             STANDARD_TEXT_COLOR,
         },
     },
-    rounds_and_indicators::resources::{Difficulty, DifficultyIndicator, INITIAL_ENEMY_SPEED},
+    map::resources::{LoadedMap, SelectedMap, MAP_PATHS},
+    randomness::resources::{GameRng, RngStream},
+    rounds_and_indicators::resources::{
+        Difficulty, DifficultyIndicator, GameMode, GameModeIndicator, INITIAL_ENEMY_SPEED,
+    },
     RoundState,
 };
 
@@ -39,10 +49,206 @@ pub fn setup_menu(mut next_menu_state: ResMut<NextState<MenuState>>) {
 }
 
 /// Spawns the main menu
-pub fn spawn_main_menu(commands: Commands, asset_server: Res<AssetServer>) {
+pub fn spawn_main_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    loaded_map: Res<LoadedMap>,
+    adaptive_difficulty: Res<AdaptiveDifficulty>,
+) {
+    spawn_map_selector(&mut commands, &asset_server, &loaded_map);
+    spawn_adaptive_difficulty_toggle(&mut commands, &asset_server, &adaptive_difficulty);
+    spawn_campaign_button(&mut commands, &asset_server);
     spawn_menu(commands, asset_server, MenuType::MainMenu);
 }
 
+/// Spawns the button at the bottom left of the main menu that cycles through the maps
+fn spawn_map_selector(commands: &mut Commands, asset_server: &AssetServer, loaded_map: &LoadedMap) {
+    commands
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    align_self: AlignSelf::FlexEnd,
+                    justify_self: JustifySelf::Start,
+                    width: Val::Percent(20.0),
+                    height: Val::Percent(10.0),
+                    margin: UiRect {
+                        left: Val::Percent(2.),
+                        right: Val::Percent(0.),
+                        top: Val::Percent(0.),
+                        bottom: Val::Percent(2.),
+                    },
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                background_color: Color::WHITE.into(),
+                image: UiImage::new(asset_server.load("ui/menu/mainMenuButton.png")),
+                ..default()
+            },
+            MenuButtonAction::NextMap,
+            MainMenuScreenUiElement,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        format!("Map: {}", loaded_map.name),
+                        TextStyle {
+                            font_size: 30.0,
+                            ..default()
+                        },
+                    ),
+                    style: Style {
+                        margin: UiRect::bottom(Val::Percent(5.0)),
+                        ..default()
+                    },
+                    ..default()
+                },
+                MainMenuText,
+                MapSelectorText,
+            ));
+        });
+}
+
+/// Spawns the button above the map selector that turns the adaptive difficulty on or off
+fn spawn_adaptive_difficulty_toggle(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    adaptive_difficulty: &AdaptiveDifficulty,
+) {
+    commands
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    align_self: AlignSelf::FlexEnd,
+                    justify_self: JustifySelf::Start,
+                    width: Val::Percent(20.0),
+                    height: Val::Percent(10.0),
+                    margin: UiRect {
+                        left: Val::Percent(2.),
+                        right: Val::Percent(0.),
+                        top: Val::Percent(0.),
+                        bottom: Val::Percent(13.),
+                    },
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                background_color: Color::WHITE.into(),
+                image: UiImage::new(asset_server.load("ui/menu/mainMenuButton.png")),
+                ..default()
+            },
+            MenuButtonAction::ToggleAdaptiveDifficulty,
+            MainMenuScreenUiElement,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        adaptive_difficulty_toggle_text(adaptive_difficulty),
+                        TextStyle {
+                            font_size: 30.0,
+                            ..default()
+                        },
+                    ),
+                    style: Style {
+                        margin: UiRect::bottom(Val::Percent(5.0)),
+                        ..default()
+                    },
+                    ..default()
+                },
+                MainMenuText,
+                AdaptiveDifficultyToggleText,
+            ));
+        });
+}
+
+/// Returns the text of the button that turns the adaptive difficulty on or off
+fn adaptive_difficulty_toggle_text(adaptive_difficulty: &AdaptiveDifficulty) -> String {
+    format!(
+        "Adaptive: {}",
+        if adaptive_difficulty.enabled {
+            "On"
+        } else {
+            "Off"
+        }
+    )
+}
+
+/// Updates the text of the adaptive difficulty button once it is turned on or off
+pub fn update_adaptive_difficulty_toggle_text(
+    adaptive_difficulty: Res<AdaptiveDifficulty>,
+    mut toggle_text_query: Query<&mut Text, With<AdaptiveDifficultyToggleText>>,
+) {
+    for mut text in toggle_text_query.iter_mut() {
+        text.sections[0].value = adaptive_difficulty_toggle_text(&adaptive_difficulty);
+    }
+}
+
+/// Spawns the button at the bottom right of the main menu that opens the level select screen
+fn spawn_campaign_button(commands: &mut Commands, asset_server: &AssetServer) {
+    commands
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    align_self: AlignSelf::FlexEnd,
+                    justify_self: JustifySelf::End,
+                    width: Val::Percent(20.0),
+                    height: Val::Percent(10.0),
+                    margin: UiRect {
+                        left: Val::Percent(0.),
+                        right: Val::Percent(2.),
+                        top: Val::Percent(0.),
+                        bottom: Val::Percent(2.),
+                    },
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                background_color: Color::WHITE.into(),
+                image: UiImage::new(asset_server.load("ui/menu/mainMenuButton.png")),
+                ..default()
+            },
+            MenuButtonAction::Campaign,
+            MainMenuScreenUiElement,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        "Campaign",
+                        TextStyle {
+                            font_size: 30.0,
+                            ..default()
+                        },
+                    ),
+                    style: Style {
+                        margin: UiRect::bottom(Val::Percent(5.0)),
+                        ..default()
+                    },
+                    ..default()
+                },
+                MainMenuText,
+            ));
+        });
+}
+
+/// Updates the text of the map selector once another map is loaded
+pub fn update_map_selector_text(
+    loaded_map: Res<LoadedMap>,
+    mut map_selector_text_query: Query<&mut Text, With<MapSelectorText>>,
+) {
+    for mut text in map_selector_text_query.iter_mut() {
+        text.sections[0].value = format!("Map: {}", loaded_map.name);
+    }
+}
+
 /// Spawns the menu according to the MenuType given. Used for the main menu, how to play and the menu
 /// that appears when the player has lost
 fn spawn_menu(mut commands: Commands, asset_server: Res<AssetServer>, type_of_menu: MenuType) {
@@ -164,6 +370,44 @@ fn spawn_menu(mut commands: Commands, asset_server: Res<AssetServer>, type_of_me
                         }
                     }
 
+                    if let MenuType::MainMenu = type_of_menu {
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: Style {
+                                        width: Val::Percent(45.0),
+                                        height: Val::Percent(BUTTON_HEIGHT),
+                                        align_items: AlignItems::Center,
+                                        justify_content: JustifyContent::Center,
+                                        flex_direction: FlexDirection::Column,
+                                        ..default()
+                                    },
+                                    background_color: Color::WHITE.into(),
+                                    image: UiImage::new(
+                                        asset_server.load("ui/menu/mainMenuButton.png"),
+                                    ),
+                                    ..default()
+                                },
+                                MenuButtonAction::PlayDaily,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    TextBundle {
+                                        text: Text::from_section(
+                                            "Daily Challenge",
+                                            button_text_style.clone(),
+                                        ),
+                                        style: Style {
+                                            margin: UiRect::bottom(Val::Percent(5.0)),
+                                            ..default()
+                                        },
+                                        ..default()
+                                    },
+                                    MainMenuText,
+                                ));
+                            });
+                    }
+
                     parent
                         .spawn((
                             ButtonBundle {
@@ -331,6 +575,10 @@ pub fn menu_action(
     mut simulation_state_next_state: ResMut<NextState<SimulationState>>,
     mut app_exit_events: ResMut<Events<bevy::app::AppExit>>,
     mut restart_event_writer: EventWriter<Restart>,
+    mut start_daily_challenge_event_writer: EventWriter<StartDailyChallengeEvent>,
+    mut start_level_event_writer: EventWriter<StartLevelEvent>,
+    mut selected_map: ResMut<SelectedMap>,
+    mut adaptive_difficulty: ResMut<AdaptiveDifficulty>,
 ) {
     for (interaction, menu_button_action) in &interaction_query {
         if *interaction == Interaction::Pressed {
@@ -345,6 +593,29 @@ pub fn menu_action(
                     next_round_state.set(RoundState::InRound);
                     next_game_started_state.set(GameStartedState::GameHasStarted);
                 }
+                MenuButtonAction::PlayDaily => {
+                    start_daily_challenge_event_writer.send(StartDailyChallengeEvent);
+                    next_game_state.set(AppState::InGame);
+                    next_menu_state.set(MenuState::NotInTheMenu);
+                    next_round_state.set(RoundState::InRound);
+                    next_game_started_state.set(GameStartedState::GameHasStarted);
+                }
+                MenuButtonAction::Campaign => {
+                    next_menu_state.set(MenuState::LevelSelect);
+                }
+                MenuButtonAction::PlayLevel(level_index) => {
+                    start_level_event_writer.send(StartLevelEvent(*level_index));
+                    next_game_state.set(AppState::InGame);
+                    next_menu_state.set(MenuState::NotInTheMenu);
+                    next_round_state.set(RoundState::InRound);
+                    next_game_started_state.set(GameStartedState::GameHasStarted);
+                }
+                MenuButtonAction::NextMap => {
+                    selected_map.index = (selected_map.index + 1) % MAP_PATHS.len();
+                }
+                MenuButtonAction::ToggleAdaptiveDifficulty => {
+                    adaptive_difficulty.enabled = !adaptive_difficulty.enabled;
+                }
                 MenuButtonAction::HowToPlay => {
                     next_menu_state.set(MenuState::HowToPlayTransition);
                 }
@@ -395,11 +666,291 @@ pub fn spawn_in_game_menu(commands: Commands, asset_server: Res<AssetServer>) {
     spawn_menu(commands, asset_server, MenuType::InGameMenu);
 }
 
-/// Spawns the game lost menu
-pub fn spawn_lost_menu(commands: Commands, asset_server: Res<AssetServer>) {
+/// Spawns the game lost menu together with the seed of the run, such that it can be replayed.
+/// In the daily challenge the shareable result and the best score of the day are shown instead and
+/// in the campaign the level that was failed. Runs with adaptive difficulty are marked as such
+pub fn spawn_lost_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    game_rng: Res<GameRng>,
+    game_mode: Res<GameModeIndicator>,
+    daily_challenge: Res<DailyChallenge>,
+    current_level: Res<CurrentLevel>,
+    adaptive_difficulty: Res<AdaptiveDifficulty>,
+) {
+    let results_text = match (game_mode.mode, &daily_challenge.result) {
+        (GameMode::Daily, Some(result)) => format!(
+            "{}\nBest score today: {}",
+            result,
+            daily_challenge.best_score.unwrap_or_default()
+        ),
+        (GameMode::Campaign, _) => format!(
+            "Level failed: {}\nContinuing won't give any stars",
+            current_level.name
+        ),
+        (GameMode::Endless, _) if adaptive_difficulty.enabled => {
+            format!("Adaptive run - Seed: {}", game_rng.seed)
+        }
+        _ => format!("Seed: {}", game_rng.seed),
+    };
+    commands.spawn((
+        TextBundle::from_section(
+            results_text,
+            TextStyle {
+                font_size: 30.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            align_self: AlignSelf::FlexEnd,
+            justify_self: JustifySelf::Center,
+            margin: UiRect::bottom(Val::Percent(6.0)),
+            ..default()
+        })
+        .with_text_justify(JustifyText::Center),
+        MainMenuScreenUiElement,
+    ));
     spawn_menu(commands, asset_server, MenuType::LostMenu);
 }
 
+/// Spawns the level select screen with a button per campaign level showing its objectives and the
+/// stars reached. Locked levels can't be started
+pub fn spawn_level_select_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    campaign_handle: Res<CampaignHandle>,
+    campaigns: Res<Assets<Campaign>>,
+    campaign_progress: Res<CampaignProgress>,
+) {
+    let title_text_style = TextStyle {
+        font_size: 40.0,
+        ..default()
+    };
+    let level_text_style = TextStyle {
+        font_size: 18.0,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    position_type: PositionType::Absolute,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Percent(1.5),
+                    ..default()
+                },
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.6).into(),
+                ..default()
+            },
+            Name::new("Level select".to_string()),
+            LevelSelectScreenUiElement,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Campaign",
+                title_text_style.clone(),
+            ));
+
+            match campaigns.get(campaign_handle.0.id()) {
+                Some(campaign) => {
+                    for (level_index, level) in campaign.levels.iter().enumerate() {
+                        let unlocked = campaign_progress.is_unlocked(campaign, level_index);
+                        let mut level_text = format!(
+                            "{}. {} - {} rounds on {}\n",
+                            level_index + 1,
+                            level.name,
+                            level.rounds,
+                            level.difficulty
+                        );
+                        if unlocked {
+                            level_text += &format!(
+                                "Stars: {}/{}",
+                                campaign_progress.stars(&level.name),
+                                MAX_STARS
+                            );
+                            for objective in level.objectives.iter() {
+                                level_text += &format!("\n{objective}");
+                            }
+                        } else {
+                            level_text += "Locked: finish the previous level first";
+                        }
+
+                        let mut level_button = parent.spawn(ButtonBundle {
+                            style: Style {
+                                width: Val::Percent(45.0),
+                                min_height: Val::Percent(BUTTON_HEIGHT * 0.8),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                flex_direction: FlexDirection::Column,
+                                ..default()
+                            },
+                            background_color: if unlocked {
+                                Color::WHITE.into()
+                            } else {
+                                Color::GRAY.into()
+                            },
+                            image: UiImage::new(asset_server.load("ui/menu/mainMenuButton.png")),
+                            ..default()
+                        });
+                        // Locked levels don't get an action, such that they can't be pressed
+                        if unlocked {
+                            level_button.insert(MenuButtonAction::PlayLevel(level_index));
+                        }
+                        level_button.with_children(|parent| {
+                            parent.spawn((
+                                TextBundle {
+                                    text: Text::from_section(level_text, level_text_style.clone())
+                                        .with_justify(JustifyText::Center),
+                                    style: Style {
+                                        margin: UiRect::bottom(Val::Percent(5.0)),
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                MainMenuText,
+                            ));
+                        });
+                    }
+                }
+                None => {
+                    parent.spawn(TextBundle::from_section(
+                        "The campaign is still loading",
+                        level_text_style.clone(),
+                    ));
+                }
+            }
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            width: Val::Percent(20.0),
+                            height: Val::Percent(10.0),
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::Center,
+                            flex_direction: FlexDirection::Column,
+                            ..default()
+                        },
+                        background_color: Color::WHITE.into(),
+                        image: UiImage::new(asset_server.load("ui/menu/mainMenuButton.png")),
+                        ..default()
+                    },
+                    MenuButtonAction::Main,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle {
+                            text: Text::from_section("Back", title_text_style.clone()),
+                            style: Style {
+                                margin: UiRect::bottom(Val::Percent(5.0)),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        MainMenuText,
+                    ));
+                });
+        });
+}
+
+/// Spawns the screen shown once all rounds of a campaign level are survived with the stars
+/// awarded and the objectives met
+pub fn spawn_level_complete_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    current_level: Res<CurrentLevel>,
+) {
+    let Some(result) = &current_level.result else {
+        return;
+    };
+    let mut result_text = if result.stars == 0 {
+        format!("{}\nLevel failed", current_level.name)
+    } else {
+        format!(
+            "{} complete!\nStars: {}/{}",
+            current_level.name, result.stars, MAX_STARS
+        )
+    };
+    for (objective, met) in current_level.objectives.iter().zip(&result.objectives_met) {
+        result_text += &format!("\n[{}] {}", if *met { "x" } else { " " }, objective);
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    position_type: PositionType::Absolute,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Percent(3.0),
+                    ..default()
+                },
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.6).into(),
+                ..default()
+            },
+            Name::new("Level complete".to_string()),
+            MainMenuScreenUiElement,
+        ))
+        .with_children(|parent| {
+            parent.spawn(
+                TextBundle::from_section(
+                    result_text,
+                    TextStyle {
+                        font_size: 30.0,
+                        ..default()
+                    },
+                )
+                .with_text_justify(JustifyText::Center),
+            );
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            width: Val::Percent(30.0),
+                            height: Val::Percent(BUTTON_HEIGHT),
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::Center,
+                            flex_direction: FlexDirection::Column,
+                            ..default()
+                        },
+                        background_color: Color::WHITE.into(),
+                        image: UiImage::new(asset_server.load("ui/menu/mainMenuButton.png")),
+                        ..default()
+                    },
+                    MenuButtonAction::Restart,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle {
+                            text: Text::from_section(
+                                "Main Menu",
+                                TextStyle {
+                                    font_size: 40.0,
+                                    ..default()
+                                },
+                            ),
+                            style: Style {
+                                margin: UiRect::bottom(Val::Percent(5.0)),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        MainMenuText,
+                    ));
+                });
+        });
+}
+
 /// Handles changing all menu button colors based on mouse interaction
 pub fn menu_button_animations(
     mut interaction_query: Query<
@@ -770,8 +1321,13 @@ pub fn change_difficulty(
     mut query_text_in_settings_menu: Query<&mut Text, With<DifficultySettingsText>>,
     mut button_query: Query<(&mut UiImage, &SettingsButton), With<Button>>,
     asset_server: Res<AssetServer>,
+    game_mode: Res<GameModeIndicator>,
 ) {
     for difficulty_changed_event in difficulty_changed_event_reader.read() {
+        // The difficulty of the daily challenge and the campaign levels is fixed
+        if game_mode.mode != GameMode::Endless {
+            continue;
+        }
         difficulty.difficulty = match (difficulty_changed_event.0, &difficulty.difficulty) {
             (true, Difficulty::Easy) => Difficulty::Medium,
             (true, Difficulty::Medium) => Difficulty::Hard,
@@ -806,6 +1362,7 @@ pub fn spawn_how_to_play_screen(
     mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
     words_handle: Res<WordsHandle>,
     words: Res<Assets<Words>>,
+    mut game_rng: ResMut<GameRng>,
 ) {
     let how_to_play_text_style = TextStyle {
         font_size: 22.0,
@@ -868,8 +1425,6 @@ pub fn spawn_how_to_play_screen(
                         },
                     ));
 
-                    let mut rng = rand::thread_rng();
-
                     // Get random enemy sprite
                     let enemy_type: EnemyType = EnemyType::Mushroom;
                     let (enemy_name, sprite_width, sprite_height, animation_length) =
@@ -901,7 +1456,7 @@ pub fn spawn_how_to_play_screen(
                     // Get random word from list
                     let word_for_enemy = word
                         .vec_of_words
-                        .choose(&mut rng)
+                        .choose(game_rng.stream(RngStream::Menu))
                         .expect("The list of words shouldn't be empty");
 
                     parent.spawn( NodeBundle {
@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+use std::{fs, path::PathBuf};
+
+/// Directory (relative to the working directory) in which save files are stored
+const SAVE_DIRECTORY: &str = "saves";
+
+/// Returns the path of the save file with the given name
+fn save_file_path(name: &str) -> PathBuf {
+    PathBuf::from(SAVE_DIRECTORY).join(name)
+}
+
+/// Reads the save file with the given name. Returns None if there is no such file (yet).
+///
+/// Saving is not supported in the browser, there the progress only lasts for the session.
+pub fn read_save_file(name: &str) -> Option<String> {
+    fs::read_to_string(save_file_path(name)).ok()
+}
+
+/// Writes the given contents into the save file with the given name, replacing the old contents
+pub fn write_save_file(name: &str, contents: &str) {
+    if let Err(error) =
+        fs::create_dir_all(SAVE_DIRECTORY).and_then(|_| fs::write(save_file_path(name), contents))
+    {
+        warn!("Failed to write save file {name}: {error:?}");
+    }
+}
@@ -1,17 +1,41 @@
-use bevy::{prelude::*, window::PrimaryWindow};
+use bevy::{
+    prelude::*,
+    render::camera::ScalingMode,
+    window::{PrimaryWindow, WindowResized},
+};
 
-/// Spawns the camera
+use crate::{WORLD_HEIGHT, WORLD_WIDTH};
+
+/// Spawns the camera. The projection scales the world such that it always fits into the window
 pub fn spawn_camera(mut commands: Commands) {
-    commands.spawn(Camera2dBundle::default());
+    let mut camera_bundle = Camera2dBundle::default();
+    camera_bundle.projection.scaling_mode = ScalingMode::AutoMin {
+        min_width: WORLD_WIDTH,
+        min_height: WORLD_HEIGHT,
+    };
+    commands.spawn(camera_bundle);
 }
 
-/// Spawns the background asset
-pub fn spawn_background(mut commands: Commands, asset_server: Res<AssetServer>) {
-    commands.spawn((SpriteBundle {
-        transform: Transform::from_xyz(0.0, 0.0, -10.0),
-        texture: asset_server.load("background/background_new.png"),
-        ..default()
-    },));
+/// Scales the ui with the same factor as the world when the window is created, resized or switched
+/// between windowed and fullscreen. Castle, trees, routes and enemies are in world coordinates, so the
+/// camera projection keeps them in place relative to each other without any re-layout
+pub fn update_ui_scale_on_window_resize(
+    mut window_resized_event_reader: EventReader<WindowResized>,
+    window_query: Query<(Entity, Ref<Window>), With<PrimaryWindow>>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let Ok((primary_window, window)) = window_query.get_single() else {
+        return;
+    };
+    let primary_window_was_resized = window_resized_event_reader
+        .read()
+        .any(|event| event.window == primary_window);
+    if primary_window_was_resized || window.is_added() {
+        let scale = (window.width() / WORLD_WIDTH).min(window.height() / WORLD_HEIGHT);
+        if ui_scale.0 != scale {
+            ui_scale.0 = scale;
+        }
+    }
 }
 
 /// Enables the option to press F11 for toggling between window modes borderless fullscreen and windowed